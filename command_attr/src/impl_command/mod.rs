@@ -1,7 +1,8 @@
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote, ToTokens};
 use syn::spanned::Spanned;
-use syn::{parse2, Attribute, Error, FnArg, ItemFn, Path, Result, Type};
+use syn::{parse2, Attribute, Error, FnArg, ItemFn, Lit, Meta, NestedMeta, Result};
+use syn::{GenericArgument, PathArguments, ReturnType, Type};
 
 use crate::paths;
 use crate::utils::{self, AttributeArgs};
@@ -13,18 +14,24 @@ use options::Options;
 pub fn impl_command(attr: TokenStream, input: TokenStream) -> Result<TokenStream> {
     let mut fun = parse2::<ItemFn>(input)?;
 
-    let names = if attr.is_empty() {
-        vec![fun.sig.ident.to_string()]
-    } else {
-        parse2::<AttributeArgs>(attr)?.0
-    };
+    let AttributeArgs {
+        mut names,
+        builder,
+        auto_respond,
+        collect_errors,
+    } = parse2::<AttributeArgs>(attr)?;
+
+    if names.is_empty() {
+        names.push(fun.sig.ident.to_string());
+    }
 
     let (ctx_name, msg_name, data, error) = utils::parse_generics(&fun.sig)?;
-    let options = Options::parse(&mut fun.attrs)?;
+    let mut options = Options::parse(&mut fun.attrs)?;
+    options.collect_errors = collect_errors;
 
-    parse_arguments(ctx_name, msg_name, &mut fun, &options)?;
+    let arguments = parse_arguments(ctx_name, msg_name, &error, &mut fun, &options, auto_respond)?;
 
-    let builder_fn = builder_fn(&data, &error, &mut fun, names, &options);
+    let builder_fn = builder_fn(&data, &error, &mut fun, names, builder, &options, &arguments);
 
     let hook_macro = paths::hook_macro();
 
@@ -44,16 +51,19 @@ fn builder_fn(
     error: &Type,
     function: &mut ItemFn,
     mut names: Vec<String>,
+    builder: Option<Ident>,
     options: &Options,
+    arguments: &[Argument],
 ) -> TokenStream {
     let name = names.remove(0);
     let aliases = names;
 
-    // Derive the name of the builder from the command function.
+    // Derive the name of the builder from the command function, unless it is
+    // given explicitly.
     // Prepend the command function's name with an underscore to avoid name
     // collisions.
-    let builder_name = function.sig.ident.clone();
-    let function_name = format_ident!("_{}", builder_name);
+    let builder_name = builder.unwrap_or_else(|| function.sig.ident.clone());
+    let function_name = format_ident!("_{}", function.sig.ident);
     function.sig.ident = function_name.clone();
 
     let command_builder = paths::command_builder_type();
@@ -62,24 +72,187 @@ fn builder_fn(
     let vis = &function.vis;
     let external = &function.attrs;
 
+    let (parser_fn, parser) = if arguments.is_empty() && !options.deny_extra_args {
+        (TokenStream::new(), TokenStream::new())
+    } else {
+        let parser_name = format_ident!("__parse_arguments");
+        let parser_fn = argument_parser_fn(&parser_name, arguments, options);
+
+        (parser_fn, quote!(.argument_parser(#parser_name)))
+    };
+
+    let arguments = arguments.iter().map(Argument::info);
+
     quote! {
         #(#external)*
         #vis fn #builder_name() -> #command {
+            #parser_fn
+
             #command_builder::new(#name)
-                #(.name(#aliases))*
+                #(.alias(#aliases))*
                 .function(#function_name)
+                #(.argument(#arguments))*
+                #parser
                 #options
                 .build()
         }
     }
 }
 
+/// Generates a function that parses the arguments of the command without running it.
+///
+/// Only the arguments given in the content of the message are parsed.
+fn argument_parser_fn(name: &Ident, arguments: &[Argument], options: &Options) -> TokenStream {
+    let hook_macro = paths::hook_macro();
+    let serenity_ctx = paths::serenity_context_type();
+    let message = paths::message_type();
+    let asegsty = paths::argument_segments_type();
+    let parse_ctx = paths::parse_context_type();
+    let dispatch_error = paths::dispatch_error_type();
+
+    let ctx_expr = quote!(__serenity_ctx);
+
+    let arguments = arguments
+        .iter()
+        .enumerate()
+        .filter(|(_, arg)| !arg.parser.type_.is_message_data())
+        .collect::<Vec<_>>();
+    // The values are discarded, so bind them to names that do not trigger warnings.
+    let bindings = arguments.iter().map(|(i, _)| format_ident!("__arg{}", i)).collect::<Vec<_>>();
+    let argument_bindings = argument_bindings(
+        &ctx_expr,
+        arguments.iter().map(|(i, arg)| (*i, *arg)),
+        &bindings,
+        options.collect_errors,
+    );
+
+    let extra_args_check = extra_args_check(arguments.iter().map(|(_, arg)| *arg), options);
+
+    quote! {
+        #[#hook_macro]
+        #[allow(unused_mut)]
+        async fn #name(
+            __serenity_ctx: &#serenity_ctx,
+            __msg: &#message,
+            mut __args: #asegsty<'fut>,
+        ) -> std::result::Result<(), #dispatch_error> {
+            let __parse_ctx = #parse_ctx::from(__msg);
+
+            #argument_bindings
+
+            #extra_args_check
+
+            Ok(())
+        }
+    }
+}
+
+/// Generates the bindings of the arguments to the names.
+///
+/// If `collect_errors` is enabled, a failure of an argument is recorded rather than
+/// returned, and all failures are returned together after every argument was parsed.
+fn argument_bindings<'a, I>(
+    serenity_ctx: &TokenStream,
+    arguments: I,
+    names: &[Ident],
+    collect_errors: bool,
+) -> TokenStream
+where
+    I: IntoIterator<Item = (usize, &'a Argument)>,
+{
+    let arguments = arguments.into_iter().collect::<Vec<_>>();
+    let tys = arguments.iter().map(|(_, arg)| &arg.ty).collect::<Vec<_>>();
+
+    if !collect_errors {
+        let calls = arguments.iter().map(|(i, arg)| arg.parser.call(serenity_ctx, &arg.name, *i));
+
+        return quote! {
+            #(let #names: #tys = #calls;)*
+        };
+    }
+
+    let dispatch_error = paths::dispatch_error_type();
+    let calls =
+        arguments.iter().map(|(i, arg)| arg.parser.collect_call(serenity_ctx, &arg.name, *i));
+
+    quote! {
+        let mut __failures = Vec::new();
+
+        #(let #names: std::option::Option<#tys> = #calls;)*
+
+        // An argument is only missing if its failure was recorded.
+        #(
+            let #names = match #names {
+                Some(#names) => #names,
+                None => return Err(#dispatch_error::ArgumentErrors(__failures).into()),
+            };
+        )*
+    }
+}
+
+/// Generates the rejection of arguments in excess of the arguments of the command,
+/// if the command denies them.
+fn extra_args_check<'a, I>(arguments: I, options: &Options) -> TokenStream
+where
+    I: IntoIterator<Item = &'a Argument>,
+{
+    // Variadic and rest arguments consume all remaining segments, leaving nothing
+    // in excess.
+    let deny_extra_args = options.deny_extra_args
+        && !arguments.into_iter().any(|arg| {
+            matches!(
+                arg.parser.type_.ordering(),
+                Some(ArgumentOrdering::Variadic) | Some(ArgumentOrdering::Rest)
+            )
+        });
+
+    if deny_extra_args {
+        let no_extra_arguments = paths::no_extra_arguments_func();
+
+        quote! {
+            #no_extra_arguments(&__args).map_err(|err| err.into_dispatch_error("", 0))?;
+        }
+    } else {
+        TokenStream::new()
+    }
+}
+
+/// Generates the rejection of unterminated quotation marks in the arguments if
+/// the configuration enables strict quotes.
+///
+/// Nothing is generated if none of the arguments are parsed from the content.
+fn quotes_check(ctx_name: &Ident, arguments: &[Argument]) -> TokenStream {
+    if arguments.iter().all(|arg| arg.parser.type_.is_message_data()) {
+        return TokenStream::new();
+    }
+
+    let terminated_quotes = paths::terminated_quotes_func();
+
+    quote! {
+        if #ctx_name.strict_quotes {
+            #terminated_quotes(&__args).map_err(|err| err.into_dispatch_error("", 0))?;
+        }
+    }
+}
+
+/// Generates the parsing of the arguments of the command before its body is run.
+///
+/// The function is changed to return the framework's error type. A failure to
+/// parse the arguments is returned as a dispatch error, whereas the result of
+/// the body is returned as a user error.
+///
+/// If `auto_respond` is enabled, the value returned by the body is converted into
+/// a response and sent to the channel of the message.
+///
+/// The arguments are removed from the function and returned.
 fn parse_arguments(
     ctx_name: Ident,
     msg_name: Ident,
+    error: &Type,
     function: &mut ItemFn,
     options: &Options,
-) -> Result<()> {
+    auto_respond: bool,
+) -> Result<Vec<Argument>> {
     let mut arguments = Vec::new();
 
     while function.sig.inputs.len() > 2 {
@@ -88,40 +261,126 @@ fn parse_arguments(
         arguments.push(Argument::new(argument)?);
     }
 
-    if !arguments.is_empty() {
-        arguments.reverse();
+    arguments.reverse();
+
+    check_arguments(&arguments)?;
 
-        check_arguments(&arguments)?;
+    // Attachment, sticker and embed arguments bind the attachments, stickers and embeds
+    // of the message in the order of their declaration.
+    let mut attachment_index = 0usize;
+    let mut sticker_index = 0usize;
+    let mut embed_index = 0usize;
 
-        let delimiter = options.delimiter.as_ref().map_or(" ", String::as_str);
-        let asegsty = paths::argument_segments_type();
+    for arg in &mut arguments {
+        let index = match arg.parser.type_ {
+            ArgumentType::Attachment | ArgumentType::OptionalAttachment => &mut attachment_index,
+            ArgumentType::Sticker | ArgumentType::OptionalSticker => &mut sticker_index,
+            ArgumentType::Embed | ArgumentType::OptionalEmbed => &mut embed_index,
+            _ => continue,
+        };
+
+        arg.parser.inputs.push(quote!(#index));
+        *index += 1;
+    }
 
-        let b = &function.block;
+    let asegsty = paths::argument_segments_type();
+    let parse_ctx = paths::parse_context_type();
 
-        let argument_names = arguments.iter().map(|arg| &arg.name).collect::<Vec<_>>();
-        let argument_tys = arguments.iter().map(|arg| &arg.ty).collect::<Vec<_>>();
-        let argument_parsers = arguments.iter().map(|arg| &arg.parser).collect::<Vec<_>>();
+    let ctx_expr = quote!(&#ctx_name.serenity_ctx);
 
-        function.block = parse2(quote! {{
+    let argument_names = arguments.iter().map(|arg| arg.name.clone()).collect::<Vec<_>>();
+    let argument_bindings = argument_bindings(
+        &ctx_expr,
+        arguments.iter().enumerate(),
+        &argument_names,
+        options.collect_errors,
+    );
+
+    let extra_args_check = extra_args_check(&arguments, options);
+    let quotes_check = quotes_check(&ctx_name, &arguments);
+
+    let parsing = if arguments.is_empty() && extra_args_check.is_empty() {
+        TokenStream::new()
+    } else if arguments.is_empty() {
+        quote! {
+            {
+                let __args = #asegsty::with_tokenizer(&#ctx_name.args, &#ctx_name.delimiter, #ctx_name.tokenizer);
+
+                #extra_args_check
+            }
+        }
+    } else {
+        quote! {
             let (#(#argument_names),*) = {
                 // Place the segments into its scope to allow mutation of `Context::args`
                 // afterwards, as `ArgumentSegments` holds a reference to the source string.
-                let mut __args = #asegsty::new(&#ctx_name.args, #delimiter);
+                let mut __args = #asegsty::with_tokenizer(&#ctx_name.args, &#ctx_name.delimiter, #ctx_name.tokenizer);
+                let __parse_ctx = #parse_ctx::from(#msg_name);
 
-                #(let #argument_names: #argument_tys = #argument_parsers(
-                    &#ctx_name.serenity_ctx,
-                    &#msg_name,
-                    &mut __args
-                ).await?;)*
+                #quotes_check
+
+                #argument_bindings
+
+                #extra_args_check
 
                 (#(#argument_names),*)
             };
+        }
+    };
 
-            #b
-        }})?;
-    }
+    let b = &function.block;
 
-    Ok(())
+    let error_type = paths::error_type(error);
+    let user_error = paths::user_error_variant();
+
+    // Run the body in its own future, so that `return` and `?` in the body
+    // produce the result of the command rather than the framework's error.
+    // Commands that return nothing cannot fail.
+    function.block = match &function.sig.output {
+        ReturnType::Default if auto_respond => {
+            return Err(Error::new(
+                function.sig.span(),
+                "a command with `auto_respond` must return a `CommandResult` of a response",
+            ));
+        },
+        ReturnType::Type(_, output) if auto_respond => {
+            let into_response = paths::into_response_func();
+            let send_response = paths::send_response_func();
+
+            parse2(quote! {{
+                #parsing
+
+                // The context is moved into the body, but sending the response requires
+                // access to Serenity's context.
+                let __serenity_ctx = #ctx_name.serenity_ctx.clone();
+                let __result: #output = async move #b.await;
+                let __response = #into_response(__result.map_err(#user_error)?);
+
+                #send_response(__response, &__serenity_ctx, #msg_name)
+                    .await
+                    .map_err(|err| #user_error(std::convert::From::from(err)))?;
+
+                Ok(())
+            }})?
+        },
+        ReturnType::Default => parse2(quote! {{
+            #parsing
+
+            async move #b.await;
+
+            Ok(())
+        }})?,
+        ReturnType::Type(_, output) => parse2(quote! {{
+            #parsing
+
+            let __result: #output = async move #b.await;
+
+            __result.map_err(#user_error)
+        }})?,
+    };
+    function.sig.output = parse2(quote!(-> std::result::Result<(), #error_type>))?;
+
+    Ok(arguments)
 }
 
 /// Returns a result indicating whether the list of arguments is valid.
@@ -134,77 +393,80 @@ fn parse_arguments(
 /// - a list of arguments that only has one rest argument parameter, if present.
 /// - a list of arguments that only has one variadic argument parameter or one rest
 /// argument parameter.
+///
+/// Attachment, sticker and embed arguments are not parsed from the content of the
+/// message, and thus may appear anywhere in the list.
 fn check_arguments(args: &[Argument]) -> Result<()> {
-    let mut last_arg: Option<&Argument> = None;
+    let mut last_arg: Option<(&Argument, ArgumentOrdering)> = None;
 
-    for arg in args {
-        if let Some(last_arg) = last_arg {
-            match (last_arg.parser.type_, arg.parser.type_) {
-                (ArgumentType::Optional, ArgumentType::Required) => {
+    for (arg, ordering) in args.iter().filter_map(|arg| Some((arg, arg.parser.type_.ordering()?))) {
+        if let Some((last_arg, last_ordering)) = last_arg {
+            match (last_ordering, ordering) {
+                (ArgumentOrdering::Optional, ArgumentOrdering::Required) => {
                     return Err(Error::new(
                         last_arg.name.span(),
                         "optional argument cannot precede a required argument",
                     ));
                 },
-                (ArgumentType::Variadic, ArgumentType::Required) => {
+                (ArgumentOrdering::Variadic, ArgumentOrdering::Required) => {
                     return Err(Error::new(
                         last_arg.name.span(),
                         "variadic argument cannot precede a required argument",
                     ));
                 },
-                (ArgumentType::Variadic, ArgumentType::Optional) => {
+                (ArgumentOrdering::Variadic, ArgumentOrdering::Optional) => {
                     return Err(Error::new(
                         last_arg.name.span(),
                         "variadic argument cannot precede an optional argument",
                     ));
                 },
-                (ArgumentType::Rest, ArgumentType::Required) => {
+                (ArgumentOrdering::Rest, ArgumentOrdering::Required) => {
                     return Err(Error::new(
                         last_arg.name.span(),
                         "rest argument cannot precede a required argument",
                     ));
                 },
-                (ArgumentType::Rest, ArgumentType::Optional) => {
+                (ArgumentOrdering::Rest, ArgumentOrdering::Optional) => {
                     return Err(Error::new(
                         last_arg.name.span(),
                         "rest argument cannot precede an optional argument",
                     ));
                 },
-                (ArgumentType::Rest, ArgumentType::Variadic) => {
+                (ArgumentOrdering::Rest, ArgumentOrdering::Variadic) => {
                     return Err(Error::new(
                         last_arg.name.span(),
                         "a rest argument cannot be used alongside a variadic argument",
                     ));
                 },
-                (ArgumentType::Variadic, ArgumentType::Rest) => {
+                (ArgumentOrdering::Variadic, ArgumentOrdering::Rest) => {
                     return Err(Error::new(
                         last_arg.name.span(),
                         "a variadic argument cannot be used alongside a rest argument",
                     ));
                 },
-                (ArgumentType::Variadic, ArgumentType::Variadic) => {
+                (ArgumentOrdering::Variadic, ArgumentOrdering::Variadic) => {
                     return Err(Error::new(
                         arg.name.span(),
                         "a command cannot have two variadic argument parameters",
                     ));
                 },
-                (ArgumentType::Rest, ArgumentType::Rest) => {
+                (ArgumentOrdering::Rest, ArgumentOrdering::Rest) => {
                     return Err(Error::new(
                         arg.name.span(),
                         "a command cannot have two rest argument parameters",
                     ));
                 },
-                (ArgumentType::Required, ArgumentType::Required)
-                | (ArgumentType::Optional, ArgumentType::Optional)
-                | (ArgumentType::Required, ArgumentType::Optional)
-                | (ArgumentType::Required, ArgumentType::Variadic)
-                | (ArgumentType::Optional, ArgumentType::Variadic)
-                | (ArgumentType::Required, ArgumentType::Rest)
-                | (ArgumentType::Optional, ArgumentType::Rest) => {},
+                (ArgumentOrdering::Required, ArgumentOrdering::Required)
+                | (ArgumentOrdering::Optional, ArgumentOrdering::Optional)
+                | (ArgumentOrdering::Required, ArgumentOrdering::Optional)
+                | (ArgumentOrdering::Required, ArgumentOrdering::Variadic)
+                | (ArgumentOrdering::Optional, ArgumentOrdering::Variadic)
+                | (ArgumentOrdering::Required, ArgumentOrdering::Rest)
+                | (ArgumentOrdering::Optional, ArgumentOrdering::Rest) => {},
             };
         }
 
-        last_arg = Some(arg);
+        last_arg = Some((arg, ordering));
     }
 
     Ok(())
@@ -224,8 +486,7 @@ impl Argument {
 
         let ty = binding.ty.clone();
 
-        let path = utils::get_path(&ty)?;
-        let parser = ArgumentParser::new(&binding.attrs, path)?;
+        let parser = ArgumentParser::new(&binding.attrs, &ty)?;
 
         Ok(Self {
             name,
@@ -233,70 +494,528 @@ impl Argument {
             parser,
         })
     }
+
+    /// Returns the construction of the information about this argument.
+    fn info(&self) -> TokenStream {
+        let info = paths::argument_info_type();
+        let kind = paths::argument_kind_type();
+
+        let name = self.name.to_string();
+        let variant = format_ident!("{}", self.parser.type_.kind());
+
+        // The values of optional and variadic arguments are the elements of their type.
+        let ty = match self.parser.type_ {
+            ArgumentType::Optional
+            | ArgumentType::SkippableOptional
+            | ArgumentType::OptionalAttachment
+            | ArgumentType::Variadic
+            | ArgumentType::LenientVariadic
+            | ArgumentType::BoundedVariadic
+            | ArgumentType::Attachments
+            | ArgumentType::OptionalSticker
+            | ArgumentType::Stickers
+            | ArgumentType::OptionalEmbed
+            | ArgumentType::Embeds => inner_type(&self.ty),
+            _ => &self.ty,
+        };
+        let type_name = type_name(ty);
+
+        quote!(#info::new(#name, #kind::#variant, #type_name))
+    }
+}
+
+/// Returns the first generic type of a type, such as `T` of `Option<T>`.
+///
+/// The type is returned as is if it has no generic type.
+fn inner_type(ty: &Type) -> &Type {
+    let path = match ty {
+        Type::Path(p) => &p.path,
+        _ => return ty,
+    };
+
+    match &path.segments.last().unwrap().arguments {
+        PathArguments::AngleBracketed(arguments) => arguments
+            .args
+            .iter()
+            .find_map(|arg| match arg {
+                GenericArgument::Type(t) => Some(t),
+                _ => None,
+            })
+            .unwrap_or(ty),
+        _ => ty,
+    }
+}
+
+/// Returns the name of a type as it would be written in source code.
+fn type_name(ty: &Type) -> String {
+    let mut name = ty.to_token_stream().to_string();
+
+    for (from, to) in &[(" < ", "<"), ("< ", "<"), (" >", ">"), (" :: ", "::"), (" ,", ",")] {
+        name = name.replace(from, to);
+    }
+
+    name
 }
 
 #[derive(Clone, Copy)]
 enum ArgumentType {
     Required,
     Optional,
+    SkippableOptional,
     Variadic,
+    LenientVariadic,
+    BoundedVariadic,
     Rest,
+    RequiredRest,
+    List,
+    Pair,
+    Emoji,
+    Timestamp,
+    Quantity,
+    Range,
+    Bool,
+    Member,
+    Attachment,
+    OptionalAttachment,
+    Attachments,
+    Sticker,
+    OptionalSticker,
+    Stickers,
+    Embed,
+    OptionalEmbed,
+    Embeds,
 }
 
+impl ArgumentType {
+    /// Returns the type as which this type is ordered among other arguments, or `None`
+    /// if it is not parsed from the content of the message.
+    fn ordering(self) -> Option<ArgumentOrdering> {
+        Some(match self {
+            // Lists, pairs, emojis, timestamps, quantities, ranges, booleans and members consume
+            // exactly one segment, like required arguments.
+            ArgumentType::Required
+            | ArgumentType::List
+            | ArgumentType::Pair
+            | ArgumentType::Emoji
+            | ArgumentType::Timestamp
+            | ArgumentType::Quantity
+            | ArgumentType::Range
+            | ArgumentType::Bool
+            | ArgumentType::Member => ArgumentOrdering::Required,
+            // Skippable optional arguments consume at most one segment, like optional arguments.
+            ArgumentType::Optional | ArgumentType::SkippableOptional => ArgumentOrdering::Optional,
+            // Lenient and bounded variadic arguments consume all remaining segments, like
+            // variadic arguments.
+            ArgumentType::Variadic
+            | ArgumentType::LenientVariadic
+            | ArgumentType::BoundedVariadic => ArgumentOrdering::Variadic,
+            // Required rest arguments consume all remaining segments, like rest arguments.
+            ArgumentType::Rest | ArgumentType::RequiredRest => ArgumentOrdering::Rest,
+            ArgumentType::Attachment
+            | ArgumentType::OptionalAttachment
+            | ArgumentType::Attachments
+            | ArgumentType::Sticker
+            | ArgumentType::OptionalSticker
+            | ArgumentType::Stickers
+            | ArgumentType::Embed
+            | ArgumentType::OptionalEmbed
+            | ArgumentType::Embeds => return None,
+        })
+    }
+
+    /// Returns the variant of this type that matches the type of the argument, such as
+    /// an optional argument for an `Option`.
+    fn refine(self, ty: &Type) -> Result<Self> {
+        let (optional, variadic) = match self {
+            ArgumentType::Required => (ArgumentType::Optional, ArgumentType::Variadic),
+            ArgumentType::Attachment => (ArgumentType::OptionalAttachment, self),
+            ArgumentType::Sticker => (ArgumentType::OptionalSticker, ArgumentType::Stickers),
+            ArgumentType::Embed => (ArgumentType::OptionalEmbed, ArgumentType::Embeds),
+            _ => return Ok(self),
+        };
+
+        let path = utils::get_path(ty)?;
+
+        Ok(match path.segments.last().unwrap().ident.to_string().as_str() {
+            "Option" => optional,
+            "Vec" => variadic,
+            _ => self,
+        })
+    }
+
+    /// Returns the name of the variant of `ArgumentKind` that describes this type.
+    fn kind(self) -> &'static str {
+        match self.ordering() {
+            Some(ArgumentOrdering::Required) => "Required",
+            Some(ArgumentOrdering::Optional) => "Optional",
+            Some(ArgumentOrdering::Variadic) => "Variadic",
+            Some(ArgumentOrdering::Rest) if matches!(self, ArgumentType::RequiredRest) => {
+                "RequiredRest"
+            },
+            Some(ArgumentOrdering::Rest) => "Rest",
+            None => match self {
+                ArgumentType::Sticker | ArgumentType::OptionalSticker | ArgumentType::Stickers => {
+                    "Sticker"
+                },
+                ArgumentType::Embed | ArgumentType::OptionalEmbed | ArgumentType::Embeds => "Embed",
+                _ => "Attachment",
+            },
+        }
+    }
+
+    /// Returns a boolean indicating whether this type binds attachments, stickers or
+    /// embeds of the message.
+    fn is_message_data(self) -> bool {
+        self.ordering().is_none()
+    }
+}
+
+/// The order in which arguments consume the segments of the content of the message.
 #[derive(Clone, Copy)]
+enum ArgumentOrdering {
+    Required,
+    Optional,
+    Variadic,
+    Rest,
+}
+
+/// The input of the `variadic` attribute.
+enum VariadicOption {
+    /// `#[variadic(skip_invalid)]`
+    SkipInvalid,
+    /// `#[variadic(max = ...)]`
+    Max(usize),
+}
+
+/// The attributes that determine the kind of an argument without accepting any input,
+/// and the type of the argument they determine.
+///
+/// An argument may only have one of these attributes, or one of `rest`, `list` or
+/// `variadic`. The values of these arguments are not parsed with the `Parse` trait.
+const FLAG_KIND_ATTRIBUTES: &[(&str, ArgumentType)] = &[
+    ("emoji", ArgumentType::Emoji),
+    ("timestamp", ArgumentType::Timestamp),
+    ("quantity", ArgumentType::Quantity),
+    ("range", ArgumentType::Range),
+    ("bool", ArgumentType::Bool),
+    ("member", ArgumentType::Member),
+    ("attachment", ArgumentType::Attachment),
+    ("attachments", ArgumentType::Attachments),
+    ("sticker", ArgumentType::Sticker),
+    ("embed", ArgumentType::Embed),
+];
+
+/// Records the attribute that determines the kind of an argument, returning an error
+/// if the kind was already determined by another attribute.
+fn set_kind(kind: &mut Option<&'static str>, name: &'static str, span: Span) -> Result<()> {
+    match *kind {
+        Some(other) if other == name => {
+            Err(Error::new(span, format!("the `{}` attribute may only be given once", name)))
+        },
+        Some(other) => Err(Error::new(
+            span,
+            format!("the `{}` attribute cannot be combined with the `{}` attribute", name, other),
+        )),
+        None => {
+            *kind = Some(name);
+            Ok(())
+        },
+    }
+}
+
+/// Returns the error for a `variadic` attribute that conflicts with a previous one.
+fn conflicting_variadic_options(attr: &Attribute) -> Error {
+    Error::new(
+        attr.span(),
+        "the `skip_invalid` and `max` options of the `variadic` attribute cannot be combined",
+    )
+}
+
+/// Parses the input of the `list` attribute into the separator of the items.
+///
+/// The separator is `,` by default. It may be given as `#[list(sep = "...")]`, or in
+/// short as `#[list("...")]` or `#[list = "..."]`.
+fn parse_list_separator(attr: &Attribute) -> Result<String> {
+    let lit = match attr.parse_meta()? {
+        Meta::Path(_) => return Ok(",".to_string()),
+        Meta::NameValue(nv) => Some(nv.lit),
+        Meta::List(list) if list.nested.len() == 1 => match list.nested.into_iter().next() {
+            Some(NestedMeta::Lit(lit)) => Some(lit),
+            Some(NestedMeta::Meta(Meta::NameValue(nv))) if nv.path.is_ident("sep") => Some(nv.lit),
+            _ => None,
+        },
+        Meta::List(_) => None,
+    };
+
+    match lit {
+        Some(Lit::Str(sep)) if sep.value().is_empty() => {
+            Err(Error::new(sep.span(), "the separator must not be empty"))
+        },
+        Some(Lit::Str(sep)) => Ok(sep.value()),
+        Some(lit) => Err(Error::new(lit.span(), "argument must be a string")),
+        None => Err(Error::new(
+            attr.span(),
+            "the `list` attribute only accepts `sep = \"...\"` as input",
+        )),
+    }
+}
+
+/// Parses the input of the `optional` attribute, `#[optional(skip = "...")]`, into the
+/// sentinel that skips the argument.
+fn parse_skip_sentinel(attr: &Attribute) -> Result<String> {
+    if let Meta::List(list) = attr.parse_meta()? {
+        if list.nested.len() == 1 {
+            if let NestedMeta::Meta(Meta::NameValue(nv)) = &list.nested[0] {
+                if nv.path.is_ident("skip") {
+                    return match &nv.lit {
+                        Lit::Str(skip) if skip.value().is_empty() => {
+                            Err(Error::new(skip.span(), "the sentinel must not be empty"))
+                        },
+                        Lit::Str(skip) => Ok(skip.value()),
+                        lit => Err(Error::new(lit.span(), "argument must be a string")),
+                    };
+                }
+            }
+        }
+    }
+
+    Err(Error::new(attr.span(), "the `optional` attribute only accepts `skip = \"...\"` as input"))
+}
+
+impl VariadicOption {
+    fn parse(attr: &Attribute) -> Result<Self> {
+        if let Meta::List(list) = attr.parse_meta()? {
+            if list.nested.len() == 1 {
+                match &list.nested[0] {
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip_invalid") => {
+                        return Ok(VariadicOption::SkipInvalid);
+                    },
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("max") => {
+                        let max = match &nv.lit {
+                            Lit::Int(max) => max.base10_parse::<usize>()?,
+                            lit => {
+                                return Err(Error::new(lit.span(), "argument must be an integer"))
+                            },
+                        };
+
+                        if max == 0 {
+                            return Err(Error::new(nv.lit.span(), "the maximum must be positive"));
+                        }
+
+                        return Ok(VariadicOption::Max(max));
+                    },
+                    _ => {},
+                }
+            }
+        }
+
+        Err(Error::new(
+            attr.span(),
+            "the `variadic` attribute only accepts `skip_invalid` or `max = ...` as input",
+        ))
+    }
+}
+
+#[derive(Clone)]
 struct ArgumentParser {
     type_: ArgumentType,
     use_parse_trait: bool,
+    /// Additional inputs to the parser function, passed after the segments.
+    inputs: Vec<TokenStream>,
 }
 
 impl ArgumentParser {
-    fn new(attrs: &[Attribute], path: &Path) -> Result<Self> {
+    fn new(attrs: &[Attribute], ty: &Type) -> Result<Self> {
         let mut is_rest_argument = false;
+        let mut is_required_rest_argument = false;
+        let mut is_lenient_variadic_argument = false;
+        let mut max_variadic_arguments = None;
+        let mut skip_sentinel = None;
         let mut use_parse_trait = false;
+        let mut list_separator = None;
+        let mut flag_kind = None;
+        let mut kind = None;
+
         for attr in attrs {
-            let attr = utils::parse_attribute(attr)?;
+            if attr.path.is_ident("variadic") {
+                match VariadicOption::parse(attr)? {
+                    VariadicOption::SkipInvalid if max_variadic_arguments.is_some() => {
+                        return Err(conflicting_variadic_options(attr));
+                    },
+                    VariadicOption::Max(_) if is_lenient_variadic_argument => {
+                        return Err(conflicting_variadic_options(attr));
+                    },
+                    VariadicOption::SkipInvalid => is_lenient_variadic_argument = true,
+                    VariadicOption::Max(max) => max_variadic_arguments = Some(max),
+                }
 
-            if attr.path.is_ident("rest") {
-                is_rest_argument = true;
+                set_kind(&mut kind, "variadic", attr.span())?;
+
+                continue;
+            }
 
+            if attr.path.is_ident("optional") {
+                skip_sentinel = Some(parse_skip_sentinel(attr)?);
+
+                continue;
+            }
+
+            if attr.path.is_ident("list") {
+                list_separator = Some(parse_list_separator(attr)?);
+                set_kind(&mut kind, "list", attr.span())?;
+
+                continue;
+            }
+
+            let span = attr.span();
+            let attr = utils::parse_attribute(attr)?;
+
+            if let Some(&(name, type_)) =
+                FLAG_KIND_ATTRIBUTES.iter().find(|(name, _)| attr.path.is_ident(name))
+            {
                 if !attr.values.is_empty() {
                     return Err(Error::new(
-                        attrs[0].span(),
-                        "the `rest` attribute does not accept any input",
+                        span,
+                        format!("the `{}` attribute does not accept any input", name),
                     ));
                 }
+
+                set_kind(&mut kind, name, span)?;
+                flag_kind = Some((name, type_));
+            } else if attr.path.is_ident("rest") {
+                set_kind(&mut kind, "rest", span)?;
+                is_rest_argument = true;
+
+                if !attr.values.is_empty() {
+                    match utils::parse_identifiers(&attr)?.as_slice() {
+                        [ident] if ident == "required" => is_required_rest_argument = true,
+                        _ => {
+                            return Err(Error::new(
+                                span,
+                                "the `rest` attribute only accepts `required` as input",
+                            ));
+                        },
+                    }
+                }
             } else if attr.path.is_ident("parse") {
                 use_parse_trait = true;
 
                 if !attr.values.is_empty() {
                     return Err(Error::new(
-                        attrs[0].span(),
+                        span,
                         "the `parse` attribute does not accept any input",
                     ));
                 }
             } else {
                 return Err(Error::new(
-                    attrs[0].span(),
-                    "invalid attribute name, expected `rest` or `parse`",
+                    span,
+                    "invalid attribute name, expected `rest`, `variadic`, `optional`, `parse`, \
+                     `list`, `emoji`, `timestamp`, `quantity`, `range`, `bool`, `member`, \
+                     `attachment`, `attachments`, `sticker` or `embed`",
                 ));
             }
         }
 
-        let type_ = if is_rest_argument {
+        if let (true, Some((name, _))) = (use_parse_trait, flag_kind) {
+            let parse = attrs.iter().find(|attr| attr.path.is_ident("parse")).unwrap();
+
+            return Err(Error::new(
+                parse.span(),
+                format!("the `parse` attribute cannot be combined with the `{}` attribute", name),
+            ));
+        }
+
+        let mut inputs = Vec::new();
+
+        let type_ = if let Some((_, type_)) = flag_kind {
+            type_.refine(ty)?
+        } else if is_lenient_variadic_argument {
+            ArgumentType::LenientVariadic
+        } else if let Some(max) = max_variadic_arguments {
+            inputs.push(quote!(#max));
+
+            ArgumentType::BoundedVariadic
+        } else if is_required_rest_argument {
+            ArgumentType::RequiredRest
+        } else if is_rest_argument {
             ArgumentType::Rest
-        } else {
-            match path.segments.last().unwrap().ident.to_string().as_str() {
-                "Option" => ArgumentType::Optional,
-                "Vec" => ArgumentType::Variadic,
-                _ => ArgumentType::Required,
+        } else if let Some(separator) = list_separator {
+            inputs.push(quote!(#separator));
+
+            match ty {
+                Type::Tuple(tuple) if tuple.elems.len() == 2 => ArgumentType::Pair,
+                Type::Tuple(tuple) => {
+                    return Err(Error::new(
+                        tuple.span(),
+                        "a tuple of a list argument must have two elements",
+                    ));
+                },
+                _ => ArgumentType::List,
             }
+        } else {
+            ArgumentType::Required.refine(ty)?
+        };
+
+        let type_ = match (type_, skip_sentinel) {
+            (ArgumentType::Optional, Some(skip)) => {
+                inputs.push(quote!(#skip));
+
+                ArgumentType::SkippableOptional
+            },
+            (_, Some(_)) => {
+                return Err(Error::new(
+                    ty.span(),
+                    "the `optional` attribute is only applicable to `Option` arguments",
+                ));
+            },
+            (type_, None) => type_,
         };
 
         Ok(Self {
             type_,
             use_parse_trait,
+            inputs,
         })
     }
+
+    /// Returns the call to the parser function.
+    ///
+    /// The `name` and `position` of the argument are used to describe a failure.
+    /// The `serenity_ctx` is the expression of the reference to Serenity's context.
+    fn call(&self, serenity_ctx: &TokenStream, name: &Ident, position: usize) -> TokenStream {
+        let inputs = &self.inputs;
+        let name = name.to_string();
+
+        quote! {
+            #self(#serenity_ctx, &__parse_ctx, &mut __args #(, #inputs)*)
+                .await
+                .map_err(|err| err.into_dispatch_error(#name, #position))?
+        }
+    }
+
+    /// Returns the call to the parser function that records a failure of the argument
+    /// in `__failures` instead of returning it.
+    ///
+    /// The call evaluates to `None` if the failure was recorded.
+    fn collect_call(
+        &self,
+        serenity_ctx: &TokenStream,
+        name: &Ident,
+        position: usize,
+    ) -> TokenStream {
+        let inputs = &self.inputs;
+        let name = name.to_string();
+        let collect_failure = paths::collect_failure_func();
+
+        quote! {
+            #collect_failure(
+                #self(#serenity_ctx, &__parse_ctx, &mut __args #(, #inputs)*).await,
+                #name,
+                #position,
+                &mut __failures,
+            )?
+        }
+    }
 }
 
 impl ToTokens for ArgumentParser {
@@ -306,10 +1025,41 @@ impl ToTokens for ArgumentParser {
             (ArgumentType::Required, true) => paths::required_argument_parse_func(),
             (ArgumentType::Optional, false) => paths::optional_argument_from_str_func(),
             (ArgumentType::Optional, true) => paths::optional_argument_parse_func(),
+            (ArgumentType::SkippableOptional, false) => {
+                paths::optional_argument_from_str_skip_func()
+            },
+            (ArgumentType::SkippableOptional, true) => paths::optional_argument_parse_skip_func(),
             (ArgumentType::Variadic, false) => paths::variadic_arguments_from_str_func(),
             (ArgumentType::Variadic, true) => paths::variadic_arguments_parse_func(),
+            (ArgumentType::LenientVariadic, false) => {
+                paths::variadic_arguments_from_str_lenient_func()
+            },
+            (ArgumentType::LenientVariadic, true) => paths::variadic_arguments_parse_lenient_func(),
+            (ArgumentType::BoundedVariadic, false) => paths::variadic_arguments_from_str_max_func(),
+            (ArgumentType::BoundedVariadic, true) => paths::variadic_arguments_parse_max_func(),
             (ArgumentType::Rest, false) => paths::rest_argument_from_str_func(),
             (ArgumentType::Rest, true) => paths::rest_argument_parse_func(),
+            (ArgumentType::RequiredRest, false) => paths::required_rest_argument_from_str_func(),
+            (ArgumentType::RequiredRest, true) => paths::required_rest_argument_parse_func(),
+            (ArgumentType::List, false) => paths::list_argument_from_str_func(),
+            (ArgumentType::List, true) => paths::list_argument_parse_func(),
+            (ArgumentType::Pair, false) => paths::pair_argument_from_str_func(),
+            (ArgumentType::Pair, true) => paths::pair_argument_parse_func(),
+            (ArgumentType::Emoji, _) => paths::emoji_argument_func(),
+            (ArgumentType::Timestamp, _) => paths::timestamp_argument_func(),
+            (ArgumentType::Quantity, _) => paths::quantity_argument_func(),
+            (ArgumentType::Range, _) => paths::range_argument_func(),
+            (ArgumentType::Bool, _) => paths::bool_argument_func(),
+            (ArgumentType::Member, _) => paths::member_argument_func(),
+            (ArgumentType::Attachment, _) => paths::attachment_argument_func(),
+            (ArgumentType::OptionalAttachment, _) => paths::optional_attachment_argument_func(),
+            (ArgumentType::Attachments, _) => paths::attachments_argument_func(),
+            (ArgumentType::Sticker, _) => paths::sticker_argument_func(),
+            (ArgumentType::OptionalSticker, _) => paths::optional_sticker_argument_func(),
+            (ArgumentType::Stickers, _) => paths::stickers_argument_func(),
+            (ArgumentType::Embed, _) => paths::embed_argument_func(),
+            (ArgumentType::OptionalEmbed, _) => paths::optional_embed_argument_func(),
+            (ArgumentType::Embeds, _) => paths::embeds_argument_func(),
         };
 
         tokens.extend(quote!(#path));
@@ -1,23 +1,37 @@
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
 
 use proc_macro2::{Ident, TokenStream};
-use quote::{quote, ToTokens};
-use syn::{Attribute, Result};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::spanned::Spanned;
+use syn::{Attribute, Error, Lit, LitStr, Meta, NestedMeta, Result};
 
-use crate::utils::{parse_bool, parse_identifier, parse_identifiers, parse_string};
+use crate::paths;
+use crate::utils::{parse_bool, parse_flag, parse_i32, parse_identifier, parse_identifiers};
+use crate::utils::{parse_string, parse_string_literals, parse_value, Attr, Value};
 
 #[derive(Default)]
 pub struct Options {
     subcommands: Vec<Ident>,
     description: Option<String>,
+    localized_descriptions: Vec<(String, String)>,
     dynamic_description: Option<Ident>,
     usage: Option<String>,
+    localized_usage: Vec<(String, String)>,
     dynamic_usage: Option<Ident>,
     examples: Vec<String>,
     dynamic_examples: Option<Ident>,
     help_available: Option<bool>,
-    check: Option<Ident>,
-    pub delimiter: Option<String>,
+    sort_key: Option<i32>,
+    checks: Vec<Ident>,
+    required_roles: Vec<TokenStream>,
+    delimiter: Option<String>,
+    locale_delimiters: Vec<(LitStr, LitStr)>,
+    tokenizer: Option<Ident>,
+    pub deny_extra_args: bool,
+    pub collect_errors: bool,
+    bot_permissions: Vec<LitStr>,
+    nsfw: bool,
+    available_in_maintenance: bool,
 }
 
 impl Options {
@@ -31,6 +45,9 @@ impl Options {
             let name = attr.path.get_ident().unwrap().to_string();
 
             match name.as_str() {
+                "description" if is_localized(attr)? => {
+                    options.localized_descriptions.push(parse_localized(attr)?)
+                },
                 "doc" | "description" => {
                     let desc = options.description.get_or_insert_with(String::new);
 
@@ -50,6 +67,9 @@ impl Options {
                 "dynamic_description" => {
                     options.dynamic_description = Some(parse_identifier(&attr.try_into()?)?)
                 },
+                "usage" if is_localized(attr)? => {
+                    options.localized_usage.push(parse_localized(attr)?)
+                },
                 "usage" => options.usage = Some(parse_string(&attr.try_into()?)?),
                 "dynamic_usage" => {
                     options.dynamic_usage = Some(parse_identifier(&attr.try_into()?)?)
@@ -59,8 +79,34 @@ impl Options {
                     options.dynamic_examples = Some(parse_identifier(&attr.try_into()?)?)
                 },
                 "help_available" => options.help_available = Some(parse_bool(&attr.try_into()?)?),
-                "check" => options.check = Some(parse_identifier(&attr.try_into()?)?),
+                "sort_key" => options.sort_key = Some(parse_i32(&attr.try_into()?)?),
+                "check" => options.checks.extend(parse_identifiers(&attr.try_into()?)?),
+                "required_role" => options.required_roles.push(parse_role(&attr.try_into()?)?),
                 "delimiter" => options.delimiter = Some(parse_string(&attr.try_into()?)?),
+                "locale_delimiter" => {
+                    let values = parse_string_literals(&attr.try_into()?)?;
+
+                    match <[LitStr; 2]>::try_from(values) {
+                        Ok([locale, delimiter]) => {
+                            options.locale_delimiters.push((locale, delimiter))
+                        },
+                        Err(_) => {
+                            return Err(Error::new(
+                                attr.span(),
+                                "the `locale_delimiter` attribute expects a locale and a delimiter",
+                            ))
+                        },
+                    }
+                },
+                "tokenizer" => options.tokenizer = Some(parse_identifier(&attr.try_into()?)?),
+                "deny_extra_args" => options.deny_extra_args = parse_flag(&attr.try_into()?)?,
+                "bot_permissions" => {
+                    options.bot_permissions = parse_string_literals(&attr.try_into()?)?
+                },
+                "nsfw" => options.nsfw = parse_flag(&attr.try_into()?)?,
+                "available_in_maintenance" => {
+                    options.available_in_maintenance = parse_flag(&attr.try_into()?)?
+                },
                 _ => {
                     i += 1;
 
@@ -80,13 +126,24 @@ impl ToTokens for Options {
         let Options {
             subcommands,
             description,
+            localized_descriptions,
             dynamic_description,
             usage,
+            localized_usage,
             dynamic_usage,
             examples,
             dynamic_examples,
             help_available,
-            check,
+            sort_key,
+            checks,
+            required_roles,
+            delimiter,
+            locale_delimiters,
+            tokenizer,
+            deny_extra_args,
+            bot_permissions,
+            nsfw,
+            available_in_maintenance,
             ..
         } = self;
 
@@ -98,6 +155,10 @@ impl ToTokens for Options {
             tokens.extend(quote!(.description(#desc)));
         }
 
+        for (locale, desc) in localized_descriptions {
+            tokens.extend(quote!(.localized_description(#locale, #desc)));
+        }
+
         if let Some(dyn_desc) = dynamic_description {
             tokens.extend(quote!(.dynamic_description(#dyn_desc)));
         }
@@ -106,6 +167,10 @@ impl ToTokens for Options {
             tokens.extend(quote!(.usage(#usage)));
         }
 
+        for (locale, usage) in localized_usage {
+            tokens.extend(quote!(.localized_usage(#locale, #usage)));
+        }
+
         if let Some(dyn_usage) = dynamic_usage {
             tokens.extend(quote!(.dynamic_usage(#dyn_usage)));
         }
@@ -122,8 +187,119 @@ impl ToTokens for Options {
             tokens.extend(quote!(.help_available(#help_available)));
         }
 
-        if let Some(check) = check {
-            tokens.extend(quote!(.check(#check)));
+        if let Some(sort_key) = sort_key {
+            tokens.extend(quote!(.sort_key(#sort_key)));
+        }
+
+        for check in checks {
+            tokens.extend(quote_spanned!(check.span()=> .check(#check)));
+        }
+
+        for role in required_roles {
+            tokens.extend(quote!(.required_role(#role)));
+        }
+
+        if let Some(delimiter) = delimiter {
+            tokens.extend(quote!(.delimiter(#delimiter)));
+        }
+
+        for (locale, delimiter) in locale_delimiters {
+            tokens.extend(quote!(.locale_delimiter(#locale, #delimiter)));
+        }
+
+        if let Some(tokenizer) = tokenizer {
+            // `default` and `shell` refer to the tokenizers of the framework. Any other
+            // identifier refers to a tokenizer in the scope of the command.
+            let tokenizer = match tokenizer.to_string().as_str() {
+                "default" => paths::default_tokenizer_type().into_token_stream(),
+                "shell" => paths::shell_tokenizer_type().into_token_stream(),
+                _ => tokenizer.into_token_stream(),
+            };
+
+            tokens.extend(quote!(.tokenizer(&#tokenizer)));
+        }
+
+        if *deny_extra_args {
+            tokens.extend(quote!(.deny_extra_args(true)));
         }
+
+        if !bot_permissions.is_empty() {
+            let permissions = paths::permissions_type();
+            let flags = bot_permissions.iter().map(|p| Ident::new(&p.value(), p.span()));
+
+            tokens.extend(quote!(.bot_required_permissions(#(#permissions::#flags)|*)));
+        }
+
+        if *nsfw {
+            tokens.extend(quote!(.nsfw(true)));
+        }
+
+        if *available_in_maintenance {
+            tokens.extend(quote!(.available_in_maintenance(true)));
+        }
+    }
+}
+
+/// Parses the input of the `required_role` attribute, which is either the name of
+/// the role or its identifier.
+fn parse_role(attr: &Attr) -> Result<TokenStream> {
+    parse_value(attr, |value| match value {
+        Value::Lit(Lit::Str(name)) => Ok(quote!(#name)),
+        Value::Lit(Lit::Int(id)) => {
+            let role_id = paths::role_id_type();
+
+            Ok(quote!(#role_id(#id)))
+        },
+        _ => Err(Error::new(value.span(), "argument must be a string or an integer")),
+    })
+}
+
+/// Returns a boolean indicating whether the attribute is given in the localized form,
+/// `#[name(locale = "...", text = "...")]`.
+fn is_localized(attr: &Attribute) -> Result<bool> {
+    Ok(match attr.parse_meta()? {
+        Meta::List(list) => {
+            list.nested.iter().any(|nested| matches!(nested, NestedMeta::Meta(Meta::NameValue(_))))
+        },
+        _ => false,
+    })
+}
+
+/// Parses an attribute of the form `#[name(locale = "...", text = "...")]` into
+/// the locale and the text.
+fn parse_localized(attr: &Attribute) -> Result<(String, String)> {
+    let mut locale = None;
+    let mut text = None;
+
+    if let Meta::List(list) = attr.parse_meta()? {
+        for nested in &list.nested {
+            let nv = match nested {
+                NestedMeta::Meta(Meta::NameValue(nv)) => nv,
+                _ => {
+                    return Err(Error::new(
+                        nested.span(),
+                        "expected `locale = \"...\"` or `text = \"...\"`",
+                    ))
+                },
+            };
+
+            let value = match &nv.lit {
+                Lit::Str(value) => value.value(),
+                lit => return Err(Error::new(lit.span(), "argument must be a string")),
+            };
+
+            if nv.path.is_ident("locale") {
+                locale = Some(value);
+            } else if nv.path.is_ident("text") {
+                text = Some(value);
+            } else {
+                return Err(Error::new(nv.path.span(), "expected `locale` or `text`"));
+            }
+        }
+    }
+
+    match (locale, text) {
+        (Some(locale), Some(text)) => Ok((locale, text)),
+        _ => Err(Error::new(attr.span(), "both `locale` and `text` must be given")),
     }
 }
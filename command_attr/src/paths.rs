@@ -34,6 +34,42 @@ pub fn command_builder_type() -> Path {
     })
 }
 
+pub fn argument_info_type() -> Path {
+    to_path(quote! {
+        serenity_framework::command::ArgumentInfo
+    })
+}
+
+pub fn argument_kind_type() -> Path {
+    to_path(quote! {
+        serenity_framework::command::ArgumentKind
+    })
+}
+
+pub fn serenity_context_type() -> Path {
+    to_path(quote! {
+        serenity::client::Context
+    })
+}
+
+pub fn message_type() -> Path {
+    to_path(quote! {
+        serenity::model::channel::Message
+    })
+}
+
+pub fn permissions_type() -> Path {
+    to_path(quote! {
+        serenity::model::permissions::Permissions
+    })
+}
+
+pub fn role_id_type() -> Path {
+    to_path(quote! {
+        serenity::model::id::RoleId
+    })
+}
+
 pub fn hook_macro() -> Path {
     to_path(quote! {
         serenity_framework::prelude::hook
@@ -46,6 +82,24 @@ pub fn argument_segments_type() -> Path {
     })
 }
 
+pub fn default_tokenizer_type() -> Path {
+    to_path(quote! {
+        serenity_framework::utils::DefaultTokenizer
+    })
+}
+
+pub fn shell_tokenizer_type() -> Path {
+    to_path(quote! {
+        serenity_framework::utils::ShellTokenizer
+    })
+}
+
+pub fn parse_context_type() -> Path {
+    to_path(quote! {
+        serenity_framework::context::ParseContext
+    })
+}
+
 pub fn required_argument_from_str_func() -> Path {
     to_path(quote! {
         serenity_framework::argument::required_argument_from_str
@@ -70,6 +124,18 @@ pub fn optional_argument_parse_func() -> Path {
     })
 }
 
+pub fn optional_argument_from_str_skip_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::optional_argument_from_str_skip
+    })
+}
+
+pub fn optional_argument_parse_skip_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::optional_argument_parse_skip
+    })
+}
+
 pub fn variadic_arguments_from_str_func() -> Path {
     to_path(quote! {
         serenity_framework::argument::variadic_arguments_from_str
@@ -82,6 +148,30 @@ pub fn variadic_arguments_parse_func() -> Path {
     })
 }
 
+pub fn variadic_arguments_from_str_lenient_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::variadic_arguments_from_str_lenient
+    })
+}
+
+pub fn variadic_arguments_parse_lenient_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::variadic_arguments_parse_lenient
+    })
+}
+
+pub fn variadic_arguments_from_str_max_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::variadic_arguments_from_str_max
+    })
+}
+
+pub fn variadic_arguments_parse_max_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::variadic_arguments_parse_max
+    })
+}
+
 pub fn rest_argument_from_str_func() -> Path {
     to_path(quote! {
         serenity_framework::argument::rest_argument_from_str
@@ -94,6 +184,150 @@ pub fn rest_argument_parse_func() -> Path {
     })
 }
 
+pub fn required_rest_argument_from_str_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::required_rest_argument_from_str
+    })
+}
+
+pub fn required_rest_argument_parse_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::required_rest_argument_parse
+    })
+}
+
+pub fn list_argument_from_str_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::list_argument_from_str
+    })
+}
+
+pub fn list_argument_parse_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::list_argument_parse
+    })
+}
+
+pub fn pair_argument_from_str_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::pair_argument_from_str
+    })
+}
+
+pub fn pair_argument_parse_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::pair_argument_parse
+    })
+}
+
+pub fn emoji_argument_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::emoji_argument
+    })
+}
+
+pub fn timestamp_argument_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::timestamp_argument
+    })
+}
+
+pub fn quantity_argument_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::quantity_argument
+    })
+}
+
+pub fn range_argument_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::range_argument
+    })
+}
+
+pub fn member_argument_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::member_argument
+    })
+}
+
+pub fn bool_argument_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::bool_argument
+    })
+}
+
+pub fn attachment_argument_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::attachment_argument
+    })
+}
+
+pub fn optional_attachment_argument_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::optional_attachment_argument
+    })
+}
+
+pub fn attachments_argument_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::attachments_argument
+    })
+}
+
+pub fn sticker_argument_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::sticker_argument
+    })
+}
+
+pub fn optional_sticker_argument_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::optional_sticker_argument
+    })
+}
+
+pub fn stickers_argument_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::stickers_argument
+    })
+}
+
+pub fn embed_argument_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::embed_argument
+    })
+}
+
+pub fn optional_embed_argument_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::optional_embed_argument
+    })
+}
+
+pub fn embeds_argument_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::embeds_argument
+    })
+}
+
+pub fn no_extra_arguments_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::no_extra_arguments
+    })
+}
+
+pub fn terminated_quotes_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::terminated_quotes
+    })
+}
+
+pub fn collect_failure_func() -> Path {
+    to_path(quote! {
+        serenity_framework::argument::collect_failure
+    })
+}
+
 pub fn check_type(data: &Type, error: &Type) -> Path {
     to_path(quote! {
         serenity_framework::check::Check<#data, #error>
@@ -105,3 +339,33 @@ pub fn check_builder_type() -> Path {
         serenity_framework::check::CheckBuilder
     })
 }
+
+pub fn error_type(error: &Type) -> Path {
+    to_path(quote! {
+        serenity_framework::error::Error<#error>
+    })
+}
+
+pub fn dispatch_error_type() -> Path {
+    to_path(quote! {
+        serenity_framework::error::DispatchError
+    })
+}
+
+pub fn into_response_func() -> Path {
+    to_path(quote! {
+        serenity_framework::command::IntoResponse::into_response
+    })
+}
+
+pub fn send_response_func() -> Path {
+    to_path(quote! {
+        serenity_framework::command::Response::send
+    })
+}
+
+pub fn user_error_variant() -> Path {
+    to_path(quote! {
+        serenity_framework::error::Error::User
+    })
+}
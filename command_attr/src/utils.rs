@@ -9,18 +9,50 @@ use syn::{NestedMeta, Pat, PatType, Path, PathArguments, Result, Signature, Toke
 
 use crate::paths::{default_data_type, default_error_type};
 
-pub struct AttributeArgs(pub Vec<String>);
+pub struct AttributeArgs {
+    pub names: Vec<String>,
+    pub builder: Option<Ident>,
+    pub auto_respond: bool,
+    pub collect_errors: bool,
+}
 
 impl Parse for AttributeArgs {
     fn parse(input: ParseStream) -> Result<Self> {
-        let mut v = Vec::new();
+        let mut names = Vec::new();
+        let mut builder = None;
+        let mut auto_respond = false;
+        let mut collect_errors = false;
 
         loop {
             if input.is_empty() {
                 break;
             }
 
-            v.push(input.parse::<LitStr>()?.value());
+            if input.peek(syn::Ident) && input.peek2(Token![=]) {
+                let key = input.parse::<Ident>()?;
+
+                if key != "builder" {
+                    return Err(Error::new(key.span(), "invalid argument, expected `builder`"));
+                }
+
+                input.parse::<Token![=]>()?;
+                builder = Some(input.parse::<Ident>()?);
+            } else if input.peek(syn::Ident) {
+                let key = input.parse::<Ident>()?;
+
+                match key.to_string().as_str() {
+                    "auto_respond" => auto_respond = true,
+                    "collect_errors" => collect_errors = true,
+                    _ => {
+                        return Err(Error::new(
+                            key.span(),
+                            "invalid argument, expected `auto_respond` or `collect_errors`",
+                        ));
+                    },
+                }
+            } else {
+                names.push(input.parse::<LitStr>()?.value());
+            }
 
             if input.is_empty() {
                 break;
@@ -29,7 +61,12 @@ impl Parse for AttributeArgs {
             input.parse::<Token![,]>()?;
         }
 
-        Ok(Self(v))
+        Ok(Self {
+            names,
+            builder,
+            auto_respond,
+            collect_errors,
+        })
     }
 }
 
@@ -157,6 +194,16 @@ pub fn parse_string(attr: &Attr) -> Result<String> {
     })
 }
 
+pub fn parse_string_literals(attr: &Attr) -> Result<Vec<LitStr>> {
+    attr.values
+        .iter()
+        .map(|v| match v {
+            Value::Lit(Lit::Str(s)) => Ok(s.clone()),
+            _ => Err(Error::new(v.span(), "argument must be a string")),
+        })
+        .collect::<Result<Vec<_>>>()
+}
+
 pub fn parse_bool(attr: &Attr) -> Result<bool> {
     parse_value(attr, |value| {
         Ok(match value {
@@ -166,6 +213,24 @@ pub fn parse_bool(attr: &Attr) -> Result<bool> {
     })
 }
 
+pub fn parse_i32(attr: &Attr) -> Result<i32> {
+    parse_value(attr, |value| {
+        Ok(match value {
+            Value::Lit(Lit::Int(i)) => i.base10_parse::<i32>()?,
+            _ => return Err(Error::new(value.span(), "argument must be an integer")),
+        })
+    })
+}
+
+/// Parses a boolean that may be omitted, in which case it is `true`.
+pub fn parse_flag(attr: &Attr) -> Result<bool> {
+    if attr.values.is_empty() {
+        return Ok(true);
+    }
+
+    parse_bool(attr)
+}
+
 pub fn parse_generics(sig: &Signature) -> Result<(Ident, Ident, Box<Type>, Box<Type>)> {
     let (ctx, msg) = get_first_two_parameters(sig)?;
 
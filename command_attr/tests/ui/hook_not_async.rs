@@ -0,0 +1,8 @@
+use serenity_framework::prelude::*;
+
+#[hook]
+fn before(_ctx: &FrameworkContext, _msg: &serenity::model::channel::Message) -> bool {
+    true
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use serenity_framework::prelude::*;
+
+#[command]
+async fn echo(
+    _ctx: FrameworkContext,
+    _msg: &serenity::model::channel::Message,
+    #[remainder] text: String,
+) -> CommandResult {
+    Ok(())
+}
+
+fn main() {}
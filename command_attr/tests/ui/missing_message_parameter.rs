@@ -0,0 +1,8 @@
+use serenity_framework::prelude::*;
+
+#[command]
+async fn ping(_ctx: FrameworkContext) -> CommandResult {
+    Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use serenity_framework::prelude::*;
+
+#[command]
+async fn add(
+    _ctx: FrameworkContext,
+    _msg: &serenity::model::channel::Message,
+    a: Option<u32>,
+    b: u32,
+) -> CommandResult {
+    Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use serenity_framework::prelude::*;
+
+#[command]
+async fn say(
+    _ctx: FrameworkContext,
+    _msg: &serenity::model::channel::Message,
+    numbers: Vec<u32>,
+    #[rest] text: String,
+) -> CommandResult {
+    Ok(())
+}
+
+fn main() {}
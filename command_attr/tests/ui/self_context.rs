@@ -0,0 +1,8 @@
+use serenity_framework::prelude::*;
+
+#[command]
+async fn ping(self, _msg: &serenity::model::channel::Message) -> CommandResult {
+    Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use serenity_framework::prelude::*;
+
+#[command]
+async fn sum(
+    _ctx: FrameworkContext,
+    _msg: &serenity::model::channel::Message,
+    a: Vec<u32>,
+    b: Vec<u32>,
+) -> CommandResult {
+    Ok(())
+}
+
+fn main() {}
@@ -1,24 +1,134 @@
 //! Utilities for parsing command arguments.
+//!
+//! The parsing functions receive a [`ParseContext`], which wraps the message that
+//! contains the arguments.
+//!
+//! # Migration
+//!
+//! The parsing functions previously received the [`Message`] directly. Calls to them
+//! are migrated by converting the message into a [`ParseContext`]:
+//!
+//! ```rust,ignore
+//! // Before:
+//! required_argument_parse::<Member>(&ctx, &msg, &mut segments).await
+//! // After:
+//! required_argument_parse::<Member>(&ctx, &ParseContext::from(&msg), &mut segments).await
+//! ```
+//!
+//! The message remains available as [`ParseContext::msg`].
 
+use std::borrow::Cow;
+use std::convert::Infallible;
 use std::error::Error as StdError;
 use std::fmt;
+use std::iter::FromIterator;
 
+use chrono::{DateTime, LocalResult, TimeZone, Utc};
+use serenity::model::sticker::StickerItem;
 use serenity::{async_trait, model::prelude::*, prelude::*, utils::Parse};
 
-use crate::utils::ArgumentSegments;
+use crate::context::ParseContext;
+use crate::error::{ArgumentErrorKind, ArgumentFailure, DispatchError};
+use crate::utils::{list_segments, ArgumentSegments};
 
 /// Error that might have occured when trying to parse an argument.
 #[derive(Debug)]
 pub enum ArgumentError<E> {
     /// Required argument is missing.
     ///
-    /// This is only returned by the [`required_argument_from_str`] and [`required_argument_parse`]
-    /// functions.
+    /// This is only returned by the [`required_argument_from_str`], [`required_argument_parse`],
+    /// [`required_rest_argument_from_str`] and [`required_rest_argument_parse`] functions.
     Missing,
     /// Parsing the argument failed.
     ///
     /// Contains the error from [`serenity::utils::Parse::Err`].
     Argument(E),
+    /// Arguments remain after all arguments of the command were parsed.
+    ///
+    /// This is only returned by the [`no_extra_arguments`] function.
+    TooManyArguments,
+    /// A variadic argument received more elements than its maximum. Contains the maximum.
+    ///
+    /// This is only returned by the [`variadic_arguments_from_str_max`] and
+    /// [`variadic_arguments_parse_max`] functions.
+    TooMany(usize),
+    /// An argument starts with a quotation mark that is never terminated.
+    ///
+    /// This is only returned by the [`terminated_quotes`] function.
+    UnterminatedQuote,
+}
+
+impl<E: fmt::Display> ArgumentError<E> {
+    /// Returns a message describing the error that is suitable for displaying to the user.
+    ///
+    /// The `arg_name` is the name of the argument that failed to parse.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::num::ParseIntError;
+    ///
+    /// use serenity_framework::argument::ArgumentError;
+    ///
+    /// let err: ArgumentError<ParseIntError> = ArgumentError::Missing;
+    /// assert_eq!(err.to_user_message("user"), "missing required argument 'user'");
+    ///
+    /// let err = ArgumentError::Argument("x".parse::<u32>().unwrap_err());
+    /// assert_eq!(
+    ///     err.to_user_message("count"),
+    ///     "invalid value for 'count': invalid digit found in string"
+    /// );
+    /// ```
+    pub fn to_user_message(&self, arg_name: &str) -> String {
+        match self {
+            ArgumentError::Missing => format!("missing required argument '{}'", arg_name),
+            ArgumentError::Argument(err) => format!("invalid value for '{}': {}", arg_name, err),
+            ArgumentError::TooManyArguments => "too many arguments".to_string(),
+            ArgumentError::TooMany(max) => {
+                format!("too many values for '{}' (at most {})", arg_name, max)
+            },
+            ArgumentError::UnterminatedQuote => "unterminated quotation mark".to_string(),
+        }
+    }
+
+    /// Converts the error into a [`DispatchError`].
+    ///
+    /// The `name` and `position` describe the argument that failed. They are
+    /// ignored for [`ArgumentError::TooManyArguments`] and
+    /// [`ArgumentError::UnterminatedQuote`], which are converted into
+    /// [`DispatchError::TooManyArguments`] and [`DispatchError::UnterminatedQuote`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::num::ParseIntError;
+    ///
+    /// use serenity_framework::argument::ArgumentError;
+    /// use serenity_framework::error::{ArgumentErrorKind, DispatchError};
+    ///
+    /// let err: ArgumentError<ParseIntError> = ArgumentError::Missing;
+    ///
+    /// assert!(matches!(err.into_dispatch_error("count", 1), DispatchError::Argument {
+    ///     position: 1,
+    ///     kind: ArgumentErrorKind::Missing,
+    ///     ..
+    /// }));
+    /// ```
+    pub fn into_dispatch_error(self, name: &str, position: usize) -> DispatchError {
+        let kind = match self {
+            ArgumentError::Missing => ArgumentErrorKind::Missing,
+            ArgumentError::Argument(err) => ArgumentErrorKind::Parse(err.to_string()),
+            ArgumentError::TooManyArguments => return DispatchError::TooManyArguments,
+            ArgumentError::TooMany(max) => ArgumentErrorKind::TooMany(max),
+            ArgumentError::UnterminatedQuote => return DispatchError::UnterminatedQuote,
+        };
+
+        DispatchError::Argument {
+            name: name.to_string(),
+            position,
+            kind,
+        }
+    }
 }
 
 impl<E: fmt::Display> fmt::Display for ArgumentError<E> {
@@ -26,6 +136,9 @@ impl<E: fmt::Display> fmt::Display for ArgumentError<E> {
         match self {
             ArgumentError::Missing => f.write_str("missing required argument"),
             ArgumentError::Argument(err) => fmt::Display::fmt(err, f),
+            ArgumentError::TooManyArguments => f.write_str("too many arguments"),
+            ArgumentError::TooMany(max) => write!(f, "too many values (at most {})", max),
+            ArgumentError::UnterminatedQuote => f.write_str("unterminated quotation mark"),
         }
     }
 }
@@ -39,6 +152,143 @@ impl<E: StdError + 'static> StdError for ArgumentError<E> {
     }
 }
 
+/// Ensures that no segments are left in the list of segments.
+///
+/// This is used by commands that reject input in excess of their arguments.
+/// Whitespace is not regarded as an argument.
+///
+/// # Errors
+///
+/// If the list of segments is not empty, [`ArgumentError::TooManyArguments`] is returned.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_framework::argument::{no_extra_arguments, ArgumentError};
+/// use serenity_framework::utils::ArgumentSegments;
+///
+/// let mut segments = ArgumentSegments::new("1 2", " ");
+///
+/// segments.next();
+/// assert!(matches!(no_extra_arguments(&segments), Err(ArgumentError::TooManyArguments)));
+///
+/// segments.next();
+/// assert!(no_extra_arguments(&segments).is_ok());
+/// ```
+pub fn no_extra_arguments(
+    segments: &ArgumentSegments<'_>,
+) -> Result<(), ArgumentError<Infallible>> {
+    if segments.source().trim().is_empty() {
+        Ok(())
+    } else {
+        Err(ArgumentError::TooManyArguments)
+    }
+}
+
+/// Ensures that no segment in the list of segments starts with a quotation mark that
+/// is never terminated.
+///
+/// This is used by commands if [`Configuration::strict_quotes`] is enabled. The list
+/// of segments is not advanced.
+///
+/// # Errors
+///
+/// If a segment starts with an unterminated quotation mark,
+/// [`ArgumentError::UnterminatedQuote`] is returned.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_framework::argument::{terminated_quotes, ArgumentError};
+/// use serenity_framework::utils::ArgumentSegments;
+///
+/// let segments = ArgumentSegments::new("\"a b\" c", " ");
+/// assert!(terminated_quotes(&segments).is_ok());
+///
+/// let segments = ArgumentSegments::new("c \"a b", " ");
+/// assert!(matches!(terminated_quotes(&segments), Err(ArgumentError::UnterminatedQuote)));
+/// ```
+///
+/// [`Configuration::strict_quotes`]: crate::configuration::Configuration::strict_quotes
+pub fn terminated_quotes(segments: &ArgumentSegments<'_>) -> Result<(), ArgumentError<Infallible>> {
+    let mut segments = segments.clone();
+
+    loop {
+        match segments.try_next() {
+            Ok(Some(_)) => {},
+            Ok(None) => return Ok(()),
+            Err(_) => return Err(ArgumentError::UnterminatedQuote),
+        }
+    }
+}
+
+/// Records the failure of an argument instead of returning it.
+///
+/// This is used by commands marked with `#[command(collect_errors)]`, which parse
+/// all of their arguments before reporting every failure in
+/// [`DispatchError::ArgumentErrors`]. The `name` and `position` describe the argument.
+///
+/// Returns the parsed argument, or `None` if the failure was recorded.
+///
+/// # Errors
+///
+/// Errors that do not concern a single argument, such as
+/// [`ArgumentError::TooManyArguments`], are not recorded and are returned as in
+/// [`ArgumentError::into_dispatch_error`].
+///
+/// # Examples
+///
+/// ```rust
+/// use std::num::ParseIntError;
+///
+/// use serenity_framework::argument::{collect_failure, ArgumentError};
+/// use serenity_framework::error::{ArgumentErrorKind, DispatchError};
+///
+/// let mut failures = Vec::new();
+///
+/// let ok: Result<u32, ArgumentError<ParseIntError>> = Ok(1);
+/// assert_eq!(collect_failure(ok, "a", 0, &mut failures), Ok(Some(1)));
+///
+/// let missing: Result<u32, ArgumentError<ParseIntError>> = Err(ArgumentError::Missing);
+/// assert_eq!(collect_failure(missing, "b", 1, &mut failures), Ok(None));
+/// assert_eq!(failures[0].kind, ArgumentErrorKind::Missing);
+///
+/// let excess: Result<(), ArgumentError<ParseIntError>> = Err(ArgumentError::TooManyArguments);
+/// assert_eq!(collect_failure(excess, "", 0, &mut failures), Err(DispatchError::TooManyArguments));
+/// assert_eq!(failures.len(), 1);
+/// ```
+pub fn collect_failure<T, E>(
+    result: Result<T, ArgumentError<E>>,
+    name: &str,
+    position: usize,
+    failures: &mut Vec<ArgumentFailure>,
+) -> Result<Option<T>, DispatchError>
+where
+    E: fmt::Display,
+{
+    let err = match result {
+        Ok(arg) => return Ok(Some(arg)),
+        Err(err) => err,
+    };
+
+    match err.into_dispatch_error(name, position) {
+        DispatchError::Argument {
+            name,
+            position,
+            kind,
+        } => {
+            failures.push(ArgumentFailure {
+                name,
+                position,
+                kind,
+            });
+
+            Ok(None)
+        },
+        err => Err(err),
+    }
+}
+
 /// Takes a single segment from a list of segments and parses an argument out of it using the
 /// [std::str::FromStr] trait.
 ///
@@ -49,14 +299,14 @@ impl<E: StdError + 'static> StdError for ArgumentError<E> {
 /// returned.
 pub async fn required_argument_from_str<T>(
     _ctx: &Context,
-    _msg: &Message,
+    _parse_ctx: &ParseContext<'_>,
     segments: &mut ArgumentSegments<'_>,
 ) -> Result<T, ArgumentError<T::Err>>
 where
     T: std::str::FromStr,
 {
     match segments.next() {
-        Some(seg) => T::from_str(seg).map_err(ArgumentError::Argument),
+        Some(seg) => T::from_str(&seg).map_err(ArgumentError::Argument),
         None => Err(ArgumentError::Missing),
     }
 }
@@ -71,14 +321,14 @@ where
 /// returned.
 pub async fn required_argument_parse<T>(
     ctx: &Context,
-    msg: &Message,
+    parse_ctx: &ParseContext<'_>,
     segments: &mut ArgumentSegments<'_>,
 ) -> Result<T, ArgumentError<T::Err>>
 where
     T: Parse,
 {
     match segments.next() {
-        Some(seg) => T::parse(ctx, msg, seg).await.map_err(ArgumentError::Argument),
+        Some(seg) => T::parse(ctx, parse_ctx.msg, &seg).await.map_err(ArgumentError::Argument),
         None => Err(ArgumentError::Missing),
     }
 }
@@ -92,14 +342,14 @@ where
 /// [`ArgumentError::Argument`].
 pub async fn optional_argument_from_str<T>(
     _ctx: &Context,
-    _msg: &Message,
+    _parse_ctx: &ParseContext<'_>,
     segments: &mut ArgumentSegments<'_>,
 ) -> Result<Option<T>, ArgumentError<T::Err>>
 where
     T: std::str::FromStr,
 {
     match segments.next() {
-        Some(seg) => T::from_str(seg).map(Some).map_err(ArgumentError::Argument),
+        Some(seg) => T::from_str(&seg).map(Some).map_err(ArgumentError::Argument),
         None => Ok(None),
     }
 }
@@ -113,14 +363,64 @@ where
 /// [`ArgumentError::Argument`].
 pub async fn optional_argument_parse<T>(
     ctx: &Context,
-    msg: &Message,
+    parse_ctx: &ParseContext<'_>,
+    segments: &mut ArgumentSegments<'_>,
+) -> Result<Option<T>, ArgumentError<T::Err>>
+where
+    T: Parse,
+{
+    match segments.next() {
+        Some(seg) => {
+            T::parse(ctx, parse_ctx.msg, &seg).await.map(Some).map_err(ArgumentError::Argument)
+        },
+        None => Ok(None),
+    }
+}
+
+/// Tries to take a single segment from a list of segments and parse
+/// an argument out of it using the [std::str::FromStr] trait, unless the
+/// segment is the `skip` sentinel.
+///
+/// This behaves like [`optional_argument_from_str`], except that a segment equal
+/// to `skip` is consumed and yields `Ok(None)`. This allows users to skip an optional
+/// argument in order to provide the arguments after it.
+pub async fn optional_argument_from_str_skip<T>(
+    _ctx: &Context,
+    _parse_ctx: &ParseContext<'_>,
+    segments: &mut ArgumentSegments<'_>,
+    skip: &str,
+) -> Result<Option<T>, ArgumentError<T::Err>>
+where
+    T: std::str::FromStr,
+{
+    match segments.next() {
+        Some(seg) if seg == skip => Ok(None),
+        Some(seg) => T::from_str(&seg).map(Some).map_err(ArgumentError::Argument),
+        None => Ok(None),
+    }
+}
+
+/// Tries to take a single segment from a list of segments and parse
+/// an argument out of it using the [serenity::utils::Parse] trait, unless the
+/// segment is the `skip` sentinel.
+///
+/// This behaves like [`optional_argument_parse`], except that a segment equal
+/// to `skip` is consumed and yields `Ok(None)`. This allows users to skip an optional
+/// argument in order to provide the arguments after it.
+pub async fn optional_argument_parse_skip<T>(
+    ctx: &Context,
+    parse_ctx: &ParseContext<'_>,
     segments: &mut ArgumentSegments<'_>,
+    skip: &str,
 ) -> Result<Option<T>, ArgumentError<T::Err>>
 where
     T: Parse,
 {
     match segments.next() {
-        Some(seg) => T::parse(ctx, msg, seg).await.map(Some).map_err(ArgumentError::Argument),
+        Some(seg) if seg == skip => Ok(None),
+        Some(seg) => {
+            T::parse(ctx, parse_ctx.msg, &seg).await.map(Some).map_err(ArgumentError::Argument)
+        },
         None => Ok(None),
     }
 }
@@ -132,13 +432,13 @@ where
 /// is returned. The error is wrapped in [`ArgumentError::Argument`].
 pub async fn variadic_arguments_from_str<T>(
     _ctx: &Context,
-    _msg: &Message,
+    _parse_ctx: &ParseContext<'_>,
     segments: &mut ArgumentSegments<'_>,
 ) -> Result<Vec<T>, ArgumentError<T::Err>>
 where
     T: std::str::FromStr,
 {
-    segments.map(|seg| T::from_str(seg).map_err(ArgumentError::Argument)).collect()
+    segments.map(|seg| T::from_str(&seg).map_err(ArgumentError::Argument)).collect()
 }
 
 /// Tries to parse many arguments from a list of segments using the [serenity::utils::Parse] trait.
@@ -148,15 +448,127 @@ where
 /// is returned. The error is wrapped in [`ArgumentError::Argument`].
 pub async fn variadic_arguments_parse<T>(
     ctx: &Context,
-    msg: &Message,
+    parse_ctx: &ParseContext<'_>,
     segments: &mut ArgumentSegments<'_>,
 ) -> Result<Vec<T>, ArgumentError<T::Err>>
 where
     T: Parse,
 {
-    serenity::futures::future::try_join_all(segments.map(|seg| T::parse(ctx, msg, seg)))
-        .await
-        .map_err(ArgumentError::Argument)
+    let segments = segments.collect::<Vec<_>>();
+
+    serenity::futures::future::try_join_all(
+        segments.iter().map(|seg| T::parse(ctx, parse_ctx.msg, seg)),
+    )
+    .await
+    .map_err(ArgumentError::Argument)
+}
+
+/// Takes at most `max` segments from a list of segments.
+///
+/// If more than `max` segments remain, [`ArgumentError::TooMany`] is returned and
+/// no segment is taken.
+fn take_at_most<'a, E>(
+    segments: &mut ArgumentSegments<'a>,
+    max: usize,
+) -> Result<Vec<Cow<'a, str>>, ArgumentError<E>> {
+    segments
+        .try_parse(|segments| {
+            let segments = segments.take(max.saturating_add(1)).collect::<Vec<_>>();
+            Some(segments).filter(|segments| segments.len() <= max)
+        })
+        .ok_or(ArgumentError::TooMany(max))
+}
+
+/// Tries to parse at most `max` arguments from a list of segments using the
+/// [std::str::FromStr] trait.
+///
+/// This behaves like [`variadic_arguments_from_str`], except that the number of segments
+/// is checked before any of them is parsed.
+///
+/// # Errors
+///
+/// - If more than `max` segments remain, [`ArgumentError::TooMany`] is returned.
+/// - If a segment cannot be parsed, [`ArgumentError::Argument`] is returned.
+pub async fn variadic_arguments_from_str_max<T>(
+    _ctx: &Context,
+    _parse_ctx: &ParseContext<'_>,
+    segments: &mut ArgumentSegments<'_>,
+    max: usize,
+) -> Result<Vec<T>, ArgumentError<T::Err>>
+where
+    T: std::str::FromStr,
+{
+    take_at_most(segments, max)?
+        .into_iter()
+        .map(|seg| T::from_str(&seg).map_err(ArgumentError::Argument))
+        .collect()
+}
+
+/// Tries to parse at most `max` arguments from a list of segments using the
+/// [serenity::utils::Parse] trait.
+///
+/// This behaves like [`variadic_arguments_parse`], except that the number of segments
+/// is checked before any of them is parsed.
+///
+/// # Errors
+///
+/// - If more than `max` segments remain, [`ArgumentError::TooMany`] is returned.
+/// - If a segment cannot be parsed, [`ArgumentError::Argument`] is returned.
+pub async fn variadic_arguments_parse_max<T>(
+    ctx: &Context,
+    parse_ctx: &ParseContext<'_>,
+    segments: &mut ArgumentSegments<'_>,
+    max: usize,
+) -> Result<Vec<T>, ArgumentError<T::Err>>
+where
+    T: Parse,
+{
+    let segments = take_at_most(segments, max)?;
+
+    serenity::futures::future::try_join_all(
+        segments.iter().map(|seg| T::parse(ctx, parse_ctx.msg, seg)),
+    )
+    .await
+    .map_err(ArgumentError::Argument)
+}
+
+/// Parses many arguments from a list of segments using the [std::str::FromStr] trait,
+/// skipping segments that cannot be parsed.
+///
+/// Unlike [`variadic_arguments_from_str`], this never fails. Each segment that is parsed
+/// successfully is collected into the returned vector, in order.
+pub async fn variadic_arguments_from_str_lenient<T>(
+    _ctx: &Context,
+    _parse_ctx: &ParseContext<'_>,
+    segments: &mut ArgumentSegments<'_>,
+) -> Result<Vec<T>, ArgumentError<Infallible>>
+where
+    T: std::str::FromStr,
+{
+    Ok(segments.filter_map(|seg| T::from_str(&seg).ok()).collect())
+}
+
+/// Parses many arguments from a list of segments using the [serenity::utils::Parse] trait,
+/// skipping segments that cannot be parsed.
+///
+/// Unlike [`variadic_arguments_parse`], this never fails. Each segment that is parsed
+/// successfully is collected into the returned vector, in order.
+pub async fn variadic_arguments_parse_lenient<T>(
+    ctx: &Context,
+    parse_ctx: &ParseContext<'_>,
+    segments: &mut ArgumentSegments<'_>,
+) -> Result<Vec<T>, ArgumentError<Infallible>>
+where
+    T: Parse,
+{
+    let segments = segments.collect::<Vec<_>>();
+
+    let results = serenity::futures::future::join_all(
+        segments.iter().map(|seg| T::parse(ctx, parse_ctx.msg, seg)),
+    )
+    .await;
+
+    Ok(results.into_iter().filter_map(Result::ok).collect())
 }
 
 /// Parses the remainder of the list of segments into an argument using the [std::str::FromStr]
@@ -166,9 +578,13 @@ where
 /// and parsed to the specified argument type. If parsing success,
 /// `Ok(...)` is returned, otherwise `Err(...)`. The error is wrapped in
 /// [`ArgumentError::Argument`].
+///
+/// If no segments remain, an empty string is parsed. For a [`String`], this
+/// succeeds with an empty string. To demand text, use [`required_rest_argument_from_str`]
+/// instead, which is selected by `#[rest(required)]` in the `#[command]` macro.
 pub async fn rest_argument_from_str<T>(
     _ctx: &Context,
-    _msg: &Message,
+    _parse_ctx: &ParseContext<'_>,
     segments: &mut ArgumentSegments<'_>,
 ) -> Result<T, ArgumentError<T::Err>>
 where
@@ -184,17 +600,1278 @@ where
 /// and parsed to the specified argument type. If parsing success,
 /// `Ok(...)` is returned, otherwise `Err(...)`. The error is wrapped in
 /// [`ArgumentError::Argument`].
+///
+/// If no segments remain, an empty string is parsed. To demand text, use
+/// [`required_rest_argument_parse`] instead, which is selected by `#[rest(required)]`
+/// in the `#[command]` macro.
 pub async fn rest_argument_parse<T>(
     ctx: &Context,
-    msg: &Message,
+    parse_ctx: &ParseContext<'_>,
+    segments: &mut ArgumentSegments<'_>,
+) -> Result<T, ArgumentError<T::Err>>
+where
+    T: Parse,
+{
+    T::parse(ctx, parse_ctx.msg, segments.source()).await.map_err(ArgumentError::Argument)
+}
+
+/// Parses the remainder of the list of segments into an argument using the [std::str::FromStr]
+/// trait, requiring the remainder to contain text.
+///
+/// This is the same as [`rest_argument_from_str`], except that no segments remaining
+/// is an error.
+///
+/// # Errors
+///
+/// - If the remainder is empty or only contains whitespace, [`ArgumentError::Missing`]
+///   is returned.
+/// - If the remainder cannot be parsed into an argument, [`ArgumentError::Argument`] is
+///   returned.
+pub async fn required_rest_argument_from_str<T>(
+    _ctx: &Context,
+    _parse_ctx: &ParseContext<'_>,
+    segments: &mut ArgumentSegments<'_>,
+) -> Result<T, ArgumentError<T::Err>>
+where
+    T: std::str::FromStr,
+{
+    if segments.source().trim().is_empty() {
+        return Err(ArgumentError::Missing);
+    }
+
+    T::from_str(segments.source()).map_err(ArgumentError::Argument)
+}
+
+/// Parses the remainder of the list of segments into an argument using the
+/// [serenity::utils::Parse] trait, requiring the remainder to contain text.
+///
+/// This is the same as [`rest_argument_parse`], except that no segments remaining
+/// is an error.
+///
+/// # Errors
+///
+/// - If the remainder is empty or only contains whitespace, [`ArgumentError::Missing`]
+///   is returned.
+/// - If the remainder cannot be parsed into an argument, [`ArgumentError::Argument`] is
+///   returned.
+pub async fn required_rest_argument_parse<T>(
+    ctx: &Context,
+    parse_ctx: &ParseContext<'_>,
     segments: &mut ArgumentSegments<'_>,
 ) -> Result<T, ArgumentError<T::Err>>
 where
     T: Parse,
 {
-    T::parse(ctx, msg, segments.source()).await.map_err(ArgumentError::Argument)
+    if segments.source().trim().is_empty() {
+        return Err(ArgumentError::Missing);
+    }
+
+    T::parse(ctx, parse_ctx.msg, segments.source()).await.map_err(ArgumentError::Argument)
+}
+
+/// Takes a single segment from a list of segments and parses a list of arguments out of it using
+/// the [std::str::FromStr] trait.
+///
+/// The segment is split into [items][list] by the `separator`, and each item is parsed into
+/// an argument. This differs from [`variadic_arguments_from_str`], which parses every remaining
+/// segment into an argument. Here, only one segment, such as `1,2,3`, is consumed.
+///
+/// The arguments can be collected into any collection, such as a [`Vec`] or a
+/// [`HashSet`][std::collections::HashSet].
+///
+/// # Errors
+///
+/// - If the list of segments is empty, [`ArgumentError::Missing`] is returned.
+/// - If an item cannot be parsed into an argument, [`ArgumentError::Argument`] is
+///   returned.
+///
+/// [list]: crate::utils::list_segments
+pub async fn list_argument_from_str<C, T>(
+    _ctx: &Context,
+    _parse_ctx: &ParseContext<'_>,
+    segments: &mut ArgumentSegments<'_>,
+    separator: &str,
+) -> Result<C, ArgumentError<T::Err>>
+where
+    C: FromIterator<T>,
+    T: std::str::FromStr,
+{
+    match segments.next() {
+        Some(seg) => list_segments(&seg, separator)
+            .map(|item| T::from_str(item).map_err(ArgumentError::Argument))
+            .collect(),
+        None => Err(ArgumentError::Missing),
+    }
+}
+
+/// Takes a single segment from a list of segments and parses a list of arguments out of it using
+/// the [serenity::utils::Parse] trait.
+///
+/// Refer to [`list_argument_from_str`] for how the segment is split.
+///
+/// # Errors
+///
+/// - If the list of segments is empty, [`ArgumentError::Missing`] is returned.
+/// - If an item cannot be parsed into an argument, [`ArgumentError::Argument`] is
+///   returned.
+pub async fn list_argument_parse<C, T>(
+    ctx: &Context,
+    parse_ctx: &ParseContext<'_>,
+    segments: &mut ArgumentSegments<'_>,
+    separator: &str,
+) -> Result<C, ArgumentError<T::Err>>
+where
+    C: FromIterator<T>,
+    T: Parse,
+{
+    match segments.next() {
+        Some(seg) => serenity::futures::future::try_join_all(
+            list_segments(&seg, separator).map(|item| T::parse(ctx, parse_ctx.msg, item)),
+        )
+        .await
+        .map(|items| items.into_iter().collect())
+        .map_err(ArgumentError::Argument),
+        None => Err(ArgumentError::Missing),
+    }
+}
+
+/// Takes a single segment from a list of segments and parses a pair of arguments out of it
+/// using the [std::str::FromStr] trait.
+///
+/// The segment is split into [items][list] by the `separator`. It must contain exactly
+/// two items.
+///
+/// # Errors
+///
+/// - If the list of segments is empty, [`ArgumentError::Missing`] is returned.
+/// - If the segment does not have two items, or an item cannot be parsed into an argument,
+///   [`ArgumentError::Argument`] is returned.
+///
+/// [list]: crate::utils::list_segments
+pub async fn pair_argument_from_str<T, U>(
+    _ctx: &Context,
+    _parse_ctx: &ParseContext<'_>,
+    segments: &mut ArgumentSegments<'_>,
+    separator: &str,
+) -> Result<(T, U), ArgumentError<PairError<T::Err, U::Err>>>
+where
+    T: std::str::FromStr,
+    U: std::str::FromStr,
+{
+    let seg = segments.next().ok_or(ArgumentError::Missing)?;
+    let (first, second) = pair(&seg, separator)?;
+
+    let first = T::from_str(first).map_err(|err| ArgumentError::Argument(PairError::First(err)))?;
+    let second =
+        U::from_str(second).map_err(|err| ArgumentError::Argument(PairError::Second(err)))?;
+
+    Ok((first, second))
+}
+
+/// Takes a single segment from a list of segments and parses a pair of arguments out of it
+/// using the [serenity::utils::Parse] trait.
+///
+/// Refer to [`pair_argument_from_str`] for how the segment is split.
+///
+/// # Errors
+///
+/// - If the list of segments is empty, [`ArgumentError::Missing`] is returned.
+/// - If the segment does not have two items, or an item cannot be parsed into an argument,
+///   [`ArgumentError::Argument`] is returned.
+pub async fn pair_argument_parse<T, U>(
+    ctx: &Context,
+    parse_ctx: &ParseContext<'_>,
+    segments: &mut ArgumentSegments<'_>,
+    separator: &str,
+) -> Result<(T, U), ArgumentError<PairError<T::Err, U::Err>>>
+where
+    T: Parse,
+    U: Parse,
+{
+    let seg = segments.next().ok_or(ArgumentError::Missing)?;
+    let (first, second) = pair(&seg, separator)?;
+
+    let first = T::parse(ctx, parse_ctx.msg, first)
+        .await
+        .map_err(|err| ArgumentError::Argument(PairError::First(err)))?;
+    let second = U::parse(ctx, parse_ctx.msg, second)
+        .await
+        .map_err(|err| ArgumentError::Argument(PairError::Second(err)))?;
+
+    Ok((first, second))
+}
+
+fn pair<'a, T, U>(
+    seg: &'a str,
+    separator: &str,
+) -> Result<(&'a str, &'a str), ArgumentError<PairError<T, U>>> {
+    let items = list_segments(seg, separator).collect::<Vec<_>>();
+
+    match items[..] {
+        [first, second] => Ok((first, second)),
+        _ => Err(ArgumentError::Argument(PairError::Count(items.len()))),
+    }
 }
 
+/// Error that is returned when parsing a pair of arguments fails.
+#[derive(Debug)]
+pub enum PairError<T, U> {
+    /// The segment does not contain exactly two items. Contains the amount of items.
+    Count(usize),
+    /// Parsing the first item failed.
+    First(T),
+    /// Parsing the second item failed.
+    Second(U),
+}
+
+impl<T: fmt::Display, U: fmt::Display> fmt::Display for PairError<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PairError::Count(count) => write!(f, "expected two items, found {}", count),
+            PairError::First(err) => fmt::Display::fmt(err, f),
+            PairError::Second(err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+impl<T, U> StdError for PairError<T, U>
+where
+    T: StdError + 'static,
+    U: StdError + 'static,
+{
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            PairError::Count(_) => None,
+            PairError::First(err) => Some(err),
+            PairError::Second(err) => Some(err),
+        }
+    }
+}
+
+/// Takes an attachment of the message as an argument.
+///
+/// The `index` is the position of the attachment among the attachments of the message.
+/// Unlike other arguments, attachments are not parsed from the content of the message.
+/// Hence, the list of segments is left untouched.
+///
+/// In the `#[command]` macro, parameters with the `#[attachment]` attribute bind the
+/// attachments in the order of their declaration, and a parameter with the `#[attachments]`
+/// attribute binds all of them. As they consume no segments, these parameters may be
+/// declared anywhere among other parameters without affecting their positions.
+///
+/// # Errors
+///
+/// If the message does not have an attachment at the `index`, [`ArgumentError::Missing`]
+/// is returned.
+pub async fn attachment_argument(
+    _ctx: &Context,
+    parse_ctx: &ParseContext<'_>,
+    _segments: &mut ArgumentSegments<'_>,
+    index: usize,
+) -> Result<Attachment, ArgumentError<Infallible>> {
+    parse_ctx.msg.attachments.get(index).cloned().ok_or(ArgumentError::Missing)
+}
+
+/// Tries to take an attachment of the message as an argument.
+///
+/// If the message does not have an attachment at the `index`, `Ok(None)` is returned.
+/// Refer to [`attachment_argument`] for more information.
+pub async fn optional_attachment_argument(
+    _ctx: &Context,
+    parse_ctx: &ParseContext<'_>,
+    _segments: &mut ArgumentSegments<'_>,
+    index: usize,
+) -> Result<Option<Attachment>, ArgumentError<Infallible>> {
+    Ok(parse_ctx.msg.attachments.get(index).cloned())
+}
+
+/// Takes all attachments of the message as an argument.
+///
+/// The list of segments is left untouched. If the message does not have any
+/// attachments, an empty vector is returned.
+pub async fn attachments_argument(
+    _ctx: &Context,
+    parse_ctx: &ParseContext<'_>,
+    _segments: &mut ArgumentSegments<'_>,
+) -> Result<Vec<Attachment>, ArgumentError<Infallible>> {
+    Ok(parse_ctx.msg.attachments.clone())
+}
+
+/// Takes a sticker of the message as an argument.
+///
+/// The `index` is the position of the sticker among the stickers of the message.
+/// Like [attachments][attachment_argument], stickers are not parsed from the content
+/// of the message, and the list of segments is left untouched.
+///
+/// In the `#[command]` macro, parameters with the `#[sticker]` attribute bind the
+/// stickers in the order of their declaration. A parameter of type `Vec<StickerItem>`
+/// binds all of them. As they consume no segments, these parameters may be declared
+/// anywhere among other parameters without affecting their positions.
+///
+/// # Errors
+///
+/// If the message does not have a sticker at the `index`, [`ArgumentError::Missing`]
+/// is returned.
+pub async fn sticker_argument(
+    _ctx: &Context,
+    parse_ctx: &ParseContext<'_>,
+    _segments: &mut ArgumentSegments<'_>,
+    index: usize,
+) -> Result<StickerItem, ArgumentError<Infallible>> {
+    parse_ctx.msg.sticker_items.get(index).cloned().ok_or(ArgumentError::Missing)
+}
+
+/// Tries to take a sticker of the message as an argument.
+///
+/// If the message does not have a sticker at the `index`, `Ok(None)` is returned.
+/// Refer to [`sticker_argument`] for more information.
+pub async fn optional_sticker_argument(
+    _ctx: &Context,
+    parse_ctx: &ParseContext<'_>,
+    _segments: &mut ArgumentSegments<'_>,
+    index: usize,
+) -> Result<Option<StickerItem>, ArgumentError<Infallible>> {
+    Ok(parse_ctx.msg.sticker_items.get(index).cloned())
+}
+
+/// Takes all stickers of the message as an argument.
+///
+/// The list of segments is left untouched. If the message does not have any
+/// stickers, an empty vector is returned.
+pub async fn stickers_argument(
+    _ctx: &Context,
+    parse_ctx: &ParseContext<'_>,
+    _segments: &mut ArgumentSegments<'_>,
+) -> Result<Vec<StickerItem>, ArgumentError<Infallible>> {
+    Ok(parse_ctx.msg.sticker_items.clone())
+}
+
+/// Takes an embed of the message as an argument.
+///
+/// The `index` is the position of the embed among the embeds of the message.
+/// Like [attachments][attachment_argument], embeds are not parsed from the content
+/// of the message, and the list of segments is left untouched.
+///
+/// In the `#[command]` macro, parameters with the `#[embed]` attribute bind the
+/// embeds in the order of their declaration. A parameter of type `Vec<Embed>` binds
+/// all of them. As they consume no segments, these parameters may be declared
+/// anywhere among other parameters without affecting their positions.
+///
+/// # Errors
+///
+/// If the message does not have an embed at the `index`, [`ArgumentError::Missing`]
+/// is returned.
+pub async fn embed_argument(
+    _ctx: &Context,
+    parse_ctx: &ParseContext<'_>,
+    _segments: &mut ArgumentSegments<'_>,
+    index: usize,
+) -> Result<Embed, ArgumentError<Infallible>> {
+    parse_ctx.msg.embeds.get(index).cloned().ok_or(ArgumentError::Missing)
+}
+
+/// Tries to take an embed of the message as an argument.
+///
+/// If the message does not have an embed at the `index`, `Ok(None)` is returned.
+/// Refer to [`embed_argument`] for more information.
+pub async fn optional_embed_argument(
+    _ctx: &Context,
+    parse_ctx: &ParseContext<'_>,
+    _segments: &mut ArgumentSegments<'_>,
+    index: usize,
+) -> Result<Option<Embed>, ArgumentError<Infallible>> {
+    Ok(parse_ctx.msg.embeds.get(index).cloned())
+}
+
+/// Takes all embeds of the message as an argument.
+///
+/// The list of segments is left untouched. If the message does not have any
+/// embeds, an empty vector is returned.
+pub async fn embeds_argument(
+    _ctx: &Context,
+    parse_ctx: &ParseContext<'_>,
+    _segments: &mut ArgumentSegments<'_>,
+) -> Result<Vec<Embed>, ArgumentError<Infallible>> {
+    Ok(parse_ctx.msg.embeds.clone())
+}
+
+/// Takes a single segment from a list of segments and parses an [emoji][EmojiArg] out of it.
+///
+/// The emoji is returned as a [`ReactionType`], which can be used to react to messages.
+///
+/// # Errors
+///
+/// - If the list of segments is empty, [`ArgumentError::Missing`] is returned.
+/// - If the segment is a malformed custom emoji, [`ArgumentError::Argument`] is returned.
+pub async fn emoji_argument(
+    _ctx: &Context,
+    _parse_ctx: &ParseContext<'_>,
+    segments: &mut ArgumentSegments<'_>,
+) -> Result<ReactionType, ArgumentError<EmojiParseError>> {
+    match segments.next() {
+        Some(seg) => seg.parse::<EmojiArg>().map(EmojiArg::into).map_err(ArgumentError::Argument),
+        None => Err(ArgumentError::Missing),
+    }
+}
+
+/// An emoji argument.
+///
+/// It can be parsed from a custom emoji, such as `<:ferris:123>` or `<a:ferris:123>` if it is
+/// animated. Any other text is treated as a unicode emoji.
+///
+/// As it implements [`FromStr`][std::str::FromStr], it also implements the [`Parse`] trait.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity::model::id::EmojiId;
+/// use serenity_framework::argument::EmojiArg;
+///
+/// assert_eq!("<a:wave:123>".parse::<EmojiArg>().unwrap(), EmojiArg::Custom {
+///     animated: true,
+///     id: EmojiId(123),
+///     name: "wave".to_string(),
+/// });
+///
+/// assert_eq!("<:ferris:456>".parse::<EmojiArg>().unwrap(), EmojiArg::Custom {
+///     animated: false,
+///     id: EmojiId(456),
+///     name: "ferris".to_string(),
+/// });
+///
+/// assert_eq!("👍".parse::<EmojiArg>().unwrap(), EmojiArg::Unicode("👍".to_string()));
+///
+/// assert!("<:ferris:abc>".parse::<EmojiArg>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EmojiArg {
+    /// A custom emoji of a guild.
+    Custom {
+        /// A boolean indicating whether the emoji is animated.
+        animated: bool,
+        /// The identifier of the emoji.
+        id: EmojiId,
+        /// The name of the emoji.
+        name: String,
+    },
+    /// A unicode emoji.
+    Unicode(String),
+}
+
+impl EmojiArg {
+    /// Returns the emoji as a [`ReactionType`].
+    pub fn as_reaction(&self) -> ReactionType {
+        self.clone().into()
+    }
+}
+
+impl From<EmojiArg> for ReactionType {
+    fn from(emoji: EmojiArg) -> Self {
+        match emoji {
+            EmojiArg::Custom {
+                animated,
+                id,
+                name,
+            } => ReactionType::Custom {
+                animated,
+                id,
+                name: Some(name),
+            },
+            EmojiArg::Unicode(emoji) => ReactionType::Unicode(emoji),
+        }
+    }
+}
+
+impl std::str::FromStr for EmojiArg {
+    type Err = EmojiParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inner = match s.strip_prefix('<').and_then(|s| s.strip_suffix('>')) {
+            Some(inner) => inner,
+            None => return Ok(EmojiArg::Unicode(s.to_string())),
+        };
+
+        match inner.split(':').collect::<Vec<_>>()[..] {
+            [animated, name, id]
+                if (animated.is_empty() || animated == "a") && !name.is_empty() =>
+            {
+                let id = id.parse::<u64>().map_err(|_| EmojiParseError)?;
+
+                Ok(EmojiArg::Custom {
+                    animated: animated == "a",
+                    id: EmojiId(id),
+                    name: name.to_string(),
+                })
+            },
+            _ => Err(EmojiParseError),
+        }
+    }
+}
+
+/// Error that is returned when parsing a malformed custom emoji.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmojiParseError;
+
+impl fmt::Display for EmojiParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid custom emoji")
+    }
+}
+
+impl StdError for EmojiParseError {}
+
+/// Takes a single segment from a list of segments and parses a [timestamp][Timestamp] out of it.
+///
+/// # Errors
+///
+/// - If the list of segments is empty, [`ArgumentError::Missing`] is returned.
+/// - If the segment is not a valid timestamp, [`ArgumentError::Argument`] is returned.
+pub async fn timestamp_argument(
+    _ctx: &Context,
+    _parse_ctx: &ParseContext<'_>,
+    segments: &mut ArgumentSegments<'_>,
+) -> Result<DateTime<Utc>, ArgumentError<TimestampParseError>> {
+    match segments.next() {
+        Some(seg) => {
+            seg.parse::<Timestamp>().map(Timestamp::datetime).map_err(ArgumentError::Argument)
+        },
+        None => Err(ArgumentError::Missing),
+    }
+}
+
+/// A point in time.
+///
+/// It can be parsed from:
+/// - an [ISO 8601][iso] (RFC 3339) date and time, such as `2021-01-01T12:00:00+00:00`;
+/// - a Discord timestamp, such as `<t:1609502400>` or `<t:1609502400:R>`, where the
+///   style is ignored;
+/// - a snowflake, such as the identifier of a message, from which its creation time is derived.
+///
+/// As it implements [`FromStr`][std::str::FromStr], it also implements the [`Parse`] trait.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_framework::argument::{Timestamp, TimestampParseError};
+///
+/// let time = "2021-01-01T12:00:00+00:00".parse::<Timestamp>().unwrap();
+/// assert_eq!(time.datetime().timestamp(), 1609502400);
+///
+/// let time = "2021-01-01T14:00:00+02:00".parse::<Timestamp>().unwrap();
+/// assert_eq!(time.datetime().timestamp(), 1609502400);
+///
+/// let time = "<t:1609502400>".parse::<Timestamp>().unwrap();
+/// assert_eq!(time.datetime().timestamp(), 1609502400);
+///
+/// let time = "<t:1609502400:R>".parse::<Timestamp>().unwrap();
+/// assert_eq!(time.datetime().timestamp(), 1609502400);
+///
+/// let time = "<t:1609502400:F>".parse::<Timestamp>().unwrap();
+/// assert_eq!(time.datetime().timestamp(), 1609502400);
+///
+/// // The snowflake of a message sent on 2021-01-01T12:00:00.000+00:00.
+/// let time = "794535395328000000".parse::<Timestamp>().unwrap();
+/// assert_eq!(time.datetime().timestamp_millis(), 1609502400000);
+///
+/// assert_eq!("0".parse::<Timestamp>(), Err(TimestampParseError::InvalidSnowflake));
+/// assert_eq!("<t:99999999999999999>".parse::<Timestamp>(), Err(TimestampParseError::OutOfRange));
+/// assert_eq!("yesterday".parse::<Timestamp>(), Err(TimestampParseError::Malformed));
+/// ```
+///
+/// [iso]: https://en.wikipedia.org/wiki/ISO_8601
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(DateTime<Utc>);
+
+impl Timestamp {
+    /// The first second of 2015, in milliseconds since the Unix epoch.
+    ///
+    /// Creation times of snowflakes are relative to this point in time.
+    pub const DISCORD_EPOCH: i64 = 1_420_070_400_000;
+
+    /// Returns the point in time as a [`DateTime`].
+    pub fn datetime(self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+impl From<Timestamp> for DateTime<Utc> {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.0
+    }
+}
+
+impl std::str::FromStr for Timestamp {
+    type Err = TimestampParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(inner) = s.strip_prefix("<t:").and_then(|s| s.strip_suffix('>')) {
+            // The style of the timestamp only affects how Discord displays it.
+            let secs = inner.split(':').next().unwrap_or_default();
+            let secs = secs.parse::<i64>().map_err(|_| TimestampParseError::Malformed)?;
+
+            return match Utc.timestamp_opt(secs, 0) {
+                LocalResult::Single(datetime) => Ok(Timestamp(datetime)),
+                _ => Err(TimestampParseError::OutOfRange),
+            };
+        }
+
+        if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+            let id = s.parse::<u64>().map_err(|_| TimestampParseError::InvalidSnowflake)?;
+
+            if id == 0 {
+                return Err(TimestampParseError::InvalidSnowflake);
+            }
+
+            // The 42 most significant bits of a snowflake are its creation time.
+            let millis = (id >> 22) as i64 + Timestamp::DISCORD_EPOCH;
+
+            return match Utc.timestamp_millis_opt(millis) {
+                LocalResult::Single(datetime) => Ok(Timestamp(datetime)),
+                _ => Err(TimestampParseError::OutOfRange),
+            };
+        }
+
+        DateTime::parse_from_rfc3339(s)
+            .map(|datetime| Timestamp(datetime.with_timezone(&Utc)))
+            .map_err(|_| TimestampParseError::Malformed)
+    }
+}
+
+/// Error that is returned when parsing a [`Timestamp`] fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampParseError {
+    /// The input is neither a date and time, a Discord timestamp, nor a snowflake.
+    Malformed,
+    /// The input is a number that is not a valid snowflake.
+    InvalidSnowflake,
+    /// The point in time cannot be represented.
+    OutOfRange,
+}
+
+impl fmt::Display for TimestampParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimestampParseError::Malformed => f.write_str("invalid timestamp"),
+            TimestampParseError::InvalidSnowflake => f.write_str("invalid snowflake"),
+            TimestampParseError::OutOfRange => f.write_str("timestamp is out of range"),
+        }
+    }
+}
+
+impl StdError for TimestampParseError {}
+
+/// Takes a single segment from a list of segments and parses a [quantity][Quantity] out of it.
+///
+/// # Errors
+///
+/// - If the list of segments is empty, [`ArgumentError::Missing`] is returned.
+/// - If the segment is not a valid quantity, [`ArgumentError::Argument`] is returned.
+pub async fn quantity_argument(
+    _ctx: &Context,
+    _parse_ctx: &ParseContext<'_>,
+    segments: &mut ArgumentSegments<'_>,
+) -> Result<Quantity, ArgumentError<QuantityParseError>> {
+    match segments.next() {
+        Some(seg) => seg.parse::<Quantity>().map_err(ArgumentError::Argument),
+        None => Err(ArgumentError::Missing),
+    }
+}
+
+/// A number that may be a percentage or scaled by a suffix.
+///
+/// It can be parsed from:
+/// - a number, such as `42`, `-3` or `2.5`;
+/// - a percentage, such as `50%`, which is parsed as a fraction;
+/// - a number with a short scale suffix, `k` for thousands, `M` for millions
+///   or `B` for billions, such as `1.5k`. The suffixes are case-insensitive.
+///
+/// Only `.` is supported as the decimal separator. A percentage cannot have a suffix.
+///
+/// As it implements [`FromStr`][std::str::FromStr], it also implements the [`Parse`] trait.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_framework::argument::{Quantity, QuantityParseError};
+///
+/// let quantity = "50%".parse::<Quantity>().unwrap();
+/// assert!(quantity.is_percentage());
+/// assert_eq!(quantity.as_f64(), 0.5);
+///
+/// let quantity = "1.5k".parse::<Quantity>().unwrap();
+/// assert!(!quantity.is_percentage());
+/// assert_eq!(quantity.as_f64(), 1500.0);
+///
+/// assert_eq!("2M".parse::<Quantity>().unwrap().as_f64(), 2_000_000.0);
+/// assert_eq!("-3b".parse::<Quantity>().unwrap().as_f64(), -3_000_000_000.0);
+/// assert_eq!("42".parse::<Quantity>().unwrap().as_f64(), 42.0);
+///
+/// assert_eq!("1k%".parse::<Quantity>(), Err(QuantityParseError::PercentageWithSuffix));
+/// assert_eq!("1,5k".parse::<Quantity>(), Err(QuantityParseError::Malformed));
+/// assert_eq!("inf".parse::<Quantity>(), Err(QuantityParseError::Malformed));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantity {
+    value: f64,
+    percentage: bool,
+}
+
+impl Quantity {
+    /// Returns the quantity as a number.
+    ///
+    /// A percentage is returned as a fraction, such that `50%` is `0.5`.
+    pub fn as_f64(self) -> f64 {
+        self.value
+    }
+
+    /// Returns a boolean indicating whether the quantity was given as a percentage.
+    pub fn is_percentage(self) -> bool {
+        self.percentage
+    }
+}
+
+impl std::str::FromStr for Quantity {
+    type Err = QuantityParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(number) = s.strip_suffix('%') {
+            if quantity_suffix(number).is_some() {
+                return Err(QuantityParseError::PercentageWithSuffix);
+            }
+
+            return Ok(Quantity {
+                value: quantity_number(number)? / 100.0,
+                percentage: true,
+            });
+        }
+
+        let (number, scale) = match quantity_suffix(s) {
+            Some(scale) => (&s[..s.len() - 1], scale),
+            None => (s, 1.0),
+        };
+
+        Ok(Quantity {
+            value: quantity_number(number)? * scale,
+            percentage: false,
+        })
+    }
+}
+
+/// Returns the scale of the short scale suffix of the string, if present.
+fn quantity_suffix(s: &str) -> Option<f64> {
+    match s.chars().last()? {
+        'k' | 'K' => Some(1e3),
+        'm' | 'M' => Some(1e6),
+        'b' | 'B' => Some(1e9),
+        _ => None,
+    }
+}
+
+fn quantity_number(s: &str) -> Result<f64, QuantityParseError> {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+
+    // Reject what `f64` parses beyond plain decimal numbers, such as `inf` or `1e3`.
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit() || b == b'.') {
+        return Err(QuantityParseError::Malformed);
+    }
+
+    s.parse::<f64>().map_err(|_| QuantityParseError::Malformed)
+}
+
+/// Error that is returned when parsing a [`Quantity`] fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuantityParseError {
+    /// The input is not a number.
+    Malformed,
+    /// The input is a percentage with a short scale suffix, such as `1k%`.
+    PercentageWithSuffix,
+}
+
+impl fmt::Display for QuantityParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuantityParseError::Malformed => f.write_str("invalid quantity"),
+            QuantityParseError::PercentageWithSuffix => {
+                f.write_str("a percentage cannot have a suffix")
+            },
+        }
+    }
+}
+
+impl StdError for QuantityParseError {}
+
+/// Takes a single segment from a list of segments and parses a [range][NumRange] out of it.
+///
+/// # Errors
+///
+/// - If the list of segments is empty, [`ArgumentError::Missing`] is returned.
+/// - If the segment is not a valid range, [`ArgumentError::Argument`] is returned.
+pub async fn range_argument<T>(
+    _ctx: &Context,
+    _parse_ctx: &ParseContext<'_>,
+    segments: &mut ArgumentSegments<'_>,
+) -> Result<NumRange<T>, ArgumentError<RangeParseError<T::Err>>>
+where
+    T: std::str::FromStr + PartialOrd,
+{
+    match segments.next() {
+        Some(seg) => seg.parse::<NumRange<T>>().map_err(ArgumentError::Argument),
+        None => Err(ArgumentError::Missing),
+    }
+}
+
+/// A range of numbers, such as `1..10`.
+///
+/// It can be parsed from:
+/// - an exclusive range, `a..b`, which does not contain `b`;
+/// - an inclusive range, `a..=b`;
+/// - an inclusive range separated by a hyphen, `a-b`.
+///
+/// The start of the range must not be greater than its end.
+///
+/// The hyphen form is ambiguous with negative numbers. The range is split at the first
+/// hyphen after the first character, such that `-5-10` is the range from `-5` to `10`,
+/// and `1--5` is the range from `1` to `-5`. Prefer the forms with `..` where negative
+/// numbers are expected.
+///
+/// As it implements [`FromStr`][std::str::FromStr], it also implements the [`Parse`] trait.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_framework::argument::{NumRange, RangeParseError};
+///
+/// let range = "1..4".parse::<NumRange<u32>>().unwrap();
+/// assert_eq!((range.start, range.end, range.inclusive), (1, 4, false));
+/// assert_eq!(range.iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+///
+/// let range = "1..=4".parse::<NumRange<u32>>().unwrap();
+/// assert_eq!(range.iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+///
+/// let range = "-2-2".parse::<NumRange<i32>>().unwrap();
+/// assert_eq!((range.start, range.end, range.inclusive), (-2, 2, true));
+/// assert!(range.contains(&0));
+///
+/// assert_eq!("5..1".parse::<NumRange<u32>>(), Err(RangeParseError::Reversed));
+/// assert_eq!("5".parse::<NumRange<u32>>(), Err(RangeParseError::Malformed));
+/// assert!(matches!("1..300".parse::<NumRange<u8>>(), Err(RangeParseError::Number(_))));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumRange<T> {
+    /// The start of the range.
+    pub start: T,
+    /// The end of the range.
+    pub end: T,
+    /// A boolean indicating whether the range contains its end.
+    pub inclusive: bool,
+}
+
+impl<T: PartialOrd> NumRange<T> {
+    /// Returns a boolean indicating whether the range contains the number.
+    pub fn contains(&self, n: &T) -> bool {
+        *n >= self.start && if self.inclusive { *n <= self.end } else { *n < self.end }
+    }
+}
+
+impl<T> NumRange<T>
+where
+    T: Copy + PartialEq,
+    std::ops::RangeInclusive<T>: Iterator<Item = T>,
+{
+    /// Returns an iterator over the numbers of the range.
+    pub fn iter(&self) -> impl Iterator<Item = T> {
+        let end = self.end;
+        let inclusive = self.inclusive;
+
+        (self.start..=self.end).filter(move |n| inclusive || *n != end)
+    }
+}
+
+impl<T> std::str::FromStr for NumRange<T>
+where
+    T: std::str::FromStr + PartialOrd,
+{
+    type Err = RangeParseError<T::Err>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end, inclusive) = if let Some(index) = s.find("..=") {
+            (&s[..index], &s[index + 3..], true)
+        } else if let Some(index) = s.find("..") {
+            (&s[..index], &s[index + 2..], false)
+        } else {
+            // Skip the first character, which may be the sign of the start.
+            match s.char_indices().skip(1).find(|(_, c)| *c == '-') {
+                Some((index, _)) => (&s[..index], &s[index + 1..], true),
+                None => return Err(RangeParseError::Malformed),
+            }
+        };
+
+        if start.is_empty() || end.is_empty() {
+            return Err(RangeParseError::Malformed);
+        }
+
+        let start = start.parse::<T>().map_err(RangeParseError::Number)?;
+        let end = end.parse::<T>().map_err(RangeParseError::Number)?;
+
+        if start > end {
+            return Err(RangeParseError::Reversed);
+        }
+
+        Ok(NumRange {
+            start,
+            end,
+            inclusive,
+        })
+    }
+}
+
+/// Error that is returned when parsing a [`NumRange`] fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RangeParseError<E> {
+    /// The input is not a range.
+    Malformed,
+    /// A bound of the range is not a valid number, such as when it overflows.
+    /// Contains the error from parsing the number.
+    Number(E),
+    /// The start of the range is greater than its end.
+    Reversed,
+}
+
+impl<E: fmt::Display> fmt::Display for RangeParseError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeParseError::Malformed => f.write_str("invalid range"),
+            RangeParseError::Number(err) => write!(f, "invalid bound of range: {}", err),
+            RangeParseError::Reversed => {
+                f.write_str("the start of a range must not exceed its end")
+            },
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> StdError for RangeParseError<E> {}
+
+/// Takes a single segment from a list of segments and parses a [boolean][Bool] out of it.
+///
+/// # Errors
+///
+/// - If the list of segments is empty, [`ArgumentError::Missing`] is returned.
+/// - If the segment is not a valid boolean, [`ArgumentError::Argument`] is returned.
+pub async fn bool_argument(
+    _ctx: &Context,
+    _parse_ctx: &ParseContext<'_>,
+    segments: &mut ArgumentSegments<'_>,
+) -> Result<bool, ArgumentError<BoolParseError>> {
+    match segments.next() {
+        Some(seg) => seg.parse::<Bool>().map(Bool::get).map_err(ArgumentError::Argument),
+        None => Err(ArgumentError::Missing),
+    }
+}
+
+/// A boolean given in words.
+///
+/// It can be parsed from `true`, `yes`, `y`, `on`, `enable`, `enabled` or `1` for `true`,
+/// and from `false`, `no`, `n`, `off`, `disable`, `disabled` or `0` for `false`.
+/// The words are case-insensitive. Any other input is rejected.
+///
+/// As it implements [`FromStr`][std::str::FromStr], it also implements the [`Parse`] trait.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_framework::argument::{Bool, BoolParseError};
+///
+/// for s in &["true", "yes", "y", "on", "enable", "enabled", "1", "YES", "On"] {
+///     assert_eq!(s.parse::<Bool>().map(Bool::get), Ok(true));
+/// }
+///
+/// for s in &["false", "no", "n", "off", "disable", "disabled", "0", "NO", "Off"] {
+///     assert_eq!(s.parse::<Bool>().map(Bool::get), Ok(false));
+/// }
+///
+/// assert_eq!("maybe".parse::<Bool>(), Err(BoolParseError));
+/// assert_eq!("".parse::<Bool>(), Err(BoolParseError));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bool(bool);
+
+impl Bool {
+    /// Returns the boolean.
+    pub fn get(self) -> bool {
+        self.0
+    }
+}
+
+impl From<Bool> for bool {
+    fn from(b: Bool) -> Self {
+        b.0
+    }
+}
+
+impl std::str::FromStr for Bool {
+    type Err = BoolParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "true" | "yes" | "y" | "on" | "enable" | "enabled" | "1" => Ok(Bool(true)),
+            "false" | "no" | "n" | "off" | "disable" | "disabled" | "0" => Ok(Bool(false)),
+            _ => Err(BoolParseError),
+        }
+    }
+}
+
+/// Error that is returned when parsing a [`Bool`] fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoolParseError;
+
+impl fmt::Display for BoolParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("expected yes or no")
+    }
+}
+
+impl StdError for BoolParseError {}
+
+/// The maximum amount of members requested when [searching a guild][search] for a
+/// member by its tag or name.
+///
+/// [search]: serenity::model::id::GuildId::search_members
+const MEMBER_SEARCH_LIMIT: u64 = 100;
+
+/// Takes a single segment from a list of segments and resolves a [member][MemberArg] of
+/// the guild out of it.
+///
+/// The member is resolved in the guild of the message:
+/// - a mention or an identifier is resolved from the cache, or from HTTP if the member
+///   is not cached;
+/// - a tag or a name is looked up among the members of the guild in the cache, and then
+///   among the members returned by [searching the guild][search] with HTTP.
+///
+/// Refer to [`MemberArg::find`] for how tags and names are matched.
+///
+/// # Errors
+///
+/// - If the list of segments is empty, [`ArgumentError::Missing`] is returned.
+/// - If the arguments are not parsed in a guild, no member matches the segment, or
+///   several members match its name, [`ArgumentError::Argument`] is returned.
+///
+/// [search]: serenity::model::id::GuildId::search_members
+pub async fn member_argument(
+    ctx: &Context,
+    parse_ctx: &ParseContext<'_>,
+    segments: &mut ArgumentSegments<'_>,
+) -> Result<Member, ArgumentError<MemberParseError>> {
+    let arg = match segments.next() {
+        Some(seg) => seg.parse::<MemberArg>().map_err(ArgumentError::Argument)?,
+        None => return Err(ArgumentError::Missing),
+    };
+
+    let guild_id = match parse_ctx.msg.guild_id {
+        Some(guild_id) => guild_id,
+        None => return Err(ArgumentError::Argument(MemberParseError::NotInGuild)),
+    };
+
+    resolve_member(ctx, guild_id, &arg).await.map_err(ArgumentError::Argument)
+}
+
+/// Resolves the member of the guild, first from the cache, and then from HTTP.
+async fn resolve_member(
+    ctx: &Context,
+    guild_id: GuildId,
+    arg: &MemberArg,
+) -> Result<Member, MemberParseError> {
+    let query = match arg {
+        MemberArg::Id(id) => {
+            return guild_id.member(ctx, *id).await.map_err(|_| MemberParseError::NotFound);
+        },
+        MemberArg::Tag {
+            name, ..
+        }
+        | MemberArg::Name(name) => name,
+    };
+
+    let cached = ctx
+        .cache
+        .guild_field(guild_id, |guild| arg.find(guild.members.values()).map(|m| m.cloned()))
+        .await;
+
+    match cached {
+        Some(Ok(Some(member))) => return Ok(member),
+        Some(Err(err)) => return Err(err),
+        // Members of large guilds are not necessarily cached.
+        Some(Ok(None)) | None => {},
+    }
+
+    let members = guild_id
+        .search_members(&ctx.http, query, Some(MEMBER_SEARCH_LIMIT))
+        .await
+        .map_err(|_| MemberParseError::NotFound)?;
+
+    arg.find(&members)?.cloned().ok_or(MemberParseError::NotFound)
+}
+
+/// A member argument.
+///
+/// It can be parsed from, in order of precedence:
+/// - a mention of the user, such as `<@123>` or `<@!123>`;
+/// - an identifier of the user, such as `123`;
+/// - a tag of the user, such as `ferris#0001`;
+/// - any other text, which is the nickname or the username of the member.
+///
+/// As it implements [`FromStr`][std::str::FromStr], it also implements the [`Parse`] trait.
+/// Refer to [`member_argument`] for resolving the member in a guild.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity::model::id::UserId;
+/// use serenity_framework::argument::{MemberArg, MemberParseError};
+///
+/// assert_eq!("<@123>".parse::<MemberArg>(), Ok(MemberArg::Id(UserId(123))));
+/// assert_eq!("<@!123>".parse::<MemberArg>(), Ok(MemberArg::Id(UserId(123))));
+/// assert_eq!("123".parse::<MemberArg>(), Ok(MemberArg::Id(UserId(123))));
+///
+/// assert_eq!(
+///     "ferris#0001".parse::<MemberArg>(),
+///     Ok(MemberArg::Tag {
+///         name: "ferris".to_string(),
+///         discriminator: 1,
+///     })
+/// );
+///
+/// assert_eq!("Ferris".parse::<MemberArg>(), Ok(MemberArg::Name("Ferris".to_string())));
+/// assert_eq!("C#".parse::<MemberArg>(), Ok(MemberArg::Name("C#".to_string())));
+///
+/// assert_eq!("".parse::<MemberArg>(), Err(MemberParseError::NotFound));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemberArg {
+    /// A mention or an identifier of the user.
+    Id(UserId),
+    /// A tag of the user.
+    Tag {
+        /// The username of the user.
+        name: String,
+        /// The discriminator of the user.
+        discriminator: u16,
+    },
+    /// A nickname or a username of the member.
+    Name(String),
+}
+
+impl MemberArg {
+    /// Returns the member that matches the argument among the members.
+    ///
+    /// An identifier matches the user of the member, and a tag matches its username and
+    /// discriminator. A name matches the nickname or the username of the member, and
+    /// matches regardless of casing if no member has the name in the exact casing.
+    ///
+    /// If no member matches, `Ok(None)` is returned.
+    ///
+    /// # Errors
+    ///
+    /// If several members match a name, [`MemberParseError::Ambiguous`] is returned.
+    pub fn find<'a, I>(&self, members: I) -> Result<Option<&'a Member>, MemberParseError>
+    where
+        I: IntoIterator<Item = &'a Member>,
+    {
+        let mut members = members.into_iter();
+
+        let name = match self {
+            MemberArg::Id(id) => return Ok(members.find(|m| m.user.id == *id)),
+            MemberArg::Tag {
+                name,
+                discriminator,
+            } => {
+                return Ok(members
+                    .find(|m| m.user.discriminator == *discriminator && m.user.name == *name));
+            },
+            MemberArg::Name(name) => name,
+        };
+
+        let lowercase = name.to_lowercase();
+
+        let mut exact = Vec::new();
+        let mut insensitive = Vec::new();
+
+        for member in members {
+            let mut names = std::iter::once(&member.user.name).chain(member.nick.as_ref());
+
+            if names.clone().any(|n| n == name) {
+                exact.push(member);
+            } else if names.any(|n| n.to_lowercase() == lowercase) {
+                insensitive.push(member);
+            }
+        }
+
+        let matches = if exact.is_empty() { insensitive } else { exact };
+
+        match matches[..] {
+            [] => Ok(None),
+            [member] => Ok(Some(member)),
+            _ => Err(MemberParseError::Ambiguous(matches.len())),
+        }
+    }
+}
+
+impl std::str::FromStr for MemberArg {
+    type Err = MemberParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(MemberParseError::NotFound);
+        }
+
+        let id = match s.strip_prefix("<@").and_then(|s| s.strip_suffix('>')) {
+            Some(mention) => mention.strip_prefix('!').unwrap_or(mention),
+            None => s,
+        };
+
+        if let Ok(id) = id.parse::<u64>() {
+            return Ok(MemberArg::Id(UserId(id)));
+        }
+
+        let tag = s.rsplit_once('#').filter(|(name, discriminator)| {
+            !name.is_empty()
+                && discriminator.len() == 4
+                && discriminator.bytes().all(|b| b.is_ascii_digit())
+        });
+
+        if let Some((name, discriminator)) = tag {
+            return Ok(MemberArg::Tag {
+                name: name.to_string(),
+                // Four digits always fit.
+                discriminator: discriminator.parse().unwrap_or_default(),
+            });
+        }
+
+        Ok(MemberArg::Name(s.to_string()))
+    }
+}
+
+/// Error that is returned when resolving a [member][MemberArg] fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemberParseError {
+    /// The arguments are not parsed in a guild.
+    NotInGuild,
+    /// No member matches the argument.
+    NotFound,
+    /// Several members match the name. Contains the amount of matching members.
+    Ambiguous(usize),
+}
+
+impl fmt::Display for MemberParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MemberParseError::NotInGuild => f.write_str("members can only be given in guilds"),
+            MemberParseError::NotFound => f.write_str("member not found"),
+            MemberParseError::Ambiguous(n) => write!(f, "{} members match the name", n),
+        }
+    }
+}
+
+impl StdError for MemberParseError {}
+
 /// Denotes a type that can be either one of two different types.
 ///
 /// It derives the [`Parse`] trait and can be used to parse an argument as either of two types.
@@ -1,18 +1,42 @@
 //! A list of separate, but related commands.
+//!
+//! # Migrating from groups
+//!
+//! Frameworks based on groups tie the display of commands to their invocation.
+//! Here, the two are separate concepts:
+//!
+//! - A group without prefixes is a [category][cat], which only affects how commands
+//!   are displayed together.
+//! - A group with a prefix is a [prefixed category][prefixed], whose commands are only
+//!   invocable after the prefix.
+//! - Subgroups are [subcommands] of a command.
+//! - The top level groups are the [root level commands][root] and the categories.
+//!
+//! [cat]: crate::configuration::Configuration::category
+//! [prefixed]: crate::configuration::Configuration::prefixed_category
+//! [subcommands]: crate::command::Command::subcommands
+//! [root]: crate::configuration::Configuration::root_level_commands
 
 use crate::command::CommandId;
 
 /// Grouping of independent commands with a related theme.
 ///
 /// This grouping, or "categorization" is not special in any way and
-/// does not affect invocation of commands. The type serves to simplify
-/// [registration of commands][register] and displaying commands together in help messages.
+/// does not affect invocation of commands, unless the category has a [`prefix`].
+/// The type serves to simplify [registration of commands][register] and displaying
+/// commands together in help messages.
 ///
+/// [`prefix`]: Self::prefix
 /// [register]: crate::configuration::Configuration::command
 #[derive(Debug, Default, Clone)]
 pub struct Category {
     /// Name of the category.
     pub name: String,
+    /// An optional prefix that must precede the commands of this category.
+    ///
+    /// If present, the commands are only invocable as `<prefix> <command>`,
+    /// instead of `<command>`.
+    pub prefix: Option<String>,
     /// [`Command`][cmd]s pertaining to this category.
     ///
     /// [cmd]: crate::command::Command
@@ -5,15 +5,26 @@
 //! it succeeded or failed. In the case of failure, additional information
 //! can be given, a reason, that describes the failure.
 //!
+//! Checks can be composed with the [`any`], [`all`] and [`not`] combinators.
+//!
+//! The framework provides the [`has_role`] check for the most common requirement
+//! of a command, a role of the author.
+//!
 //! [command]: crate::command
 
+use std::collections::HashMap;
 use std::error::Error as StdError;
 use std::fmt::{self, Display};
+use std::sync::Arc;
 
+use serenity::client::Context as SerenityContext;
 use serenity::futures::future::BoxFuture;
 use serenity::model::channel::Message;
+use serenity::model::guild::Role;
+use serenity::model::id::{GuildId, RoleId};
 
 use crate::context::CheckContext;
+use crate::utils::cache_or_http;
 
 /// The reason describing why a check failed.
 ///
@@ -21,7 +32,7 @@ use crate::context::CheckContext;
 ///
 /// This information is not handled by the framework; it is only propagated
 /// to the consumer of the framework.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum Reason {
     /// There is no information.
@@ -53,6 +64,63 @@ impl Display for Reason {
     }
 }
 
+impl Reason {
+    /// Combines multiple reasons into one.
+    ///
+    /// Information for the user and for logging purposes is joined separately
+    /// with `; `. If none of the reasons contain information, [`Reason::Unknown`]
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_framework::check::Reason;
+    ///
+    /// let reason = Reason::combine(vec![
+    ///     Reason::User("not an admin".to_string()),
+    ///     Reason::Unknown,
+    ///     Reason::UserAndLog {
+    ///         user: "not the owner".to_string(),
+    ///         log: "owner check failed".to_string(),
+    ///     },
+    /// ]);
+    ///
+    /// assert_eq!(reason.to_string(), "User: not an admin; not the owner; Log: owner check failed");
+    /// ```
+    pub fn combine<I>(reasons: I) -> Self
+    where
+        I: IntoIterator<Item = Reason>,
+    {
+        let mut users = Vec::new();
+        let mut logs = Vec::new();
+
+        for reason in reasons {
+            match reason {
+                Reason::Unknown => {},
+                Reason::User(user) => users.push(user),
+                Reason::Log(log) => logs.push(log),
+                Reason::UserAndLog {
+                    user,
+                    log,
+                } => {
+                    users.push(user);
+                    logs.push(log);
+                },
+            }
+        }
+
+        match (users.is_empty(), logs.is_empty()) {
+            (true, true) => Reason::Unknown,
+            (false, true) => Reason::User(users.join("; ")),
+            (true, false) => Reason::Log(logs.join("; ")),
+            (false, false) => Reason::UserAndLog {
+                user: users.join("; "),
+                log: logs.join("; "),
+            },
+        }
+    }
+}
+
 impl StdError for Reason {}
 
 /// The result type of a [check function][fn]
@@ -61,8 +129,17 @@ impl StdError for Reason {}
 pub type CheckResult<T = ()> = std::result::Result<T, Reason>;
 
 /// The definition of a check function.
-pub type CheckFn<D, E> =
-    for<'fut> fn(&'fut CheckContext<'_, D, E>, &'fut Message) -> BoxFuture<'fut, CheckResult<()>>;
+///
+/// The function is boxed, as the [combinators][any] capture the checks
+/// they combine.
+pub type CheckFn<D, E> = Arc<
+    dyn for<'fut> Fn(
+            &'fut CheckContext<'_, D, E>,
+            &'fut Message,
+        ) -> BoxFuture<'fut, CheckResult<()>>
+        + Send
+        + Sync,
+>;
 
 /// A constructor of the [`Check`] type provided by the consumer of the framework.
 pub type CheckConstructor<D, E> = fn() -> Check<D, E>;
@@ -90,7 +167,7 @@ impl<D, E> Clone for Check<D, E> {
     fn clone(&self) -> Self {
         Self {
             name: self.name.clone(),
-            function: self.function,
+            function: Arc::clone(&self.function),
             check_in_help: self.check_in_help,
             display_in_help: self.display_in_help,
         }
@@ -101,7 +178,7 @@ impl<D, E> Default for Check<D, E> {
     fn default() -> Self {
         Self {
             name: String::default(),
-            function: |_, _| Box::pin(async move { Ok(()) }),
+            function: Arc::new(|_, _| Box::pin(async move { Ok(()) })),
             check_in_help: true,
             display_in_help: true,
         }
@@ -152,8 +229,17 @@ impl<D, E> CheckBuilder<D, E> {
         }
     }
     /// Assigns the function to this function.
-    pub fn function(mut self, function: CheckFn<D, E>) -> Self {
-        self.inner.function = function;
+    pub fn function<F>(mut self, function: F) -> Self
+    where
+        F: for<'fut> Fn(
+                &'fut CheckContext<'_, D, E>,
+                &'fut Message,
+            ) -> BoxFuture<'fut, CheckResult<()>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.inner.function = Arc::new(function);
         self
     }
 
@@ -196,3 +282,289 @@ impl<D, E> fmt::Debug for CheckBuilder<D, E> {
         f.debug_struct("CheckBuilder").field("inner", &self.inner).finish()
     }
 }
+
+/// Combines checks into a check that passes if any of them passes.
+///
+/// The checks are run in order until one passes. If all of them fail, the
+/// reasons of their failures are [combined][Reason::combine]. The check passes
+/// if no checks are given.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_framework::check::{self, Check};
+/// use serenity_framework::{DefaultData, DefaultError};
+/// # use serenity_framework::prelude::*;
+/// # use serenity::model::channel::Message;
+/// #
+/// # #[check]
+/// # async fn admin(_ctx: &CheckContext<'_>, _msg: &Message) -> CheckResult {
+/// #     Ok(())
+/// # }
+/// #
+/// # #[check]
+/// # async fn owner(_ctx: &CheckContext<'_>, _msg: &Message) -> CheckResult {
+/// #     Ok(())
+/// # }
+///
+/// fn admin_or_owner() -> Check<DefaultData, DefaultError> {
+///     check::any(&[admin, owner])
+/// }
+///
+/// assert_eq!(admin_or_owner().name, "admin or owner");
+/// ```
+pub fn any<D, E>(checks: &[CheckConstructor<D, E>]) -> Check<D, E>
+where
+    D: Send + Sync + 'static,
+    E: Send + Sync + 'static,
+{
+    let checks = construct(checks);
+    let name = join_names(&checks, " or ");
+    let (check_in_help, display_in_help) = help_flags(&checks);
+    let checks = Arc::new(checks);
+
+    CheckBuilder::new(name)
+        .function(move |ctx, msg| {
+            let checks = Arc::clone(&checks);
+
+            Box::pin(async move {
+                let mut reasons = Vec::new();
+
+                for check in checks.iter() {
+                    match (check.function)(ctx, msg).await {
+                        Ok(()) => return Ok(()),
+                        Err(reason) => reasons.push(reason),
+                    }
+                }
+
+                if reasons.is_empty() {
+                    Ok(())
+                } else {
+                    Err(Reason::combine(reasons))
+                }
+            })
+        })
+        .check_in_help(check_in_help)
+        .display_in_help(display_in_help)
+        .build()
+}
+
+/// Combines checks into a check that passes if all of them pass.
+///
+/// The checks are run in order until one fails, whose reason is returned.
+/// The check passes if no checks are given.
+pub fn all<D, E>(checks: &[CheckConstructor<D, E>]) -> Check<D, E>
+where
+    D: Send + Sync + 'static,
+    E: Send + Sync + 'static,
+{
+    let checks = construct(checks);
+    let name = join_names(&checks, " and ");
+    let (check_in_help, display_in_help) = help_flags(&checks);
+    let checks = Arc::new(checks);
+
+    CheckBuilder::new(name)
+        .function(move |ctx, msg| {
+            let checks = Arc::clone(&checks);
+
+            Box::pin(async move {
+                for check in checks.iter() {
+                    (check.function)(ctx, msg).await?;
+                }
+
+                Ok(())
+            })
+        })
+        .check_in_help(check_in_help)
+        .display_in_help(display_in_help)
+        .build()
+}
+
+/// Inverts a check into a check that passes if it fails.
+///
+/// If the check passes, the inverted check fails with a [`Reason::Log`]
+/// naming the check.
+pub fn not<D, E>(check: CheckConstructor<D, E>) -> Check<D, E>
+where
+    D: Send + Sync + 'static,
+    E: Send + Sync + 'static,
+{
+    let check = Arc::new(check());
+    let name = format!("not {}", check.name);
+    let check_in_help = check.check_in_help;
+    let display_in_help = check.display_in_help;
+
+    CheckBuilder::new(name)
+        .function(move |ctx, msg| {
+            let check = Arc::clone(&check);
+
+            Box::pin(async move {
+                match (check.function)(ctx, msg).await {
+                    Ok(()) => Err(Reason::Log(format!("\"{}\" check passed", check.name))),
+                    Err(_) => Ok(()),
+                }
+            })
+        })
+        .check_in_help(check_in_help)
+        .display_in_help(display_in_help)
+        .build()
+}
+
+/// A role that is required by the [`has_role`] check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RoleSpec {
+    /// The role with the identifier.
+    Id(RoleId),
+    /// Any role with the name, among the roles of the guild.
+    Name(String),
+}
+
+impl RoleSpec {
+    /// Returns a boolean indicating whether the member roles contain this role.
+    ///
+    /// The `guild_roles` are used to resolve a [name][RoleSpec::Name], and are
+    /// ignored for an [identifier][RoleSpec::Id].
+    pub fn is_held(&self, member_roles: &[RoleId], guild_roles: &HashMap<RoleId, Role>) -> bool {
+        match self {
+            RoleSpec::Id(id) => member_roles.contains(id),
+            RoleSpec::Name(name) => guild_roles
+                .values()
+                .any(|role| &role.name == name && member_roles.contains(&role.id)),
+        }
+    }
+}
+
+impl Display for RoleSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RoleSpec::Id(id) => write!(f, "{}", id.0),
+            RoleSpec::Name(name) => f.write_str(name),
+        }
+    }
+}
+
+impl From<RoleId> for RoleSpec {
+    fn from(id: RoleId) -> Self {
+        RoleSpec::Id(id)
+    }
+}
+
+impl From<u64> for RoleSpec {
+    fn from(id: u64) -> Self {
+        RoleSpec::Id(RoleId(id))
+    }
+}
+
+impl From<&str> for RoleSpec {
+    fn from(name: &str) -> Self {
+        RoleSpec::Name(name.to_string())
+    }
+}
+
+impl From<String> for RoleSpec {
+    fn from(name: String) -> Self {
+        RoleSpec::Name(name)
+    }
+}
+
+/// Creates a check that passes if the author of the message has the role.
+///
+/// The roles of the author are taken from the message, or from the cache or Discord
+/// if the message does not contain them. A role [name][RoleSpec::Name] is resolved
+/// against the roles of the guild from the cache, or from Discord.
+///
+/// The check fails with a [`Reason::User`] if the author does not have the role,
+/// or if the message was sent in direct messages. It fails with a [`Reason::Log`]
+/// if the roles cannot be fetched.
+///
+/// The check can be added to a command with the `required_role` attribute of the
+/// `#[command]` macro, or with [`CommandBuilder::required_role`].
+///
+/// [`CommandBuilder::required_role`]: crate::command::CommandBuilder::required_role
+pub fn has_role<D, E>(spec: RoleSpec) -> Check<D, E>
+where
+    D: Send + Sync + 'static,
+    E: Send + Sync + 'static,
+{
+    let name = format!("has role {}", spec);
+    let spec = Arc::new(spec);
+
+    CheckBuilder::new(name)
+        .function(move |ctx, msg| {
+            let spec = Arc::clone(&spec);
+
+            Box::pin(async move {
+                let guild_id = match msg.guild_id {
+                    Some(guild_id) => guild_id,
+                    None => {
+                        return Err(Reason::User(
+                            "This command can only be used in a server.".to_string(),
+                        ))
+                    },
+                };
+
+                let member_roles = member_roles(ctx.serenity_ctx, msg, guild_id)
+                    .await
+                    .map_err(|err| Reason::Log(format!("failed to fetch the member: {}", err)))?;
+
+                let guild_roles = match &*spec {
+                    RoleSpec::Id(_) => HashMap::new(),
+                    RoleSpec::Name(_) => {
+                        guild_roles(ctx.serenity_ctx, guild_id).await.map_err(|err| {
+                            Reason::Log(format!("failed to fetch the roles: {}", err))
+                        })?
+                    },
+                };
+
+                if spec.is_held(&member_roles, &guild_roles) {
+                    Ok(())
+                } else {
+                    Err(Reason::User(format!("You need the {} role.", spec)))
+                }
+            })
+        })
+        .build()
+}
+
+/// Returns the roles of the author of the message.
+async fn member_roles(
+    ctx: &SerenityContext,
+    msg: &Message,
+    guild_id: GuildId,
+) -> serenity::Result<Vec<RoleId>> {
+    if let Some(member) = &msg.member {
+        return Ok(member.roles.clone());
+    }
+
+    let member = cache_or_http(ctx.cache.member(guild_id, msg.author.id), async {
+        ctx.http.get_member(guild_id.0, msg.author.id.0).await
+    })
+    .await?;
+
+    Ok(member.roles)
+}
+
+/// Returns the roles of the guild.
+async fn guild_roles(
+    ctx: &SerenityContext,
+    guild_id: GuildId,
+) -> serenity::Result<HashMap<RoleId, Role>> {
+    cache_or_http(ctx.cache.guild_roles(guild_id), guild_id.roles(&ctx.http)).await
+}
+
+fn construct<D, E>(checks: &[CheckConstructor<D, E>]) -> Vec<Check<D, E>> {
+    checks.iter().map(|check| check()).collect()
+}
+
+fn join_names<D, E>(checks: &[Check<D, E>], separator: &str) -> String {
+    checks.iter().map(|check| check.name.as_str()).collect::<Vec<_>>().join(separator)
+}
+
+/// Returns the help indicators of a combination of checks, which apply only
+/// if they apply to all of the checks.
+fn help_flags<D, E>(checks: &[Check<D, E>]) -> (bool, bool) {
+    let check_in_help = checks.iter().all(|check| check.check_in_help);
+    let display_in_help = checks.iter().all(|check| check.display_in_help);
+
+    (check_in_help, display_in_help)
+}
@@ -6,17 +6,81 @@
 //! information that relays to the user what it does, what it is for, and how it
 //! is used. It may have [`check`]s to allow/deny a user's access to the command.
 //!
+//! # Examples
+//!
+//! Commands are usually defined with the `#[command]` macro, which generates a function
+//! constructing the [`Command`]. The function has the same name as the command function,
+//! unless it is given with the `builder` argument:
+//!
+//! ```rust
+//! use serenity::model::channel::Message;
+//! use serenity_framework::prelude::*;
+//!
+//! #[command("ping", "pong", builder = ping_command)]
+//! async fn ping(_ctx: FrameworkContext, _msg: &Message) -> CommandResult {
+//!     Ok(())
+//! }
+//!
+//! let command = ping_command();
+//!
+//! assert_eq!(command.names, vec!["ping".to_string(), "pong".to_string()]);
+//! ```
+//!
+//! A command may have several checks, which are run in the order they are given.
+//! They may be given in one `check` attribute or across multiple:
+//!
+//! ```rust
+//! use serenity::model::channel::Message;
+//! use serenity_framework::prelude::*;
+//!
+//! #[check]
+//! async fn guild_only(_ctx: &CheckContext<'_>, msg: &Message) -> CheckResult {
+//!     msg.guild_id.map(|_| ()).ok_or(Reason::Unknown)
+//! }
+//!
+//! #[check]
+//! async fn not_bot(_ctx: &CheckContext<'_>, msg: &Message) -> CheckResult {
+//!     if msg.author.bot {
+//!         Err(Reason::Unknown)
+//!     } else {
+//!         Ok(())
+//!     }
+//! }
+//!
+//! #[check]
+//! async fn not_muted(_ctx: &CheckContext<'_>, _msg: &Message) -> CheckResult {
+//!     Ok(())
+//! }
+//!
+//! #[command]
+//! #[check(guild_only, not_bot)]
+//! #[check(not_muted)]
+//! async fn kick(_ctx: FrameworkContext, _msg: &Message) -> CommandResult {
+//!     Ok(())
+//! }
+//!
+//! let command = kick();
+//! let names = command.checks.iter().map(|check| check.name.as_str()).collect::<Vec<_>>();
+//!
+//! assert_eq!(names, vec!["guild_only", "not_bot", "not_muted"]);
+//! ```
+//!
 //! [`check`]: crate::check
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::error::Error as StdError;
 use std::fmt;
 
+use serenity::builder::CreateEmbed;
+use serenity::client::Context as SerenityContext;
 use serenity::futures::future::BoxFuture;
 use serenity::model::channel::Message;
+use serenity::model::permissions::Permissions;
 
-use crate::check::{Check, CheckConstructor};
+use crate::check::{self, Check, CheckConstructor, RoleSpec};
 use crate::context::Context;
-use crate::utils::IdMap;
+use crate::error::{DispatchError, Error, FromSerenityError};
+use crate::utils::{ArgumentSegments, DefaultTokenizer, IdMap, Tokenizer};
 use crate::DefaultError;
 
 /// A function to dynamically create a string.
@@ -38,12 +102,316 @@ pub type CommandMap<D, E> = IdMap<String, CommandId, Command<D, E>>;
 
 /// The result type of a [command function][fn].
 ///
+/// The `?` operator converts errors into the error type `E` with the [`From`] trait.
+/// The [default error type][DefaultError] can be converted from any error. A custom
+/// error type must implement `From` for every error that is propagated with `?`,
+/// such as [`serenity::Error`]:
+///
+/// ```rust
+/// use std::fmt;
+///
+/// use serenity::model::channel::Message;
+/// use serenity_framework::command::CommandResult;
+/// use serenity_framework::context::Context;
+/// use serenity_framework::prelude::command;
+///
+/// #[derive(Debug)]
+/// enum MyError {
+///     Serenity(serenity::Error),
+///     NotFound,
+/// }
+///
+/// impl From<serenity::Error> for MyError {
+///     fn from(err: serenity::Error) -> Self {
+///         MyError::Serenity(err)
+///     }
+/// }
+///
+/// async fn send(_ctx: &Context<(), MyError>) -> serenity::Result<()> {
+///     Ok(())
+/// }
+///
+/// #[command]
+/// async fn ping(ctx: Context<(), MyError>, _msg: &Message) -> CommandResult<(), MyError> {
+///     send(&ctx).await?;
+///
+///     Err(MyError::NotFound)
+/// }
+/// ```
+///
+/// Alternatively, `From<serenity::Error>` may be implemented in terms of
+/// [`FromSerenityError`] with the [`from_serenity_error`] macro, or [`CommandError`]
+/// may be used as the error type.
+///
 /// [fn]: CommandFn
+/// [`from_serenity_error`]: crate::from_serenity_error
 pub type CommandResult<T = (), E = DefaultError> = std::result::Result<T, E>;
 
+/// An error type for commands that distinguishes errors of Serenity from other errors.
+///
+/// It can be converted from a [`serenity::Error`], any boxed error, or a message
+/// describing the error.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity::model::channel::Message;
+/// use serenity_framework::command::{CommandError, CommandResult};
+/// use serenity_framework::context::Context;
+/// use serenity_framework::prelude::command;
+///
+/// async fn send() -> serenity::Result<()> {
+///     Ok(())
+/// }
+///
+/// #[command]
+/// async fn ping(
+///     _ctx: Context<(), CommandError>,
+///     _msg: &Message,
+/// ) -> CommandResult<(), CommandError> {
+///     send().await?;
+///
+///     let n = "42".parse::<u32>().map_err(|err| CommandError::Other(Box::new(err)))?;
+///
+///     if n != 42 {
+///         return Err("unexpected number".into());
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub enum CommandError {
+    /// An error of Serenity.
+    Serenity(serenity::Error),
+    /// Any other error.
+    Other(DefaultError),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::Serenity(err) => fmt::Display::fmt(err, f),
+            CommandError::Other(err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+impl StdError for CommandError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            CommandError::Serenity(err) => Some(err),
+            CommandError::Other(err) => Some(&**err),
+        }
+    }
+}
+
+impl FromSerenityError for CommandError {
+    fn from_serenity_error(err: serenity::Error) -> Self {
+        CommandError::Serenity(err)
+    }
+}
+
+crate::from_serenity_error!(CommandError);
+
+impl From<DefaultError> for CommandError {
+    fn from(err: DefaultError) -> Self {
+        CommandError::Other(err)
+    }
+}
+
+impl From<String> for CommandError {
+    fn from(msg: String) -> Self {
+        CommandError::Other(msg.into())
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(msg: &str) -> Self {
+        CommandError::Other(msg.into())
+    }
+}
+
+/// A message sent in response to a command.
+///
+/// Commands marked with `#[command(auto_respond)]` return a value that is
+/// [converted][conv] into a response, which the framework sends to the channel of
+/// the invocation after the body of the command has finished. If the response
+/// cannot be sent, the error is converted into the error type of the command
+/// with the [`From`] trait and returned as a user error.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use serenity::model::channel::Message;
+/// use serenity_framework::command::Response;
+/// use serenity_framework::prelude::*;
+///
+/// #[command(auto_respond)]
+/// async fn ping(_ctx: FrameworkContext, _msg: &Message) -> CommandResult<&'static str> {
+///     Ok("Pong!")
+/// }
+///
+/// #[command(auto_respond)]
+/// async fn greet(_ctx: FrameworkContext, msg: &Message, name: String) -> CommandResult<Response> {
+///     if name == msg.author.name {
+///         return Ok(Response::Reply("That is you!".to_string()));
+///     }
+///
+///     Ok(Response::Text(format!("Hello, {}!", name)))
+/// }
+/// ```
+///
+/// [conv]: IntoResponse
+#[derive(Debug, Clone)]
+pub enum Response {
+    /// No message is sent.
+    None,
+    /// A message with the text as its content.
+    Text(String),
+    /// A message with the embed.
+    Embed(CreateEmbed),
+    /// A message with the text as its content that replies to the invocation.
+    Reply(String),
+}
+
+impl Response {
+    /// Sends the response to the channel of the message.
+    ///
+    /// Returns the sent message, or `None` if the response is [`Response::None`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if sending the message fails.
+    pub async fn send(
+        self,
+        ctx: &SerenityContext,
+        msg: &Message,
+    ) -> serenity::Result<Option<Message>> {
+        let sent = match self {
+            Response::None => return Ok(None),
+            Response::Text(text) => msg.channel_id.say(&ctx.http, text).await?,
+            Response::Embed(embed) => {
+                msg.channel_id.send_message(&ctx.http, |m| m.set_embed(embed)).await?
+            },
+            Response::Reply(text) => msg.reply(ctx, text).await?,
+        };
+
+        Ok(Some(sent))
+    }
+}
+
+/// A type that can be converted into a [`Response`].
+///
+/// Text is converted into [`Response::Text`], an embed into [`Response::Embed`],
+/// and `()` into [`Response::None`]. An [`Option`] is converted into its value,
+/// or into [`Response::None`] if it is `None`.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_framework::command::{IntoResponse, Response};
+///
+/// assert!(matches!("pong".into_response(), Response::Text(text) if text == "pong"));
+/// assert!(matches!(Some("pong".to_string()).into_response(), Response::Text(_)));
+/// assert!(matches!(None::<String>.into_response(), Response::None));
+/// assert!(matches!(().into_response(), Response::None));
+/// ```
+pub trait IntoResponse {
+    /// Converts the value into a [`Response`].
+    fn into_response(self) -> Response;
+}
+
+impl IntoResponse for Response {
+    fn into_response(self) -> Response {
+        self
+    }
+}
+
+impl IntoResponse for () {
+    fn into_response(self) -> Response {
+        Response::None
+    }
+}
+
+impl IntoResponse for String {
+    fn into_response(self) -> Response {
+        Response::Text(self)
+    }
+}
+
+impl IntoResponse for &str {
+    fn into_response(self) -> Response {
+        Response::Text(self.to_string())
+    }
+}
+
+impl IntoResponse for CreateEmbed {
+    fn into_response(self) -> Response {
+        Response::Embed(self)
+    }
+}
+
+impl<T: IntoResponse> IntoResponse for Option<T> {
+    fn into_response(self) -> Response {
+        self.map_or(Response::None, IntoResponse::into_response)
+    }
+}
+
 /// The definition of a command function.
+///
+/// Unlike the [result of the command][res], the function returns an [`Error`],
+/// as the function generated by the [`command`] macro parses the arguments of
+/// the command before running its body. Failing to parse them yields an
+/// [`Error::Dispatch`], while an error of the body is wrapped in [`Error::User`].
+///
+/// # Migrating from `CommandResult`
+///
+/// Command functions previously returned a [`CommandResult`]. Functions that are
+/// assigned by hand with [`CommandBuilder::function`] must now wrap the error of
+/// their body in [`Error::User`]:
+///
+/// ```rust
+/// use serenity::futures::future::{BoxFuture, FutureExt};
+/// use serenity::model::channel::Message;
+/// use serenity_framework::command::{Command, CommandResult};
+/// use serenity_framework::context::Context;
+/// use serenity_framework::error::Error;
+/// use serenity_framework::DefaultError;
+///
+/// async fn ping_body(_ctx: Context, _msg: &Message) -> CommandResult {
+///     Ok(())
+/// }
+///
+/// fn ping(ctx: Context, msg: &Message) -> BoxFuture<'_, Result<(), Error<DefaultError>>> {
+///     async move { ping_body(ctx, msg).await.map_err(Error::User) }.boxed()
+/// }
+///
+/// let command = Command::builder("ping").function(ping).build();
+/// assert_eq!(command.names, vec!["ping"]);
+/// ```
+///
+/// Functions generated by the [`command`] macro are unaffected.
+///
+/// [res]: CommandResult
+/// [`command`]: crate::prelude::command
 pub type CommandFn<D, E> =
-    for<'a> fn(Context<D, E>, &'a Message) -> BoxFuture<'a, CommandResult<(), E>>;
+    for<'a> fn(Context<D, E>, &'a Message) -> BoxFuture<'a, Result<(), Error<E>>>;
+
+/// The definition of a function that parses the arguments of a command without
+/// running it.
+///
+/// Unlike the [command function][fn], it only parses the arguments given in the
+/// content of the message. Attachment, sticker and embed arguments are not bound.
+///
+/// Used for [`Command::argument_parser`].
+///
+/// [fn]: CommandFn
+pub type ArgumentParserFn = for<'a> fn(
+    &'a SerenityContext,
+    &'a Message,
+    ArgumentSegments<'a>,
+) -> BoxFuture<'a, Result<(), DispatchError>>;
 
 /// A constructor of the [`Command`] type provided by the consumer of the framework.
 pub type CommandConstructor<D, E> = fn() -> Command<D, E>;
@@ -56,6 +424,11 @@ pub struct CommandId(pub(crate) usize);
 
 impl CommandId {
     /// Converts the identifier to its internal representation.
+    ///
+    /// The representation may be restored into an identifier with
+    /// [`Configuration::command_id_from_usize`].
+    ///
+    /// [`Configuration::command_id_from_usize`]: crate::configuration::Configuration::command_id_from_usize
     pub fn into_usize(self) -> usize {
         self.0
     }
@@ -87,15 +460,33 @@ pub struct Command<D, E> {
     /// The function of this command.
     pub function: CommandFn<D, E>,
     /// The names of this command by which it can be invoked.
+    ///
+    /// The first name is the [primary name][primary], and the rest are
+    /// [aliases][aliases].
+    ///
+    /// [primary]: Self::primary_name
+    /// [aliases]: Self::aliases
     pub names: Vec<String>,
     /// The subcommands belonging to this command.
     pub subcommands: HashSet<CommandId>,
     /// A string describing this command.
     pub description: Option<String>,
+    /// Strings describing this command in specific locales, keyed by the locale.
+    ///
+    /// Refer to [`description_for`] for how a description is chosen.
+    ///
+    /// [`description_for`]: Self::description_for
+    pub localized_descriptions: HashMap<String, String>,
     /// A function to dynamically describe this command.
     pub dynamic_description: Option<StringHook<D, E>>,
     /// A string to express usage of this command.
     pub usage: Option<String>,
+    /// Strings to express usage of this command in specific locales, keyed by the locale.
+    ///
+    /// Refer to [`usage_for`] for how a usage is chosen.
+    ///
+    /// [`usage_for`]: Self::usage_for
+    pub localized_usage: HashMap<String, String>,
     /// A function to dynamically express usage of this command.
     pub dynamic_usage: Option<StringHook<D, E>>,
     /// A list of strings demonstrating usage of this command.
@@ -104,8 +495,88 @@ pub struct Command<D, E> {
     pub dynamic_examples: Option<StringsHook<D, E>>,
     /// A boolean to indicate whether the command can be shown in help commands.
     pub help_available: bool,
-    /// A function that allows/denies access to this command.
-    pub check: Option<Check<D, E>>,
+    /// A key by which the command is ordered among other commands in help commands.
+    ///
+    /// Refer to [`help_order`] for how commands are ordered.
+    ///
+    /// [`help_order`]: Self::help_order
+    pub sort_key: Option<i32>,
+    /// A boolean to indicate whether the command rejects arguments in excess of
+    /// the arguments it declares.
+    ///
+    /// Dispatching a command with extra arguments fails with
+    /// [`DispatchError::TooManyArguments`]:
+    #[cfg_attr(feature = "test-util", doc = "```rust")]
+    #[cfg_attr(not(feature = "test-util"), doc = "```rust,ignore")]
+    /// use serenity::futures::executor::block_on;
+    /// use serenity::model::channel::Message;
+    /// use serenity_framework::error::{DispatchError, Error};
+    /// use serenity_framework::prelude::*;
+    /// use serenity_framework::test_util;
+    ///
+    /// #[command]
+    /// #[deny_extra_args]
+    /// async fn double(_ctx: FrameworkContext, _msg: &Message, n: i64) {
+    ///     assert_eq!(n, 1);
+    /// }
+    ///
+    /// let mut conf = Configuration::new();
+    /// conf.prefix("!").command(double);
+    ///
+    /// let framework: Framework = Framework::new(conf);
+    ///
+    /// let ctx = test_util::context();
+    ///
+    /// assert!(block_on(framework.dispatch(&ctx, &test_util::message("!double 1"))).is_ok());
+    ///
+    /// let result = block_on(framework.dispatch(&ctx, &test_util::message("!double 1 2")));
+    /// assert!(matches!(result, Err(Error::Dispatch(DispatchError::TooManyArguments))));
+    /// ```
+    /// 
+    /// [`DispatchError::TooManyArguments`]: crate::error::DispatchError::TooManyArguments
+    pub deny_extra_args: bool,
+    /// Functions that allow/deny access to this command, called in order.
+    pub checks: Vec<Check<D, E>>,
+    /// Permissions that the bot must have in the channel to run this command.
+    pub bot_required_permissions: Permissions,
+    /// A boolean to indicate whether the command can only be run in NSFW channels.
+    ///
+    /// Refer to [`Configuration::nsfw_in_dms`] for whether the command can be run
+    /// in direct messages.
+    ///
+    /// [`Configuration::nsfw_in_dms`]: crate::configuration::Configuration::nsfw_in_dms
+    pub nsfw: bool,
+    /// A boolean to indicate whether the command can be run while the bot is in
+    /// [maintenance mode][maintenance].
+    ///
+    /// [maintenance]: crate::configuration::Configuration::maintenance
+    pub available_in_maintenance: bool,
+    /// The arguments of this command, in the order of their declaration.
+    pub arguments: Vec<ArgumentInfo>,
+    /// A function that parses the arguments of this command without running it.
+    ///
+    /// Refer to [`validate_examples`] for its use.
+    ///
+    /// [`validate_examples`]: Self::validate_examples
+    pub argument_parser: Option<ArgumentParserFn>,
+    /// The string that separates the arguments of this command.
+    ///
+    /// This may be overridden per invocation by the [`dynamic_delimiter`] hook.
+    ///
+    /// [`dynamic_delimiter`]: crate::configuration::Configuration::dynamic_delimiter
+    pub delimiter: String,
+    /// The strings that separate the arguments of this command in specific locales.
+    ///
+    /// If the [`locale_resolver`] hook resolves the locale of an invocation to a
+    /// locale in this map, its delimiter is used instead of the [`delimiter`].
+    ///
+    /// [`locale_resolver`]: crate::configuration::Configuration::locale_resolver
+    /// [`delimiter`]: Self::delimiter
+    pub delimiter_by_locale: HashMap<String, String>,
+    /// The tokenizer that splits the arguments of this command into segments.
+    ///
+    /// The default tokenizer is the [`DefaultTokenizer`].
+    pub tokenizer: &'static dyn Tokenizer,
 }
 
 impl<D, E> Clone for Command<D, E> {
@@ -116,13 +587,25 @@ impl<D, E> Clone for Command<D, E> {
             names: self.names.clone(),
             subcommands: self.subcommands.clone(),
             description: self.description.clone(),
+            localized_descriptions: self.localized_descriptions.clone(),
             dynamic_description: self.dynamic_description,
             usage: self.usage.clone(),
+            localized_usage: self.localized_usage.clone(),
             dynamic_usage: self.dynamic_usage,
             examples: self.examples.clone(),
             dynamic_examples: self.dynamic_examples,
             help_available: self.help_available,
-            check: self.check.clone(),
+            sort_key: self.sort_key,
+            deny_extra_args: self.deny_extra_args,
+            checks: self.checks.clone(),
+            bot_required_permissions: self.bot_required_permissions,
+            nsfw: self.nsfw,
+            available_in_maintenance: self.available_in_maintenance,
+            arguments: self.arguments.clone(),
+            argument_parser: self.argument_parser,
+            delimiter: self.delimiter.clone(),
+            delimiter_by_locale: self.delimiter_by_locale.clone(),
+            tokenizer: self.tokenizer,
         }
     }
 }
@@ -135,13 +618,25 @@ impl<D, E> Default for Command<D, E> {
             names: Vec::default(),
             subcommands: HashSet::default(),
             description: None,
+            localized_descriptions: HashMap::default(),
             dynamic_description: None,
             usage: None,
+            localized_usage: HashMap::default(),
             dynamic_usage: None,
             examples: Vec::default(),
             dynamic_examples: None,
             help_available: true,
-            check: None,
+            sort_key: None,
+            deny_extra_args: false,
+            checks: Vec::new(),
+            bot_required_permissions: Permissions::empty(),
+            nsfw: false,
+            available_in_maintenance: false,
+            arguments: Vec::default(),
+            argument_parser: None,
+            delimiter: " ".to_string(),
+            delimiter_by_locale: HashMap::default(),
+            tokenizer: &DefaultTokenizer,
         }
     }
 }
@@ -154,13 +649,25 @@ impl<D, E> fmt::Debug for Command<D, E> {
             .field("names", &self.names)
             .field("subcommands", &self.subcommands)
             .field("description", &self.description)
+            .field("localized_descriptions", &self.localized_descriptions)
             .field("dynamic_description", &"<fn>")
             .field("usage", &self.usage)
+            .field("localized_usage", &self.localized_usage)
             .field("dynamic_usage", &"<fn>")
             .field("examples", &self.examples)
             .field("dynamic_examples", &"<fn>")
             .field("help_available", &self.help_available)
-            .field("check", &self.check)
+            .field("sort_key", &self.sort_key)
+            .field("deny_extra_args", &self.deny_extra_args)
+            .field("checks", &self.checks)
+            .field("bot_required_permissions", &self.bot_required_permissions)
+            .field("nsfw", &self.nsfw)
+            .field("available_in_maintenance", &self.available_in_maintenance)
+            .field("arguments", &self.arguments)
+            .field("argument_parser", &"<fn>")
+            .field("delimiter", &self.delimiter)
+            .field("delimiter_by_locale", &self.delimiter_by_locale)
+            .field("tokenizer", &self.tokenizer)
             .finish()
     }
 }
@@ -175,6 +682,306 @@ impl<D, E> Command<D, E> {
     {
         CommandBuilder::new(name)
     }
+
+    /// Returns the primary name of the command, by which it is displayed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_framework::command::Command;
+    /// use serenity_framework::DefaultError;
+    ///
+    /// let command = Command::<(), DefaultError>::builder("ping").aliases(vec!["p", "pong"]).build();
+    ///
+    /// assert_eq!(command.primary_name(), "ping");
+    /// assert_eq!(command.aliases(), &["p".to_string(), "pong".to_string()]);
+    /// ```
+    pub fn primary_name(&self) -> &str {
+        &self.names[0]
+    }
+
+    /// Returns the aliases of the command, which are all of its names except the
+    /// [primary name][primary].
+    ///
+    /// [primary]: Self::primary_name
+    pub fn aliases(&self) -> &[String] {
+        &self.names[1..]
+    }
+
+    /// Returns the description of the command in the locale.
+    ///
+    /// If the command has no [description in the locale][localized], its
+    /// [default description][description] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::model::channel::Message;
+    /// use serenity_framework::prelude::*;
+    ///
+    /// /// Greets the author.
+    /// #[command]
+    /// #[description(locale = "de", text = "Begrüßt den Autor.")]
+    /// #[usage("hello")]
+    /// #[usage(locale = "de", text = "hallo")]
+    /// async fn hello(_ctx: FrameworkContext, _msg: &Message) {}
+    ///
+    /// let command = hello();
+    ///
+    /// assert_eq!(command.description_for("de"), Some("Begrüßt den Autor."));
+    /// assert_eq!(command.description_for("fr"), Some("Greets the author."));
+    /// assert_eq!(command.usage_for("de"), Some("hallo"));
+    /// assert_eq!(command.usage_for("en"), Some("hello"));
+    /// ```
+    ///
+    /// [localized]: Self::localized_descriptions
+    /// [description]: Self::description
+    pub fn description_for(&self, locale: &str) -> Option<&str> {
+        self.localized_descriptions.get(locale).or(self.description.as_ref()).map(String::as_str)
+    }
+
+    /// Returns the usage of the command in the locale.
+    ///
+    /// If the command has no [usage in the locale][localized], its
+    /// [default usage][usage] is returned.
+    ///
+    /// [localized]: Self::localized_usage
+    /// [usage]: Self::usage
+    pub fn usage_for(&self, locale: &str) -> Option<&str> {
+        self.localized_usage.get(locale).or(self.usage.as_ref()).map(String::as_str)
+    }
+
+    /// Returns the value by which the command is ordered among other commands in
+    /// help commands.
+    ///
+    /// Commands with a [sort key][key] come first, in ascending order of their keys.
+    /// Commands without a sort key follow them. Commands with the same key, or without
+    /// one, are ordered by their [primary name][primary].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_framework::command::Command;
+    /// use serenity_framework::DefaultError;
+    ///
+    /// let mut commands = vec![
+    ///     Command::<(), DefaultError>::builder("ping").build(),
+    ///     Command::builder("help").sort_key(1).build(),
+    ///     Command::builder("about").build(),
+    ///     Command::builder("setup").sort_key(0).build(),
+    /// ];
+    ///
+    /// commands.sort_by(|a, b| a.help_order().cmp(&b.help_order()));
+    ///
+    /// let names = commands.iter().map(|c| c.primary_name()).collect::<Vec<_>>();
+    /// assert_eq!(names, vec!["setup", "help", "about", "ping"]);
+    /// ```
+    ///
+    /// [key]: Self::sort_key
+    /// [primary]: Self::primary_name
+    pub fn help_order(&self) -> (bool, i32, &str) {
+        (self.sort_key.is_none(), self.sort_key.unwrap_or_default(), self.primary_name())
+    }
+
+    /// Returns the names of the [checks] of the command that are
+    /// [displayed in help commands][display], in the order they are run.
+    ///
+    /// Global checks are not included. Refer to [`Configuration::check_names`] for the
+    /// names of all checks that apply to the command.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::model::channel::Message;
+    /// use serenity_framework::prelude::*;
+    ///
+    /// #[check("Manage Messages")]
+    /// async fn manage_messages(_ctx: &CheckContext<'_>, _msg: &Message) -> CheckResult {
+    ///     Ok(())
+    /// }
+    ///
+    /// #[check]
+    /// #[display_in_help(false)]
+    /// async fn not_muted(_ctx: &CheckContext<'_>, _msg: &Message) -> CheckResult {
+    ///     Ok(())
+    /// }
+    ///
+    /// #[command]
+    /// #[check(manage_messages, not_muted)]
+    /// async fn purge(_ctx: FrameworkContext, _msg: &Message) {}
+    ///
+    /// assert_eq!(purge().check_names(), vec!["Manage Messages"]);
+    /// ```
+    ///
+    /// [checks]: Self::checks
+    /// [display]: crate::check::Check::display_in_help
+    /// [`Configuration::check_names`]: crate::configuration::Configuration::check_names
+    pub fn check_names(&self) -> Vec<&str> {
+        self.checks
+            .iter()
+            .filter(|check| check.display_in_help)
+            .map(|check| check.name.as_str())
+            .collect()
+    }
+
+    /// Returns the signature of the command, which is its [primary name][primary]
+    /// followed by its [arguments].
+    ///
+    /// Required arguments are enclosed in `<>` and optional arguments in `[]`.
+    /// Arguments that consume all remaining segments are suffixed with `...`.
+    /// Attachment, sticker and embed arguments are not given in the content of the
+    /// message and are omitted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::model::channel::{Attachment, Message};
+    /// use serenity::model::id::UserId;
+    /// use serenity_framework::prelude::*;
+    ///
+    /// #[command]
+    /// async fn ping(_ctx: FrameworkContext, _msg: &Message) {}
+    ///
+    /// #[command]
+    /// async fn warn(_ctx: FrameworkContext, _msg: &Message, user: UserId, #[rest] reason: String) {}
+    ///
+    /// #[command]
+    /// async fn roll(_ctx: FrameworkContext, _msg: &Message, sides: Option<u32>, dice: Vec<u32>) {}
+    ///
+    /// #[command]
+    /// async fn say(_ctx: FrameworkContext, _msg: &Message, #[rest(required)] text: String) {}
+    ///
+    /// #[command]
+    /// async fn upload(
+    ///     _ctx: FrameworkContext,
+    ///     _msg: &Message,
+    ///     #[attachment] file: Attachment,
+    ///     #[list] tags: Vec<String>,
+    ///     #[list] size: (u32, u32),
+    /// ) {
+    /// }
+    ///
+    /// assert_eq!(ping().signature(), "ping");
+    /// assert_eq!(warn().signature(), "warn <user: UserId> [reason: String...]");
+    /// assert_eq!(roll().signature(), "roll [sides: u32] [dice: u32...]");
+    /// assert_eq!(say().signature(), "say <text: String...>");
+    /// assert_eq!(upload().signature(), "upload <tags: Vec<String>> <size: (u32, u32)>");
+    /// ```
+    ///
+    /// [primary]: Self::primary_name
+    /// [arguments]: Self::arguments
+    pub fn signature(&self) -> String {
+        let mut signature = self.primary_name().to_string();
+
+        for argument in &self.arguments {
+            let (open, close, suffix) = match argument.kind {
+                ArgumentKind::Required => ('<', '>', ""),
+                ArgumentKind::Optional => ('[', ']', ""),
+                ArgumentKind::Variadic | ArgumentKind::Rest => ('[', ']', "..."),
+                ArgumentKind::RequiredRest => ('<', '>', "..."),
+                ArgumentKind::Attachment | ArgumentKind::Sticker | ArgumentKind::Embed => continue,
+            };
+
+            signature.push_str(&format!(
+                " {}{}: {}{}{}",
+                open, argument.name, argument.type_name, suffix, close
+            ));
+        }
+
+        signature
+    }
+
+    /// Returns the [examples] of the command that fail to parse as its arguments.
+    ///
+    /// Each example is given the arguments of the command, without its name, and is
+    /// split with the [`delimiter`] and [`tokenizer`] of the command. The examples are
+    /// parsed in the context and the guild of the message, but only the arguments given
+    /// in the content are parsed. Attachment, sticker and embed arguments are ignored.
+    ///
+    /// If the command has no [argument parser][parser], no example fails.
+    ///
+    /// This is intended to be called in tests, to keep the examples of a command in
+    /// line with its arguments. The `test_util` module provides a mock context and
+    /// message for this purpose.
+    ///
+    /// [examples]: Self::examples
+    /// [`delimiter`]: Self::delimiter
+    /// [`tokenizer`]: Self::tokenizer
+    /// [parser]: Self::argument_parser
+    pub async fn validate_examples(&self, ctx: &SerenityContext, msg: &Message) -> Vec<String> {
+        let parser = match self.argument_parser {
+            Some(parser) => parser,
+            None => return Vec::new(),
+        };
+
+        let mut invalid = Vec::new();
+
+        for example in &self.examples {
+            let segments =
+                ArgumentSegments::with_tokenizer(example, &self.delimiter, self.tokenizer);
+
+            if parser(ctx, msg, segments).await.is_err() {
+                invalid.push(example.clone());
+            }
+        }
+
+        invalid
+    }
+}
+
+/// The kind of an [argument][ArgumentInfo], which determines how it is parsed
+/// from the content of the message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgumentKind {
+    /// The argument consumes exactly one segment, which must be present.
+    Required,
+    /// The argument consumes one segment, if present.
+    Optional,
+    /// The argument consumes all remaining segments, each as an element.
+    Variadic,
+    /// The argument consumes the remainder of the content as a whole.
+    Rest,
+    /// The argument consumes the remainder of the content as a whole, which must
+    /// not be empty.
+    RequiredRest,
+    /// The argument binds attachments of the message, and consumes no segments.
+    Attachment,
+    /// The argument binds stickers of the message, and consumes no segments.
+    Sticker,
+    /// The argument binds embeds of the message, and consumes no segments.
+    Embed,
+}
+
+/// Information about an argument of a command.
+///
+/// This is generated by the `#[command]` macro for every argument of the command
+/// function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgumentInfo {
+    /// The name of the argument.
+    pub name: String,
+    /// The kind of the argument.
+    pub kind: ArgumentKind,
+    /// The name of the type of a value of the argument.
+    ///
+    /// For [optional][ArgumentKind::Optional] and [variadic][ArgumentKind::Variadic]
+    /// arguments, this is the type of the value inside the `Option` or `Vec`.
+    pub type_name: String,
+}
+
+impl ArgumentInfo {
+    /// Creates information about an argument.
+    pub fn new<N, T>(name: N, kind: ArgumentKind, type_name: T) -> Self
+    where
+        N: Into<String>,
+        T: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            kind,
+            type_name: type_name.into(),
+        }
+    }
 }
 
 /// A builder type for creating a [`Command`] from scratch.
@@ -206,7 +1013,63 @@ impl<D, E> CommandBuilder<D, E> {
         self
     }
 
+    /// Assigns many names to this command.
+    ///
+    /// The names are added to the [`names`] list.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_framework::command::Command;
+    /// use serenity_framework::DefaultError;
+    ///
+    /// let command =
+    ///     Command::<(), DefaultError>::builder("ping").names(vec!["pong", "ping", "pong"]).build();
+    ///
+    /// // Duplicate names are removed when the command is built.
+    /// assert_eq!(command.names, vec!["ping".to_string(), "pong".to_string()]);
+    /// ```
+    ///
+    /// [`names`]: Command::names
+    pub fn names<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.inner.names.extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// Assigns an alias to this command.
+    ///
+    /// This is equivalent to [`name`], but clarifies that the name is not the
+    /// [primary name][primary] of the command.
+    ///
+    /// [`name`]: Self::name
+    /// [primary]: Command::primary_name
+    pub fn alias<I>(self, alias: I) -> Self
+    where
+        I: Into<String>,
+    {
+        self.name(alias)
+    }
+
+    /// Assigns many aliases to this command.
+    ///
+    /// This is equivalent to [`names`].
+    ///
+    /// [`names`]: Self::names
+    pub fn aliases<I, S>(self, aliases: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.names(aliases)
+    }
+
     /// Assigns the function to this command.
+    ///
+    /// Refer to [`CommandFn`] for adapting functions that return a [`CommandResult`].
     pub fn function(mut self, f: CommandFn<D, E>) -> Self {
         self.inner.function = f;
         self
@@ -232,6 +1095,20 @@ impl<D, E> CommandBuilder<D, E> {
         self
     }
 
+    /// Assigns a static description to this command in a locale.
+    ///
+    /// The description is added to the [`localized_descriptions`] map.
+    ///
+    /// [`localized_descriptions`]: Command::localized_descriptions
+    pub fn localized_description<L, I>(mut self, locale: L, description: I) -> Self
+    where
+        L: Into<String>,
+        I: Into<String>,
+    {
+        self.inner.localized_descriptions.insert(locale.into(), description.into());
+        self
+    }
+
     /// Assigns a function to dynamically create a description to this command.
     pub fn dynamic_description(mut self, hook: StringHook<D, E>) -> Self {
         self.inner.dynamic_description = Some(hook);
@@ -247,6 +1124,20 @@ impl<D, E> CommandBuilder<D, E> {
         self
     }
 
+    /// Assigns a static usage to this command in a locale.
+    ///
+    /// The usage is added to the [`localized_usage`] map.
+    ///
+    /// [`localized_usage`]: Command::localized_usage
+    pub fn localized_usage<L, I>(mut self, locale: L, usage: I) -> Self
+    where
+        L: Into<String>,
+        I: Into<String>,
+    {
+        self.inner.localized_usage.insert(locale.into(), usage.into());
+        self
+    }
+
     /// Assigns a function to dynamically create a usage to this command.
     pub fn dynamic_usage(mut self, hook: StringHook<D, E>) -> Self {
         self.inner.dynamic_usage = Some(hook);
@@ -272,24 +1163,145 @@ impl<D, E> CommandBuilder<D, E> {
         self
     }
 
+    /// Assigns a key by which the command is ordered among other commands in help commands.
+    ///
+    /// Refer to [`Command::help_order`] for how commands are ordered.
+    pub fn sort_key(mut self, key: i32) -> Self {
+        self.inner.sort_key = Some(key);
+        self
+    }
+
+    /// Assigns the string that separates the arguments of this command.
+    ///
+    /// The default delimiter is a space.
+    pub fn delimiter<I>(mut self, delimiter: I) -> Self
+    where
+        I: Into<String>,
+    {
+        self.inner.delimiter = delimiter.into();
+        self
+    }
+
+    /// Assigns the string that separates the arguments of this command in a locale.
+    ///
+    /// The delimiter is added to the [`delimiter_by_locale`] map.
+    ///
+    /// [`delimiter_by_locale`]: Command::delimiter_by_locale
+    pub fn locale_delimiter<L, I>(mut self, locale: L, delimiter: I) -> Self
+    where
+        L: Into<String>,
+        I: Into<String>,
+    {
+        self.inner.delimiter_by_locale.insert(locale.into(), delimiter.into());
+        self
+    }
+
+    /// Assigns the tokenizer that splits the arguments of this command into segments.
+    ///
+    /// The default tokenizer is the [`DefaultTokenizer`]. Use the [`ShellTokenizer`]
+    /// to allow escaping quotation marks with backslashes.
+    ///
+    /// [`ShellTokenizer`]: crate::utils::ShellTokenizer
+    pub fn tokenizer(mut self, tokenizer: &'static dyn Tokenizer) -> Self {
+        self.inner.tokenizer = tokenizer;
+        self
+    }
+
+    /// Assigns a boolean indicating whether the command rejects arguments in excess
+    /// of the arguments it declares.
+    pub fn deny_extra_args(mut self, b: bool) -> Self {
+        self.inner.deny_extra_args = b;
+        self
+    }
+
+    /// Assigns an argument to this command.
+    ///
+    /// The argument is added to the [`arguments`] list.
+    ///
+    /// [`arguments`]: Command::arguments
+    pub fn argument(mut self, argument: ArgumentInfo) -> Self {
+        self.inner.arguments.push(argument);
+        self
+    }
+
+    /// Assigns a function that parses the arguments of this command without running it.
+    pub fn argument_parser(mut self, f: ArgumentParserFn) -> Self {
+        self.inner.argument_parser = Some(f);
+        self
+    }
+
     /// Assigns a [`check`] function to this command.
     ///
+    /// The check is added to the [`checks`] list.
+    ///
     /// [`check`]: crate::check
+    /// [`checks`]: Command::checks
     pub fn check(mut self, check: CheckConstructor<D, E>) -> Self {
-        self.inner.check = Some(check());
+        self.inner.checks.push(check());
+        self
+    }
+
+    /// Assigns several [`check`] functions to this command.
+    ///
+    /// The checks are added to the [`checks`] list in the order they are given.
+    ///
+    /// [`check`]: crate::check
+    /// [`checks`]: Command::checks
+    pub fn checks(mut self, checks: &[CheckConstructor<D, E>]) -> Self {
+        self.inner.checks.extend(checks.iter().map(|check| check()));
+        self
+    }
+
+    /// Assigns a check that the author has a role to this command.
+    ///
+    /// The check is created with [`check::has_role`] and added to the [`checks`] list.
+    ///
+    /// [`check::has_role`]: crate::check::has_role
+    /// [`checks`]: Command::checks
+    pub fn required_role<R>(mut self, role: R) -> Self
+    where
+        R: Into<RoleSpec>,
+        D: Send + Sync + 'static,
+        E: Send + Sync + 'static,
+    {
+        self.inner.checks.push(check::has_role(role.into()));
+        self
+    }
+
+    /// Assigns the permissions that the bot must have in the channel to run this command.
+    pub fn bot_required_permissions(mut self, permissions: Permissions) -> Self {
+        self.inner.bot_required_permissions = permissions;
+        self
+    }
+
+    /// Assigns a boolean indicating whether the command can only be run in NSFW channels.
+    pub fn nsfw(mut self, b: bool) -> Self {
+        self.inner.nsfw = b;
+        self
+    }
+
+    /// Assigns a boolean indicating whether the command can be run while the bot is in
+    /// maintenance mode.
+    pub fn available_in_maintenance(mut self, b: bool) -> Self {
+        self.inner.available_in_maintenance = b;
         self
     }
 
     /// Complete building a command.
     ///
+    /// Duplicate names are removed, keeping the first occurrence of each name.
+    ///
     /// # Panics
     ///
     /// This function may panic if:
     ///
     /// - The command that is about to be built is missing names.
-    pub fn build(self) -> Command<D, E> {
+    pub fn build(mut self) -> Command<D, E> {
         assert!(!self.inner.names.is_empty(), "a command must have at least one name");
 
+        let mut seen = HashSet::with_capacity(self.inner.names.len());
+        self.inner.names.retain(|name| seen.insert(name.clone()));
+
         self.inner
     }
 }
@@ -1,36 +1,510 @@
 //! Configuration of the framework.
 
-use std::collections::HashSet;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
 
+use once_cell::sync::OnceCell;
 use serenity::futures::future::BoxFuture;
-use serenity::model::channel::Message;
-use serenity::model::id::UserId;
+use serenity::model::channel::{ChannelType, Message, ReactionType};
+use serenity::model::id::{ChannelId, GuildId, UserId};
 
 use crate::category::Category;
-use crate::command::{CommandConstructor, CommandId, CommandMap};
-use crate::context::PrefixContext;
+use crate::check::{Check, CheckConstructor};
+use crate::command::{Command, CommandConstructor, CommandId, CommandMap};
+use crate::context::{Context, NormalMessageContext, PrefixContext};
+use crate::error::{ConfigError, DispatchError, Error};
+use crate::prefix::PrefixMatcher;
 
 /// The definition of the dynamic prefix hook.
 pub type DynamicPrefix<D, E> =
     for<'a> fn(ctx: PrefixContext<'_, D, E>, msg: &'a Message) -> BoxFuture<'a, Option<usize>>;
 
+/// The definition of the lazy prefix function.
+///
+/// Refer to [`Configuration::prefix_fn`].
+pub type PrefixFn = fn() -> Vec<String>;
+
+/// The definition of the dynamic delimiter hook.
+///
+/// Refer to [`Configuration::dynamic_delimiter`].
+pub type DynamicDelimiter = for<'a> fn(msg: &'a Message, args: &'a str) -> Option<String>;
+
+/// The definition of the locale resolver hook.
+///
+/// Refer to [`Configuration::locale_resolver`].
+pub type LocaleResolver = for<'a> fn(msg: &'a Message) -> Option<String>;
+
+/// The definition of the prefix requirement hook.
+///
+/// Refer to [`Configuration::prefix_requirement`].
+pub type PrefixRequirement = fn(kind: ChannelType) -> bool;
+
+/// The definition of the content preprocessor hook.
+///
+/// Refer to [`Configuration::content_preprocessor`].
+pub type ContentPreprocessor = for<'a> fn(msg: &'a Message) -> Cow<'a, str>;
+
+/// The definition of the before hook.
+///
+/// Refer to [`Configuration::before`].
+pub type BeforeHook<D, E> =
+    for<'fut> fn(&'fut Context<D, E>, &'fut Message) -> BoxFuture<'fut, BeforeAction>;
+
+/// The definition of the command enabled hook.
+///
+/// Refer to [`Configuration::command_enabled`].
+pub type CommandEnabledHook = for<'fut> fn(&'fut Message, CommandId) -> BoxFuture<'fut, bool>;
+
+/// The definition of the blocked hook.
+///
+/// Refer to [`Configuration::on_blocked`].
+pub type BlockedHook = for<'fut> fn(&'fut Message, &'fut DispatchError) -> BoxFuture<'fut, ()>;
+
+/// The definition of the normal message hook.
+///
+/// Refer to [`Configuration::normal_message`].
+pub type NormalMessageHook<D> =
+    for<'fut> fn(&'fut NormalMessageContext<'_, D>, &'fut Message) -> BoxFuture<'fut, ()>;
+
+/// The definition of the timing hook.
+///
+/// Refer to [`Configuration::on_timing`].
+pub type TimingHook = fn(CommandId, Duration);
+
+/// The action to take after the [before hook][hook] is called.
+///
+/// [hook]: Configuration::before
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BeforeAction {
+    /// Proceed to invoke the command.
+    Proceed,
+    /// Abort invoking the command.
+    ///
+    /// Dispatching fails with [`DispatchError::Aborted`].
+    ///
+    /// [`DispatchError::Aborted`]: crate::error::DispatchError::Aborted
+    Abort,
+    /// Invoke a different command instead.
+    ///
+    /// The command goes through the same requirements as a command parsed from
+    /// the message, including the [global checks][global], and the before hook is
+    /// called again for the command.
+    ///
+    /// [global]: Configuration::global_checks
+    Redirect {
+        /// The identifier of the command.
+        command: CommandId,
+        /// The arguments of the command.
+        args: String,
+    },
+}
+
 /// The configuration of the framework.
 #[non_exhaustive]
 pub struct Configuration<D, E> {
     /// A list of static prefixes.
     pub prefixes: Vec<String>,
+    /// A function returning a list of static prefixes, evaluated on first use.
+    ///
+    /// This allows for prefixes that are only known after the configuration is created,
+    /// such as prefixes loaded from a file or the environment. The function is called
+    /// once, when the first message is parsed, and its prefixes are cached for the
+    /// lifetime of the configuration. They are tried after the [`prefixes`] list.
+    ///
+    /// # Notes
+    ///
+    /// The function may be called from any thread. If several messages are parsed
+    /// concurrently before the prefixes are cached, the function is still only called
+    /// once, while the other threads wait for it to return.
+    ///
+    /// [`prefixes`]: Self::prefixes
+    pub prefix_fn: Option<PrefixFn>,
+    /// The cached prefixes of the [`prefix_fn`].
+    ///
+    /// [`prefix_fn`]: Self::prefix_fn
+    lazy_prefixes: OnceCell<Vec<String>>,
     /// A function to dynamically parse the prefix.
     pub dynamic_prefix: Option<DynamicPrefix<D, E>>,
+    /// A list of [`PrefixMatcher`]s that are tried in order after the built-in prefixes.
+    ///
+    /// Refer to [`parse::content`] for the order of the built-in prefixes.
+    ///
+    /// [`PrefixMatcher`]: crate::prefix::PrefixMatcher
+    /// [`parse::content`]: crate::parse::content
+    pub prefix_matchers: Vec<Arc<dyn PrefixMatcher<D, E>>>,
+    /// A function to transform the content of the message before it is parsed.
+    ///
+    /// This can be used to strip markdown, unwrap quotes, or otherwise normalize the
+    /// content. The prefix, command names and arguments are parsed out of the
+    /// transformed content. As the transformed content may be an owned string, it is kept
+    /// alive for the duration of parsing, and the [`Context`] only receives owned copies of
+    /// its parts.
+    ///
+    /// The index returned by the [`dynamic_prefix`] hook is applied to the transformed
+    /// content, not the original content of the message.
+    ///
+    /// [`Context`]: crate::context::Context
+    /// [`dynamic_prefix`]: Self::dynamic_prefix
+    pub content_preprocessor: Option<ContentPreprocessor>,
+    /// A character that escapes static prefixes.
+    ///
+    /// A message starting with this character, followed by one of the [`prefixes`],
+    /// is not treated as a command invocation. For example, with the `\` escape
+    /// and the `!` prefix, `\!ping` does not invoke the `ping` command.
+    /// If the escape is the prefix character itself, a doubled prefix (`!!ping`)
+    /// escapes the prefix.
+    ///
+    /// The escape takes precedence over static prefixes. If `!` is the escape and
+    /// both `!` and `!!` are registered prefixes, a message starting with `!!`
+    /// is always escaped.
+    ///
+    /// [`prefixes`]: Self::prefixes
+    pub prefix_escape: Option<char>,
+    /// A boolean indicating whether leading whitespace and invisible characters are
+    /// ignored before the prefix.
+    ///
+    /// If enabled, ` !ping` and `\t!ping` invoke the `ping` command with the `!` prefix.
+    /// Refer to [`parse::trim_leading`] for the characters that are ignored.
+    ///
+    /// [`parse::trim_leading`]: crate::parse::trim_leading
+    pub trim_leading: bool,
+    /// A function to choose the delimiter of the arguments of a command invocation.
+    ///
+    /// The function receives the message and the arguments of the command. If it
+    /// returns a delimiter, the delimiter is used instead of the [delimiter of the
+    /// command][delimiter], and is available as [`Context::delimiter`].
+    ///
+    /// [delimiter]: crate::command::Command::delimiter
+    /// [`Context::delimiter`]: crate::context::Context::delimiter
+    pub dynamic_delimiter: Option<DynamicDelimiter>,
+    /// A function to resolve the locale of the author of a message.
+    ///
+    /// If the resolved locale has a delimiter in the [`delimiter_by_locale`] map of
+    /// the invoked command, that delimiter is used for the arguments of the command.
+    /// The [`dynamic_delimiter`] hook takes precedence over the locale.
+    ///
+    /// [`delimiter_by_locale`]: crate::command::Command::delimiter_by_locale
+    /// [`dynamic_delimiter`]: Self::dynamic_delimiter
+    pub locale_resolver: Option<LocaleResolver>,
+    /// A function called before a command is invoked.
+    ///
+    /// The hook decides whether the command is invoked, aborted, or whether
+    /// a [different command is invoked instead][redirect]. This allows for
+    /// user-defined aliases and macro commands.
+    ///
+    /// Redirects may be chained up to [`MAX_REDIRECTS`] times, after which dispatching
+    /// fails with [`DispatchError::TooManyRedirects`].
+    ///
+    /// [redirect]: BeforeAction::Redirect
+    /// [`MAX_REDIRECTS`]: crate::MAX_REDIRECTS
+    /// [`DispatchError::TooManyRedirects`]: crate::error::DispatchError::TooManyRedirects
+    pub before: Option<BeforeHook<D, E>>,
+    /// A function deciding whether a command is enabled for a message.
+    ///
+    /// The function receives the message and the identifier of a command that was
+    /// parsed from it. If it returns `false`, dispatching fails with
+    /// [`DispatchError::DisabledInGuild`]. This allows toggling commands per guild,
+    /// such as by looking up the [guild of the message][guild] in a settings store,
+    /// without adding a check to every command.
+    ///
+    /// The function is called after the [maintenance mode][maintenance] is enforced,
+    /// and before the permissions of the bot are verified and the checks are run.
+    ///
+    /// [`DispatchError::DisabledInGuild`]: crate::error::DispatchError::DisabledInGuild
+    /// [guild]: serenity::model::channel::Message::guild_id
+    /// [maintenance]: Self::maintenance
+    pub command_enabled: Option<CommandEnabledHook>,
+    /// A function that is called when a [dispatched][dispatch] message does not
+    /// contain a command invocation.
+    ///
+    /// This allows handling every message through the framework, such as to
+    /// respond to keywords.
+    ///
+    /// If set, [`dispatch`] calls the hook and returns `Ok(())` for such messages,
+    /// instead of failing with [`DispatchError::NormalMessage`]. Messages that
+    /// only contain a prefix are not passed to the hook.
+    ///
+    /// [dispatch]: crate::Framework::dispatch
+    /// [`dispatch`]: crate::Framework::dispatch
+    /// [`DispatchError::NormalMessage`]: crate::error::DispatchError::NormalMessage
+    pub normal_message: Option<NormalMessageHook<D>>,
+    /// A command that is invoked when the first name in a command invocation does not
+    /// belong to any command.
+    ///
+    /// This allows handling unknown commands, such as by suggesting the help command,
+    /// or by looking up user-defined commands in a database.
+    ///
+    /// The [`Context::args`] of the command are the whole content of the message after
+    /// the prefix, and the [`Context::command_name`] is the unknown name. The command is
+    /// verified as any other command, including maintenance mode, permissions and checks.
+    ///
+    /// The command is not added to the [`commands`] map, and is thus not invocable by
+    /// its names.
+    ///
+    /// [`Context::args`]: crate::context::Context::args
+    /// [`Context::command_name`]: crate::context::Context::command_name
+    /// [`commands`]: Self::commands
+    pub unknown_command: Option<Command<D, E>>,
+    /// A function that is called with the execution time of every invoked command.
+    ///
+    /// The time is measured around the command function only. It excludes parsing,
+    /// checks, the [before hook][before], and waiting for other commands of the user if
+    /// [`serialize_per_user`] is enabled. The function is called whether or not the
+    /// command succeeded.
+    ///
+    /// This can be used to log slow commands, or to record metrics.
+    ///
+    /// [before]: Self::before
+    /// [`serialize_per_user`]: Self::serialize_per_user
+    pub on_timing: Option<TimingHook>,
+    /// A function called when a command invocation is rejected, because its author,
+    /// channel or guild is blocked.
+    ///
+    /// The function receives the message and the [`DispatchError::Blocked`] error,
+    /// before the error is returned. This allows logging blocked attempts.
+    ///
+    /// Refer to [`blocked_users`] for how invocations are blocked.
+    ///
+    /// [`DispatchError::Blocked`]: crate::error::DispatchError::Blocked
+    /// [`blocked_users`]: Self::blocked_users
+    pub on_blocked: Option<BlockedHook>,
     /// A boolean indicating whether casing of the letters in static prefixes,
     /// or command names does not matter.
     pub case_insensitive: bool,
     /// A boolean indicating whether the prefix is not necessary in direct messages.
     pub no_dm_prefix: bool,
+    /// A function that decides whether the prefix is necessary in a type of channel.
+    ///
+    /// The function receives the [type of the channel][type] of the message, and returns
+    /// whether a prefix is required. If no prefix is found in a message in a channel where
+    /// it is not required, the whole message is the content. This allows, for example,
+    /// invoking commands without a prefix in threads, but not in text channels.
+    ///
+    /// If the type of the channel is unknown, such as when the channel is not cached,
+    /// the prefix is required. Refer to [`prefix_required`].
+    ///
+    /// Unlike [`no_dm_prefix`], prefixes are still parsed in channels where they are
+    /// not required.
+    ///
+    /// [type]: crate::parse::channel_type
+    /// [`prefix_required`]: Self::prefix_required
+    /// [`no_dm_prefix`]: Self::no_dm_prefix
+    pub prefix_requirement: Option<PrefixRequirement>,
+    /// A boolean indicating whether [NSFW commands][nsfw] can be run in direct messages.
+    ///
+    /// This is `true` by default.
+    ///
+    /// [nsfw]: crate::command::Command::nsfw
+    pub nsfw_in_dms: bool,
+    /// A boolean indicating whether commands are rejected in channels where the bot
+    /// cannot send messages.
+    ///
+    /// If enabled, commands invoked in a channel where the bot lacks the `SEND_MESSAGES`
+    /// permission fail with [`DispatchError::CannotRespond`] before they are run.
+    ///
+    /// # Notes
+    ///
+    /// This is best-effort. The permissions of the bot are computed from the cache, or
+    /// requested from Discord if the guild of the channel is not cached. Commands are not
+    /// rejected if the request fails, and never in direct messages. Refer to
+    /// [`parse::bot_permissions`].
+    ///
+    /// [`DispatchError::CannotRespond`]: crate::error::DispatchError::CannotRespond
+    /// [`parse::bot_permissions`]: crate::parse::bot_permissions
+    pub require_send_permission: bool,
+    /// A boolean indicating whether commands of the same user are run one at a time.
+    ///
+    /// If enabled, a command invoked by a user waits until any other command of
+    /// the same user completes. This prevents races between commands that mutate
+    /// state belonging to the user.
+    ///
+    /// # Notes
+    ///
+    /// A command that [invokes][invoke] another command on behalf of the same user
+    /// waits for itself to complete, and thus deadlocks.
+    ///
+    /// [invoke]: crate::Framework::invoke
+    pub serialize_per_user: bool,
+    /// A boolean indicating whether the typing indicator is shown while a command runs.
+    ///
+    /// If enabled, typing is started in the channel of the message before the command
+    /// is invoked, and stopped after it completes. The command is invoked even if
+    /// typing could not be started.
+    ///
+    /// # Notes
+    ///
+    /// This adds an HTTP request to every command invocation.
+    pub auto_typing: bool,
+    /// A boolean indicating whether the message that invoked a command is deleted
+    /// after the command succeeds.
+    ///
+    /// Only messages [dispatched][dispatch] by the framework are deleted. The result of
+    /// the command is returned even if the message could not be deleted, such as when
+    /// the bot lacks permission to do so.
+    ///
+    /// # Notes
+    ///
+    /// Deleting messages of other users requires the bot to have the `MANAGE_MESSAGES`
+    /// permission in the channel. This adds an HTTP request to every successful command
+    /// invocation.
+    ///
+    /// [dispatch]: crate::Framework::dispatch
+    pub delete_command_messages: bool,
+    /// A boolean indicating whether the reason of a failed check is sent to the channel
+    /// of the message.
+    ///
+    /// If enabled, and [dispatching][dispatch] a message fails with
+    /// [`DispatchError::CheckFailed`] whose reason contains information for the user,
+    /// such as [`Reason::User`] or [`Reason::UserAndLog`], the information is sent to
+    /// the channel before the error is returned. The error is returned even if the
+    /// information could not be sent.
+    ///
+    /// [dispatch]: crate::Framework::dispatch
+    /// [`DispatchError::CheckFailed`]: crate::error::DispatchError::CheckFailed
+    /// [`Reason::User`]: crate::check::Reason::User
+    /// [`Reason::UserAndLog`]: crate::check::Reason::UserAndLog
+    pub reply_check_failures: bool,
+    /// A boolean indicating whether the message that invoked a command is reacted to
+    /// with the result of the command.
+    ///
+    /// If enabled, the [success reaction][success] is added to the message after the
+    /// command [dispatched][dispatch] by the framework succeeds, and the
+    /// [failure reaction][failure] after it fails. The result of the command is returned
+    /// even if the reaction could not be added, such as when the bot lacks permission
+    /// to do so.
+    ///
+    /// # Notes
+    ///
+    /// Adding reactions requires the bot to have the `ADD_REACTIONS` and
+    /// `READ_MESSAGE_HISTORY` permissions in the channel. This adds an HTTP request to
+    /// every command invocation.
+    ///
+    /// [success]: Self::success_reaction
+    /// [failure]: Self::failure_reaction
+    /// [dispatch]: crate::Framework::dispatch
+    pub react_on_result: bool,
+    /// The reaction added to the message of a successful command if [`react_on_result`]
+    /// is enabled.
+    ///
+    /// This is `✅` by default.
+    ///
+    /// [`react_on_result`]: Self::react_on_result
+    pub success_reaction: ReactionType,
+    /// The reaction added to the message of a failed command if [`react_on_result`]
+    /// is enabled.
+    ///
+    /// This is `❌` by default.
+    ///
+    /// [`react_on_result`]: Self::react_on_result
+    pub failure_reaction: ReactionType,
+    /// A duration in which a command invocation that is identical to a previous
+    /// invocation of the same user is rejected.
+    ///
+    /// If set, [dispatching][dispatch] a message with the same content as a message
+    /// of the same user that invoked a command within the duration fails with
+    /// [`DispatchError::DuplicateInvocation`].
+    ///
+    /// [dispatch]: crate::Framework::dispatch
+    /// [`DispatchError::DuplicateInvocation`]: crate::error::DispatchError::DuplicateInvocation
+    pub dedupe_window: Option<Duration>,
+    /// A boolean indicating whether several commands may share a name.
+    ///
+    /// If enabled, a name that belongs to several commands resolves to each of them
+    /// in the order they were registered. [Dispatching][dispatch] tries the checks of
+    /// every candidate and invokes the first whose checks pass. This allows for
+    /// context-dependent commands, such as a guild-only and a DM-only command sharing
+    /// an alias.
+    ///
+    /// If disabled, the name belongs to the command registered last.
+    ///
+    /// [dispatch]: crate::Framework::dispatch
+    pub allow_name_collisions: bool,
+    /// A string that separates subcommands from their parent command.
+    ///
+    /// If set, subcommands are no longer separated by spaces. Once the root command
+    /// is parsed, each subcommand must directly follow this separator, such as
+    /// `config/set` with a separator of `/`. The arguments start after the first
+    /// space following the last subcommand. If the name after a separator is not a
+    /// subcommand, the arguments start after the separator instead.
+    ///
+    /// Prefixes of categories are still separated from the root command by a space.
+    ///
+    /// If unset, or set to an empty string, subcommands are separated by spaces.
+    pub subcommand_separator: Option<String>,
+    /// A boolean indicating whether arguments that start with a quotation mark must
+    /// terminate it.
+    ///
+    /// By default, an argument that starts with a quotation mark that is never terminated
+    /// spans the rest of the arguments. If enabled, commands with arguments fail with
+    /// [`DispatchError::UnterminatedQuote`] instead. This applies to commands created
+    /// with the `#[command]` macro. Whether a quotation mark is terminated is decided by
+    /// the [tokenizer of the command][tokenizer].
+    ///
+    /// [`DispatchError::UnterminatedQuote`]: crate::error::DispatchError::UnterminatedQuote
+    /// [tokenizer]: crate::utils::Tokenizer::try_token_split
+    pub strict_quotes: bool,
     /// A user id of the bot that is used to compare mentions in prefix position.
     ///
     /// If filled, this allows for invoking commands by mentioning the bot.
     pub on_mention: Option<String>,
+    /// A boolean indicating whether a textual mention of the name of the bot is a prefix.
+    ///
+    /// If enabled, a message starting with `@` followed by the nickname of the bot in
+    /// the guild, or by its username, is treated as if it started with a mention.
+    ///
+    /// # Notes
+    ///
+    /// The names of the bot are retrieved from the cache. Refer to
+    /// [`parse::bot_name_mention`] for more information.
+    ///
+    /// [`parse::bot_name_mention`]: crate::parse::bot_name_mention
+    pub mention_by_name: bool,
+    /// A set of users who own the bot.
+    ///
+    /// Refer to [`Context::author_is_owner`].
+    ///
+    /// [`Context::author_is_owner`]: crate::context::Context::author_is_owner
+    pub owners: HashSet<UserId>,
+    /// A boolean indicating whether the bot is in maintenance mode.
+    ///
+    /// While in maintenance mode, commands fail with [`DispatchError::Maintenance`],
+    /// unless they are [available in maintenance mode][available] or the author of
+    /// the message is one of the [`owners`]. As the configuration can be modified
+    /// at runtime, maintenance mode can be toggled while the bot is running.
+    ///
+    /// [`DispatchError::Maintenance`]: crate::error::DispatchError::Maintenance
+    /// [available]: crate::command::Command::available_in_maintenance
+    /// [`owners`]: Self::owners
+    pub maintenance: bool,
+    /// A set of users who may not invoke commands.
+    ///
+    /// Messages that invoke a command from blocked users, in blocked channels, or in
+    /// blocked guilds fail with [`DispatchError::Blocked`], without the command being
+    /// parsed. Messages without a prefix are not affected.
+    ///
+    /// [`DispatchError::Blocked`]: crate::error::DispatchError::Blocked
+    pub blocked_users: HashSet<UserId>,
+    /// A set of channels in which commands may not be invoked.
+    ///
+    /// Refer to [`blocked_users`] for how invocations are blocked.
+    ///
+    /// [`blocked_users`]: Self::blocked_users
+    pub blocked_channels: HashSet<ChannelId>,
+    /// A set of guilds in which commands may not be invoked.
+    ///
+    /// Refer to [`blocked_users`] for how invocations are blocked.
+    ///
+    /// [`blocked_users`]: Self::blocked_users
+    pub blocked_guilds: HashSet<GuildId>,
+    /// A list of [`Check`]s that apply to every command.
+    ///
+    /// Global checks are run before the check of the first command in a message.
+    ///
+    /// [`Check`]: crate::check::Check
+    pub global_checks: Vec<Check<D, E>>,
     /// A list of [`Category`]s.
     ///
     /// [`Category`]: crate::category::Category
@@ -42,19 +516,61 @@ pub struct Configuration<D, E> {
     /// [`IdMap`]: crate::utils::IdMap
     /// [`Command`]: crate::command::Command
     pub commands: CommandMap<D, E>,
+    /// A map of names to every command registered under them, in registration order.
+    ///
+    /// Names are only resolved from this map if [`allow_name_collisions`] is enabled.
+    ///
+    /// [`allow_name_collisions`]: Self::allow_name_collisions
+    pub command_names: HashMap<String, Vec<CommandId>>,
 }
 
 impl<D, E> Clone for Configuration<D, E> {
     fn clone(&self) -> Self {
         Self {
             prefixes: self.prefixes.clone(),
+            prefix_fn: self.prefix_fn,
+            lazy_prefixes: self.lazy_prefixes.clone(),
             dynamic_prefix: self.dynamic_prefix,
+            prefix_matchers: self.prefix_matchers.clone(),
+            content_preprocessor: self.content_preprocessor,
+            prefix_escape: self.prefix_escape,
+            trim_leading: self.trim_leading,
+            dynamic_delimiter: self.dynamic_delimiter,
+            locale_resolver: self.locale_resolver,
+            before: self.before,
+            command_enabled: self.command_enabled,
+            normal_message: self.normal_message,
+            unknown_command: self.unknown_command.clone(),
+            on_timing: self.on_timing,
+            on_blocked: self.on_blocked,
             case_insensitive: self.case_insensitive,
             no_dm_prefix: self.no_dm_prefix,
+            prefix_requirement: self.prefix_requirement,
+            nsfw_in_dms: self.nsfw_in_dms,
+            require_send_permission: self.require_send_permission,
+            serialize_per_user: self.serialize_per_user,
+            auto_typing: self.auto_typing,
+            delete_command_messages: self.delete_command_messages,
+            reply_check_failures: self.reply_check_failures,
+            react_on_result: self.react_on_result,
+            success_reaction: self.success_reaction.clone(),
+            failure_reaction: self.failure_reaction.clone(),
+            dedupe_window: self.dedupe_window,
+            allow_name_collisions: self.allow_name_collisions,
+            subcommand_separator: self.subcommand_separator.clone(),
+            strict_quotes: self.strict_quotes,
             on_mention: self.on_mention.clone(),
+            mention_by_name: self.mention_by_name,
+            owners: self.owners.clone(),
+            maintenance: self.maintenance,
+            blocked_users: self.blocked_users.clone(),
+            blocked_channels: self.blocked_channels.clone(),
+            blocked_guilds: self.blocked_guilds.clone(),
+            global_checks: self.global_checks.clone(),
             categories: self.categories.clone(),
             root_level_commands: self.root_level_commands.clone(),
             commands: self.commands.clone(),
+            command_names: self.command_names.clone(),
         }
     }
 }
@@ -63,13 +579,49 @@ impl<D, E> Default for Configuration<D, E> {
     fn default() -> Self {
         Self {
             prefixes: Vec::default(),
+            prefix_fn: None,
+            lazy_prefixes: OnceCell::new(),
             dynamic_prefix: None,
+            prefix_matchers: Vec::default(),
+            content_preprocessor: None,
+            prefix_escape: None,
+            trim_leading: false,
+            dynamic_delimiter: None,
+            locale_resolver: None,
+            before: None,
+            command_enabled: None,
+            normal_message: None,
+            unknown_command: None,
+            on_timing: None,
+            on_blocked: None,
             case_insensitive: false,
             no_dm_prefix: false,
+            prefix_requirement: None,
+            nsfw_in_dms: true,
+            require_send_permission: false,
+            serialize_per_user: false,
+            auto_typing: false,
+            delete_command_messages: false,
+            reply_check_failures: false,
+            react_on_result: false,
+            success_reaction: ReactionType::from('✅'),
+            failure_reaction: ReactionType::from('❌'),
+            dedupe_window: None,
+            allow_name_collisions: false,
+            subcommand_separator: None,
+            strict_quotes: false,
             on_mention: None,
+            mention_by_name: false,
+            owners: HashSet::default(),
+            maintenance: false,
+            blocked_users: HashSet::default(),
+            blocked_channels: HashSet::default(),
+            blocked_guilds: HashSet::default(),
+            global_checks: Vec::default(),
             categories: Vec::default(),
             root_level_commands: HashSet::default(),
             commands: CommandMap::default(),
+            command_names: HashMap::default(),
         }
     }
 }
@@ -80,6 +632,29 @@ impl<D, E> Configuration<D, E> {
         Self::default()
     }
 
+    /// Constructs a builder that will be used to create a configuration in one
+    /// expression.
+    ///
+    /// Refer to [`ConfigurationBuilder`].
+    pub fn builder() -> ConfigurationBuilder<D, E> {
+        ConfigurationBuilder::new()
+    }
+
+    /// Creates a new instance of the framework configuration with a prefix.
+    ///
+    /// This is equivalent to calling [`prefix`] on a [new][`new`] configuration.
+    ///
+    /// [`prefix`]: Self::prefix
+    /// [`new`]: Self::new
+    pub fn with_prefix<I>(prefix: I) -> Self
+    where
+        I: Into<String>,
+    {
+        let mut conf = Self::new();
+        conf.prefix(prefix);
+        conf
+    }
+
     /// Assigns a prefix to this configuration.
     ///
     /// The prefix is added to the [`prefixes`] list.
@@ -93,118 +668,1852 @@ impl<D, E> Configuration<D, E> {
         self
     }
 
+    /// Assigns a function returning a list of static prefixes, evaluated on first use.
+    ///
+    /// Any prefixes cached from a previous function are discarded.
+    ///
+    /// Refer to [`prefix_fn`] for more information.
+    ///
+    /// [`prefix_fn`]: Self::prefix_fn
+    pub fn prefix_fn(&mut self, f: PrefixFn) -> &mut Self {
+        self.prefix_fn = Some(f);
+        self.lazy_prefixes = OnceCell::new();
+        self
+    }
+
+    /// Returns the prefixes of the [`prefix_fn`], calling it if they are not cached yet.
+    ///
+    /// If no function is assigned, an empty list is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_framework::configuration::Configuration;
+    /// use serenity_framework::DefaultError;
+    ///
+    /// fn prefixes() -> Vec<String> {
+    ///     std::env::var("BOT_PREFIX").map_or_else(|_| vec!["!".to_string()], |p| vec![p])
+    /// }
+    ///
+    /// let mut conf = Configuration::<(), DefaultError>::new();
+    /// assert!(conf.lazy_prefixes().is_empty());
+    ///
+    /// conf.prefix_fn(prefixes);
+    /// std::env::set_var("BOT_PREFIX", "?");
+    ///
+    /// assert_eq!(conf.lazy_prefixes(), &["?".to_string()]);
+    ///
+    /// // The prefixes are cached.
+    /// std::env::set_var("BOT_PREFIX", "$");
+    /// assert_eq!(conf.lazy_prefixes(), &["?".to_string()]);
+    /// ```
+    ///
+    /// [`prefix_fn`]: Self::prefix_fn
+    pub fn lazy_prefixes(&self) -> &[String] {
+        match self.prefix_fn {
+            Some(f) => self.lazy_prefixes.get_or_init(f),
+            None => &[],
+        }
+    }
+
     /// Assigns a function to dynamically parse the prefix.
     pub fn dynamic_prefix(&mut self, prefix: DynamicPrefix<D, E>) -> &mut Self {
         self.dynamic_prefix = Some(prefix);
         self
     }
 
-    /// Assigns a boolean indicating whether the casing of letters in static prefixes,
-    /// or command names does not matter.
-    pub fn case_insensitive(&mut self, b: bool) -> &mut Self {
-        self.case_insensitive = b;
+    /// Assigns a custom strategy to parse the prefix.
+    ///
+    /// The matcher is added to the [`prefix_matchers`] list.
+    ///
+    /// [`prefix_matchers`]: Self::prefix_matchers
+    pub fn prefix_matcher<M>(&mut self, matcher: M) -> &mut Self
+    where
+        M: PrefixMatcher<D, E> + 'static,
+    {
+        self.prefix_matchers.push(Arc::new(matcher));
+        self
+    }
 
+    /// Assigns a function to transform the content of the message before it is parsed.
+    ///
+    /// Refer to [`content_preprocessor`] for more information.
+    ///
+    /// [`content_preprocessor`]: Self::content_preprocessor
+    pub fn content_preprocessor(&mut self, f: ContentPreprocessor) -> &mut Self {
+        self.content_preprocessor = Some(f);
         self
     }
 
-    /// Assigns a boolean indicating whether the prefix is not necessary in
-    /// direct messages.
-    pub fn no_dm_prefix(&mut self, b: bool) -> &mut Self {
-        self.no_dm_prefix = b;
+    /// Assigns a character that escapes static prefixes.
+    ///
+    /// Refer to [`prefix_escape`] for more information.
+    ///
+    /// [`prefix_escape`]: Self::prefix_escape
+    pub fn prefix_escape(&mut self, escape: char) -> &mut Self {
+        self.prefix_escape = Some(escape);
         self
     }
 
-    /// Assigns a user id of the bot that will allow for mentions in prefix position.
-    pub fn on_mention<I>(&mut self, id: I) -> &mut Self
-    where
-        I: Into<UserId>,
-    {
-        self.on_mention = Some(id.into().to_string());
+    /// Assigns a boolean indicating whether leading whitespace and invisible characters
+    /// are ignored before the prefix.
+    ///
+    /// Refer to [`trim_leading`] for more information.
+    ///
+    /// [`trim_leading`]: Self::trim_leading
+    pub fn trim_leading(&mut self, b: bool) -> &mut Self {
+        self.trim_leading = b;
         self
     }
 
-    /// Assigns a category to this configuration.
+    /// Assigns a function to choose the delimiter of the arguments of a command invocation.
     ///
-    /// The category is added to the [`categories`] list. Additionally,
-    /// all of its commands [are added][cmd] to the [`commands`] map
+    /// Refer to [`dynamic_delimiter`] for more information.
     ///
-    /// [`categories`]: Self::categories
-    /// [`commands`]: Self::commands
-    /// [cmd]: Self::command
-    pub fn category<I>(&mut self, name: I, cmds: &[CommandConstructor<D, E>]) -> &mut Self
-    where
-        I: Into<String>,
-    {
-        let mut commands = Vec::with_capacity(cmds.len());
+    /// [`dynamic_delimiter`]: Self::dynamic_delimiter
+    pub fn dynamic_delimiter(&mut self, f: DynamicDelimiter) -> &mut Self {
+        self.dynamic_delimiter = Some(f);
+        self
+    }
 
-        for cmd in cmds {
-            self.command(*cmd);
-            commands.push(CommandId::from(*cmd));
-        }
+    /// Assigns a function to resolve the locale of the author of a message.
+    ///
+    /// Refer to [`locale_resolver`] for more information.
+    ///
+    /// [`locale_resolver`]: Self::locale_resolver
+    pub fn locale_resolver(&mut self, f: LocaleResolver) -> &mut Self {
+        self.locale_resolver = Some(f);
+        self
+    }
 
-        self.categories.push(Category {
-            name: name.into(),
-            commands,
-        });
+    /// Returns the delimiter of the arguments of an invocation of the command.
+    ///
+    /// This is the delimiter chosen by the [`dynamic_delimiter`] hook, if one is
+    /// registered and chooses one. Otherwise, it is the [delimiter of the command
+    /// in the locale][locale] resolved by the [`locale_resolver`] hook, if there is
+    /// one, or the [delimiter of the command][delimiter].
+    ///
+    /// [`dynamic_delimiter`]: Self::dynamic_delimiter
+    /// [locale]: crate::command::Command::delimiter_by_locale
+    /// [`locale_resolver`]: Self::locale_resolver
+    /// [delimiter]: crate::command::Command::delimiter
+    pub(crate) fn delimiter(&self, command: &Command<D, E>, msg: &Message, args: &str) -> String {
+        self.dynamic_delimiter
+            .and_then(|f| f(msg, args))
+            .or_else(|| {
+                let locale = self.locale_resolver.and_then(|f| f(msg))?;
 
-        self
+                command.delimiter_by_locale.get(&locale).cloned()
+            })
+            .unwrap_or_else(|| command.delimiter.clone())
     }
 
-    /// Assigns a command to this configuration.
+    /// Assigns a function to be called before a command is invoked.
     ///
-    /// The command is added to the [`commands`] map, alongside its subcommands.
-    /// It it also added into the [`root_level_commands`] set.
+    /// Refer to [`before`] for more information.
     ///
-    /// [`commands`]: Self::commands
-    /// [`root_level_commands`]: Self::root_level_commands
-    pub fn command(&mut self, command: CommandConstructor<D, E>) -> &mut Self {
-        let id = CommandId::from(command);
+    /// [`before`]: Self::before
+    pub fn before(&mut self, f: BeforeHook<D, E>) -> &mut Self {
+        self.before = Some(f);
+        self
+    }
 
-        // Skip instantiating this root command if if already exists.
-        if self.root_level_commands.contains(&id) {
-            return self;
-        }
+    /// Assigns a function deciding whether a command is enabled for a message.
+    ///
+    /// Refer to [`command_enabled`] for more information.
+    ///
+    /// [`command_enabled`]: Self::command_enabled
+    pub fn command_enabled(&mut self, f: CommandEnabledHook) -> &mut Self {
+        self.command_enabled = Some(f);
+        self
+    }
 
-        self.root_level_commands.insert(id);
-        self._command(id, command);
+    /// Assigns the function that is called for messages without a command invocation.
+    ///
+    /// Refer to [`normal_message`] for more information.
+    ///
+    /// [`normal_message`]: Self::normal_message
+    pub fn normal_message(&mut self, f: NormalMessageHook<D>) -> &mut Self {
+        self.normal_message = Some(f);
         self
     }
 
-    fn _command(&mut self, id: CommandId, command: CommandConstructor<D, E>) {
-        let mut command = command();
-        command.id = id;
+    /// Assigns the command that is invoked for unknown command names.
+    ///
+    /// Refer to [`unknown_command`] for more information.
+    ///
+    /// [`unknown_command`]: Self::unknown_command
+    pub fn unknown_command(&mut self, command: CommandConstructor<D, E>) -> &mut Self {
+        let mut cmd = command();
+        cmd.id = CommandId::from(command);
 
-        for name in &command.names {
-            let name = if self.case_insensitive { name.to_lowercase() } else { name.clone() };
+        self.unknown_command = Some(cmd);
+        self
+    }
 
-            self.commands.insert_name(name, command.id);
-        }
+    /// Assigns a function to be called with the execution time of every command.
+    ///
+    /// Refer to [`on_timing`] for more information.
+    ///
+    /// [`on_timing`]: Self::on_timing
+    pub fn on_timing(&mut self, f: TimingHook) -> &mut Self {
+        self.on_timing = Some(f);
+        self
+    }
 
-        for id in &command.subcommands {
-            // Skip instantiating this subcommand if it already exists.
-            if self.commands.contains_id(*id) {
-                continue;
-            }
+    /// Assigns a function to be called when a command invocation is blocked.
+    ///
+    /// Refer to [`on_blocked`] for more information.
+    ///
+    /// [`on_blocked`]: Self::on_blocked
+    pub fn on_blocked(&mut self, f: BlockedHook) -> &mut Self {
+        self.on_blocked = Some(f);
+        self
+    }
 
-            let ctor: CommandConstructor<D, E> = id.into_constructor();
-            self._command(*id, ctor);
-        }
+    /// Assigns a boolean indicating whether the casing of letters in static prefixes,
+    /// or command names does not matter.
+    pub fn case_insensitive(&mut self, b: bool) -> &mut Self {
+        self.case_insensitive = b;
 
-        self.commands.insert(command.id, command);
+        self
     }
-}
 
-impl<D, E> fmt::Debug for Configuration<D, E> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Configuration")
-            .field("prefixes", &self.prefixes)
-            .field("dynamic_prefix", &"<fn>")
+    /// Assigns a boolean indicating whether the prefix is not necessary in
+    /// direct messages.
+    pub fn no_dm_prefix(&mut self, b: bool) -> &mut Self {
+        self.no_dm_prefix = b;
+        self
+    }
+
+    /// Assigns a function that decides whether the prefix is necessary in a type of channel.
+    ///
+    /// Refer to [`prefix_requirement`] for more information.
+    ///
+    /// [`prefix_requirement`]: Self::prefix_requirement
+    pub fn prefix_requirement(&mut self, f: PrefixRequirement) -> &mut Self {
+        self.prefix_requirement = Some(f);
+        self
+    }
+
+    /// Returns a boolean indicating whether the prefix is necessary in a channel of the type.
+    ///
+    /// The prefix is necessary if the [`prefix_requirement`] hook is not set, if the hook
+    /// requires it, or if the type of the channel is unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::model::channel::ChannelType;
+    /// use serenity_framework::configuration::Configuration;
+    /// use serenity_framework::DefaultError;
+    ///
+    /// fn outside_threads(kind: ChannelType) -> bool {
+    ///     !matches!(
+    ///         kind,
+    ///         ChannelType::PublicThread | ChannelType::PrivateThread | ChannelType::NewsThread
+    ///     )
+    /// }
+    ///
+    /// let mut conf = Configuration::<(), DefaultError>::new();
+    ///
+    /// assert!(conf.prefix_required(Some(ChannelType::PublicThread)));
+    ///
+    /// conf.prefix_requirement(outside_threads);
+    ///
+    /// assert!(!conf.prefix_required(Some(ChannelType::PublicThread)));
+    /// assert!(!conf.prefix_required(Some(ChannelType::PrivateThread)));
+    /// assert!(conf.prefix_required(Some(ChannelType::Text)));
+    /// assert!(conf.prefix_required(None));
+    /// ```
+    ///
+    /// [`prefix_requirement`]: Self::prefix_requirement
+    pub fn prefix_required(&self, kind: Option<ChannelType>) -> bool {
+        match (self.prefix_requirement, kind) {
+            (Some(requirement), Some(kind)) => requirement(kind),
+            _ => true,
+        }
+    }
+
+    /// Assigns a boolean indicating whether [NSFW commands][nsfw] can be run in
+    /// direct messages.
+    ///
+    /// [nsfw]: crate::command::Command::nsfw
+    pub fn nsfw_in_dms(&mut self, b: bool) -> &mut Self {
+        self.nsfw_in_dms = b;
+        self
+    }
+
+    /// Assigns a boolean indicating whether commands are rejected in channels where
+    /// the bot cannot send messages.
+    ///
+    /// Refer to [`require_send_permission`] for more information.
+    ///
+    /// [`require_send_permission`]: Self::require_send_permission
+    pub fn require_send_permission(&mut self, b: bool) -> &mut Self {
+        self.require_send_permission = b;
+        self
+    }
+
+    /// Assigns a boolean indicating whether commands of the same user are run
+    /// one at a time.
+    ///
+    /// Refer to [`serialize_per_user`] for more information.
+    ///
+    /// [`serialize_per_user`]: Self::serialize_per_user
+    pub fn serialize_per_user(&mut self, b: bool) -> &mut Self {
+        self.serialize_per_user = b;
+        self
+    }
+
+    /// Assigns a boolean indicating whether the typing indicator is shown while a
+    /// command runs.
+    ///
+    /// Refer to [`auto_typing`] for more information.
+    ///
+    /// [`auto_typing`]: Self::auto_typing
+    pub fn auto_typing(&mut self, b: bool) -> &mut Self {
+        self.auto_typing = b;
+        self
+    }
+
+    /// Assigns a boolean indicating whether the message that invoked a command is
+    /// deleted after the command succeeds.
+    ///
+    /// Refer to [`delete_command_messages`] for more information.
+    ///
+    /// [`delete_command_messages`]: Self::delete_command_messages
+    pub fn delete_command_messages(&mut self, b: bool) -> &mut Self {
+        self.delete_command_messages = b;
+        self
+    }
+
+    /// Assigns a boolean indicating whether the reason of a failed check is sent to
+    /// the channel of the message.
+    ///
+    /// Refer to [`reply_check_failures`] for more information.
+    ///
+    /// [`reply_check_failures`]: Self::reply_check_failures
+    pub fn reply_check_failures(&mut self, b: bool) -> &mut Self {
+        self.reply_check_failures = b;
+        self
+    }
+
+    /// Assigns a boolean indicating whether the message that invoked a command is
+    /// reacted to with the result of the command.
+    ///
+    /// Refer to [`react_on_result`] for more information.
+    ///
+    /// [`react_on_result`]: Self::react_on_result
+    pub fn react_on_result(&mut self, b: bool) -> &mut Self {
+        self.react_on_result = b;
+        self
+    }
+
+    /// Assigns the reaction added to the message of a successful command.
+    ///
+    /// Refer to [`success_reaction`] for more information.
+    ///
+    /// [`success_reaction`]: Self::success_reaction
+    pub fn success_reaction<R>(&mut self, reaction: R) -> &mut Self
+    where
+        R: Into<ReactionType>,
+    {
+        self.success_reaction = reaction.into();
+        self
+    }
+
+    /// Assigns the reaction added to the message of a failed command.
+    ///
+    /// Refer to [`failure_reaction`] for more information.
+    ///
+    /// [`failure_reaction`]: Self::failure_reaction
+    pub fn failure_reaction<R>(&mut self, reaction: R) -> &mut Self
+    where
+        R: Into<ReactionType>,
+    {
+        self.failure_reaction = reaction.into();
+        self
+    }
+
+    /// Returns the reaction to add to the message of a command with the result.
+    ///
+    /// This is the [success reaction][success] if the command succeeded, and the
+    /// [failure reaction][failure] if it failed. `None` is returned if
+    /// [`react_on_result`] is disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::model::channel::ReactionType;
+    /// use serenity_framework::configuration::Configuration;
+    /// use serenity_framework::error::{DispatchError, Error};
+    /// use serenity_framework::DefaultError;
+    ///
+    /// let mut conf = Configuration::<(), DefaultError>::new();
+    ///
+    /// assert_eq!(conf.result_reaction(&Ok(())), None);
+    ///
+    /// conf.react_on_result(true).failure_reaction('🚫');
+    ///
+    /// let err = Err(Error::Dispatch(DispatchError::Aborted));
+    ///
+    /// assert_eq!(conf.result_reaction(&Ok(())), Some(&ReactionType::from('✅')));
+    /// assert_eq!(conf.result_reaction(&err), Some(&ReactionType::from('🚫')));
+    /// ```
+    ///
+    /// [success]: Self::success_reaction
+    /// [failure]: Self::failure_reaction
+    /// [`react_on_result`]: Self::react_on_result
+    pub fn result_reaction(&self, result: &Result<(), Error<E>>) -> Option<&ReactionType> {
+        if !self.react_on_result {
+            return None;
+        }
+
+        Some(match result {
+            Ok(()) => &self.success_reaction,
+            Err(_) => &self.failure_reaction,
+        })
+    }
+
+    /// Assigns a duration in which identical command invocations of a user are
+    /// rejected.
+    ///
+    /// Refer to [`dedupe_window`] for more information.
+    ///
+    /// [`dedupe_window`]: Self::dedupe_window
+    pub fn dedupe_window(&mut self, window: Duration) -> &mut Self {
+        self.dedupe_window = Some(window);
+        self
+    }
+
+    /// Assigns a boolean indicating whether several commands may share a name.
+    ///
+    /// Refer to [`allow_name_collisions`] for more information.
+    ///
+    /// [`allow_name_collisions`]: Self::allow_name_collisions
+    pub fn allow_name_collisions(&mut self, b: bool) -> &mut Self {
+        self.allow_name_collisions = b;
+        self
+    }
+
+    /// Assigns a string that separates subcommands from their parent command.
+    ///
+    /// Refer to [`subcommand_separator`] for more information.
+    ///
+    /// [`subcommand_separator`]: Self::subcommand_separator
+    pub fn subcommand_separator<I>(&mut self, separator: I) -> &mut Self
+    where
+        I: Into<String>,
+    {
+        self.subcommand_separator = Some(separator.into());
+        self
+    }
+
+    /// Assigns a boolean indicating whether arguments that start with a quotation mark
+    /// must terminate it.
+    ///
+    /// Refer to [`strict_quotes`] for more information.
+    ///
+    /// [`strict_quotes`]: Self::strict_quotes
+    pub fn strict_quotes(&mut self, b: bool) -> &mut Self {
+        self.strict_quotes = b;
+        self
+    }
+
+    /// Assigns a user id of the bot that will allow for mentions in prefix position.
+    pub fn on_mention<I>(&mut self, id: I) -> &mut Self
+    where
+        I: Into<UserId>,
+    {
+        self.on_mention = Some(id.into().to_string());
+        self
+    }
+
+    /// Assigns a boolean indicating whether a textual mention of the name of the bot
+    /// is a prefix.
+    ///
+    /// Refer to [`mention_by_name`] for more information.
+    ///
+    /// [`mention_by_name`]: Self::mention_by_name
+    pub fn mention_by_name(&mut self, b: bool) -> &mut Self {
+        self.mention_by_name = b;
+        self
+    }
+
+    /// Assigns users who own the bot.
+    ///
+    /// The users are added to the [`owners`] set.
+    ///
+    /// [`owners`]: Self::owners
+    pub fn owners<I, U>(&mut self, owners: I) -> &mut Self
+    where
+        I: IntoIterator<Item = U>,
+        U: Into<UserId>,
+    {
+        self.owners.extend(owners.into_iter().map(Into::into));
+        self
+    }
+
+    /// Assigns a boolean indicating whether the bot is in maintenance mode.
+    ///
+    /// Refer to [`maintenance`] for more information.
+    ///
+    /// [`maintenance`]: Self::maintenance
+    pub fn maintenance(&mut self, b: bool) -> &mut Self {
+        self.maintenance = b;
+        self
+    }
+
+    /// Assigns users who may not invoke commands.
+    ///
+    /// The users are added to the [`blocked_users`] set.
+    ///
+    /// [`blocked_users`]: Self::blocked_users
+    pub fn blocked_users<I, U>(&mut self, users: I) -> &mut Self
+    where
+        I: IntoIterator<Item = U>,
+        U: Into<UserId>,
+    {
+        self.blocked_users.extend(users.into_iter().map(Into::into));
+        self
+    }
+
+    /// Assigns channels in which commands may not be invoked.
+    ///
+    /// The channels are added to the [`blocked_channels`] set.
+    ///
+    /// [`blocked_channels`]: Self::blocked_channels
+    pub fn blocked_channels<I, C>(&mut self, channels: I) -> &mut Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<ChannelId>,
+    {
+        self.blocked_channels.extend(channels.into_iter().map(Into::into));
+        self
+    }
+
+    /// Assigns guilds in which commands may not be invoked.
+    ///
+    /// The guilds are added to the [`blocked_guilds`] set.
+    ///
+    /// [`blocked_guilds`]: Self::blocked_guilds
+    pub fn blocked_guilds<I, G>(&mut self, guilds: I) -> &mut Self
+    where
+        I: IntoIterator<Item = G>,
+        G: Into<GuildId>,
+    {
+        self.blocked_guilds.extend(guilds.into_iter().map(Into::into));
+        self
+    }
+
+    /// Assigns a check that applies to every command.
+    ///
+    /// The check is added to the [`global_checks`] list.
+    ///
+    /// [`global_checks`]: Self::global_checks
+    pub fn global_check(&mut self, check: CheckConstructor<D, E>) -> &mut Self {
+        self.global_checks.push(check());
+        self
+    }
+
+    /// Assigns a category to this configuration.
+    ///
+    /// The category is added to the [`categories`] list. Additionally,
+    /// all of its commands [are added][cmd] to the [`commands`] map
+    ///
+    /// [`categories`]: Self::categories
+    /// [`commands`]: Self::commands
+    /// [cmd]: Self::command
+    pub fn category<I>(&mut self, name: I, cmds: &[CommandConstructor<D, E>]) -> &mut Self
+    where
+        I: Into<String>,
+    {
+        let mut commands = Vec::with_capacity(cmds.len());
+
+        for cmd in cmds {
+            self.command(*cmd);
+            commands.push(CommandId::from(*cmd));
+        }
+
+        self.categories.push(Category {
+            name: name.into(),
+            prefix: None,
+            commands,
+        });
+
+        self
+    }
+
+    /// Assigns many categories to this configuration.
+    ///
+    /// Each pair of a name and a list of commands is [assigned as a category][cat],
+    /// in order. The [`categories!`] macro provides a more concise syntax.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_framework::command::Command;
+    /// use serenity_framework::configuration::Configuration;
+    /// use serenity_framework::DefaultError;
+    ///
+    /// fn ping() -> Command<(), DefaultError> {
+    ///     Command::builder("ping").build()
+    /// }
+    ///
+    /// fn pong() -> Command<(), DefaultError> {
+    ///     Command::builder("pong").build()
+    /// }
+    ///
+    /// fn ban() -> Command<(), DefaultError> {
+    ///     Command::builder("ban").build()
+    /// }
+    ///
+    /// fn kick() -> Command<(), DefaultError> {
+    ///     Command::builder("kick").build()
+    /// }
+    ///
+    /// let mut conf = Configuration::new();
+    /// conf.categories_from(&[("General", &[ping, pong]), ("Moderation", &[ban, kick])]);
+    ///
+    /// assert_eq!(conf.categories.len(), 2);
+    /// assert_eq!(conf.categories[0].name, "General");
+    /// assert_eq!(conf.categories[1].name, "Moderation");
+    ///
+    /// for name in &["ping", "pong", "ban", "kick"] {
+    ///     assert!(conf.commands.get_by_name(*name).is_some());
+    /// }
+    /// ```
+    ///
+    /// [cat]: Self::category
+    /// [`categories!`]: crate::categories
+    pub fn categories_from(
+        &mut self,
+        categories: &[(&str, &[CommandConstructor<D, E>])],
+    ) -> &mut Self {
+        for (name, cmds) in categories {
+            self.category(*name, cmds);
+        }
+
+        self
+    }
+
+    /// Assigns a category with a [prefix] to this configuration.
+    ///
+    /// The category is added to the [`categories`] list. Additionally,
+    /// all of its commands are added to the [`commands`] map, but not to the
+    /// [`root_level_commands`] set, as they are only invocable after the prefix
+    /// of the category.
+    ///
+    /// # Notes
+    ///
+    /// Names of commands are shared between all categories. A command in a prefixed
+    /// category must not share a name with any other command.
+    ///
+    /// If the prefix of a category is the same as the name of a root level command,
+    /// the category takes precedence.
+    ///
+    /// [prefix]: crate::category::Category::prefix
+    /// [`categories`]: Self::categories
+    /// [`commands`]: Self::commands
+    /// [`root_level_commands`]: Self::root_level_commands
+    pub fn prefixed_category<I, P>(
+        &mut self,
+        name: I,
+        prefix: P,
+        cmds: &[CommandConstructor<D, E>],
+    ) -> &mut Self
+    where
+        I: Into<String>,
+        P: Into<String>,
+    {
+        let mut commands = Vec::with_capacity(cmds.len());
+
+        for cmd in cmds {
+            let id = CommandId::from(*cmd);
+
+            if !self.commands.contains_id(id) {
+                self._command(id, *cmd);
+            }
+
+            commands.push(id);
+        }
+
+        self.categories.push(Category {
+            name: name.into(),
+            prefix: Some(prefix.into()),
+            commands,
+        });
+
+        self
+    }
+
+    /// Assigns a command to this configuration.
+    ///
+    /// The command is added to the [`commands`] map, alongside its subcommands.
+    /// It it also added into the [`root_level_commands`] set.
+    ///
+    /// [`commands`]: Self::commands
+    /// [`root_level_commands`]: Self::root_level_commands
+    pub fn command(&mut self, command: CommandConstructor<D, E>) -> &mut Self {
+        let id = CommandId::from(command);
+
+        // Skip instantiating this root command if if already exists.
+        if self.root_level_commands.contains(&id) {
+            return self;
+        }
+
+        self.root_level_commands.insert(id);
+        self._command(id, command);
+        self
+    }
+
+    /// Assigns many commands to this configuration.
+    ///
+    /// Each command is [added][cmd] in order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_framework::command::Command;
+    /// use serenity_framework::configuration::Configuration;
+    /// use serenity_framework::DefaultError;
+    ///
+    /// fn ping() -> Command<(), DefaultError> {
+    ///     Command::builder("ping").build()
+    /// }
+    ///
+    /// fn pong() -> Command<(), DefaultError> {
+    ///     Command::builder("pong").build()
+    /// }
+    ///
+    /// fn echo() -> Command<(), DefaultError> {
+    ///     Command::builder("echo").build()
+    /// }
+    ///
+    /// let mut conf = Configuration::new();
+    /// conf.commands(&[ping, pong, echo]);
+    ///
+    /// assert_eq!(conf.root_level_commands.len(), 3);
+    /// assert!(conf.commands.get_by_name("echo").is_some());
+    /// ```
+    ///
+    /// [cmd]: Self::command
+    pub fn commands(&mut self, cmds: &[CommandConstructor<D, E>]) -> &mut Self {
+        self.extend_commands(cmds.iter().copied())
+    }
+
+    /// Assigns commands from an iterator to this configuration.
+    ///
+    /// Each command is [added][cmd] in order. This allows registering commands
+    /// from any collection or iterator adapter, such as a filtered list.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_framework::command::{Command, CommandConstructor};
+    /// use serenity_framework::configuration::Configuration;
+    /// use serenity_framework::DefaultError;
+    ///
+    /// fn ping() -> Command<(), DefaultError> {
+    ///     Command::builder("ping").build()
+    /// }
+    ///
+    /// fn debug() -> Command<(), DefaultError> {
+    ///     Command::builder("debug").build()
+    /// }
+    ///
+    /// let debug_enabled = false;
+    /// let cmds: Vec<CommandConstructor<(), DefaultError>> = vec![ping, debug];
+    ///
+    /// let mut conf = Configuration::new();
+    /// conf.extend_commands(
+    ///     cmds.into_iter().filter(|cmd| debug_enabled || cmd().primary_name() != "debug"),
+    /// );
+    ///
+    /// assert_eq!(conf.root_level_commands.len(), 1);
+    /// assert!(conf.commands.get_by_name("ping").is_some());
+    /// assert!(conf.commands.get_by_name("debug").is_none());
+    /// ```
+    ///
+    /// [cmd]: Self::command
+    pub fn extend_commands<I>(&mut self, cmds: I) -> &mut Self
+    where
+        I: IntoIterator<Item = CommandConstructor<D, E>>,
+    {
+        for cmd in cmds {
+            self.command(cmd);
+        }
+
+        self
+    }
+
+    /// Removes all commands and categories from this configuration.
+    ///
+    /// This empties the [`commands`] map, the [`root_level_commands`] set, the
+    /// [`command_names`] map and the [`categories`] list, such that commands can be
+    /// registered again, such as when reloading them at runtime. The [`unknown_command`]
+    /// is kept.
+    ///
+    /// # Notes
+    ///
+    /// The identifier of a command is derived from its constructor, so registering the
+    /// same constructors again yields the same identifiers. The [`Context::command_id`]
+    /// of a command that is running while the commands are cleared thus remains valid
+    /// if its constructor is registered again. Otherwise, looking up the command by its
+    /// identifier returns `None` until it is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_framework::command::{Command, CommandId};
+    /// use serenity_framework::configuration::Configuration;
+    /// use serenity_framework::DefaultError;
+    ///
+    /// fn ping() -> Command<(), DefaultError> {
+    ///     Command::builder("ping").build()
+    /// }
+    ///
+    /// fn ban() -> Command<(), DefaultError> {
+    ///     Command::builder("ban").build()
+    /// }
+    ///
+    /// fn kick() -> Command<(), DefaultError> {
+    ///     Command::builder("kick").build()
+    /// }
+    ///
+    /// let mut conf = Configuration::new();
+    /// conf.command(ping).category("moderation", &[ban]);
+    ///
+    /// conf.clear_commands();
+    ///
+    /// assert!(conf.commands.is_empty());
+    /// assert!(conf.root_level_commands.is_empty());
+    /// assert!(conf.categories.is_empty());
+    /// assert!(conf.commands.get_by_name("ping").is_none());
+    ///
+    /// conf.command(ping).category("moderation", &[kick]);
+    ///
+    /// assert_eq!(conf.commands.len(), 2);
+    /// assert_eq!(conf.commands.get_id("ping"), Some(CommandId::from(ping as fn() -> _)));
+    /// assert!(conf.commands.get_by_name("kick").is_some());
+    /// assert!(conf.commands.get_by_name("ban").is_none());
+    /// assert_eq!(conf.categories.len(), 1);
+    /// ```
+    ///
+    /// [`commands`]: Self::commands
+    /// [`root_level_commands`]: Self::root_level_commands
+    /// [`command_names`]: Self::command_names
+    /// [`categories`]: Self::categories
+    /// [`unknown_command`]: Self::unknown_command
+    /// [`Context::command_id`]: crate::context::Context::command_id
+    pub fn clear_commands(&mut self) -> &mut Self {
+        self.commands = CommandMap::default();
+        self.root_level_commands.clear();
+        self.command_names.clear();
+        self.categories.clear();
+        self
+    }
+
+    /// Returns the [category][cat] that the command belongs to.
+    ///
+    /// Only the commands that the category was [created with][category] belong to
+    /// it; their subcommands do not. If the command belongs to multiple categories,
+    /// the category that was added first is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_framework::command::{Command, CommandId};
+    /// use serenity_framework::configuration::Configuration;
+    /// use serenity_framework::DefaultError;
+    ///
+    /// fn ban() -> Command<(), DefaultError> {
+    ///     Command::builder("ban").build()
+    /// }
+    ///
+    /// fn ping() -> Command<(), DefaultError> {
+    ///     Command::builder("ping").build()
+    /// }
+    ///
+    /// let mut conf = Configuration::new();
+    /// conf.category("moderation", &[ban]).command(ping);
+    ///
+    /// let category = conf.category_of(CommandId::from(ban as fn() -> _)).unwrap();
+    /// assert_eq!(category.name, "moderation");
+    ///
+    /// assert!(conf.category_of(CommandId::from(ping as fn() -> _)).is_none());
+    /// ```
+    ///
+    /// [cat]: crate::category::Category
+    /// [category]: Self::category
+    pub fn category_of(&self, id: CommandId) -> Option<&Category> {
+        self.categories.iter().find(|category| category.commands.contains(&id))
+    }
+
+    /// Returns the names of all checks that apply to the command and are
+    /// [displayed in help commands][display], in the order they are run.
+    ///
+    /// The [`global_checks`] come first, followed by the [checks of the command][cmd].
+    /// Categories do not have checks of their own.
+    ///
+    /// `None` is returned if the command is not in the [`commands`] map.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::model::channel::Message;
+    /// use serenity_framework::command::CommandId;
+    /// use serenity_framework::prelude::*;
+    ///
+    /// #[check("Not Blocked")]
+    /// async fn not_blocked(_ctx: &CheckContext<'_>, _msg: &Message) -> CheckResult {
+    ///     Ok(())
+    /// }
+    ///
+    /// #[check("Manage Messages")]
+    /// async fn manage_messages(_ctx: &CheckContext<'_>, _msg: &Message) -> CheckResult {
+    ///     Ok(())
+    /// }
+    ///
+    /// #[check]
+    /// #[display_in_help(false)]
+    /// async fn not_muted(_ctx: &CheckContext<'_>, _msg: &Message) -> CheckResult {
+    ///     Ok(())
+    /// }
+    ///
+    /// #[command]
+    /// #[check(manage_messages, not_muted)]
+    /// async fn purge(_ctx: FrameworkContext, _msg: &Message) {}
+    ///
+    /// let mut conf = Configuration::new();
+    /// conf.global_check(not_blocked).category("moderation", &[purge]);
+    ///
+    /// let id = CommandId::from(purge as fn() -> _);
+    /// assert_eq!(conf.check_names(id), Some(vec!["Not Blocked", "Manage Messages"]));
+    /// ```
+    ///
+    /// [display]: crate::check::Check::display_in_help
+    /// [`global_checks`]: Self::global_checks
+    /// [cmd]: crate::command::Command::check_names
+    /// [`commands`]: Self::commands
+    pub fn check_names(&self, id: CommandId) -> Option<Vec<&str>> {
+        let command = self.commands.get(id)?;
+
+        let mut names = self
+            .global_checks
+            .iter()
+            .filter(|check| check.display_in_help)
+            .map(|check| check.name.as_str())
+            .collect::<Vec<_>>();
+
+        names.extend(command.check_names());
+
+        Some(names)
+    }
+
+    /// Reconstructs the identifier of a command from its [internal representation][repr].
+    ///
+    /// The identifier is returned only if it belongs to a command in the [`commands`] map.
+    /// This allows persisting identifiers, such as for usage statistics, and restoring
+    /// them safely, even if the commands of the bot have changed since.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_framework::command::{Command, CommandId};
+    /// use serenity_framework::configuration::Configuration;
+    /// use serenity_framework::DefaultError;
+    ///
+    /// fn ping() -> Command<(), DefaultError> {
+    ///     Command::builder("ping").build()
+    /// }
+    ///
+    /// let mut conf = Configuration::new();
+    /// conf.command(ping);
+    ///
+    /// let id = CommandId::from(ping as fn() -> _);
+    /// let persisted = id.into_usize();
+    ///
+    /// assert_eq!(conf.command_id_from_usize(persisted), Some(id));
+    /// assert_eq!(conf.command_id_from_usize(persisted + 1), None);
+    /// assert_eq!(conf.command_id_from_usize(0), None);
+    /// ```
+    ///
+    /// [repr]: crate::command::CommandId::into_usize
+    /// [`commands`]: Self::commands
+    pub fn command_id_from_usize(&self, n: usize) -> Option<CommandId> {
+        let id = CommandId(n);
+
+        if self.commands.contains_id(id) {
+            Some(id)
+        } else {
+            None
+        }
+    }
+
+    /// Validates that every command name refers to only one command.
+    ///
+    /// All commands share one [map][`commands`] of names. If two commands share a name,
+    /// the command that was added last overwrites the name of the other command.
+    /// This returns an error for every overwritten name. If the name is shared between a
+    /// root level command and a subcommand, the error is
+    /// [`ConfigError::ShadowedRootCommand`]. Otherwise, it is [`ConfigError::DuplicateName`].
+    ///
+    /// The errors are sorted by name.
+    ///
+    /// If [`allow_name_collisions`] is enabled, names are not overwritten, and this
+    /// always returns `Ok(())`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_framework::command::{Command, CommandId};
+    /// use serenity_framework::configuration::Configuration;
+    /// use serenity_framework::error::ConfigError;
+    /// use serenity_framework::DefaultError;
+    ///
+    /// fn config() -> Command<(), DefaultError> {
+    ///     Command::builder("config").subcommand(config_list).build()
+    /// }
+    ///
+    /// fn config_list() -> Command<(), DefaultError> {
+    ///     Command::builder("list").build()
+    /// }
+    ///
+    /// fn list() -> Command<(), DefaultError> {
+    ///     Command::builder("list").build()
+    /// }
+    ///
+    /// fn show() -> Command<(), DefaultError> {
+    ///     Command::builder("show").name("ls").build()
+    /// }
+    ///
+    /// fn ls() -> Command<(), DefaultError> {
+    ///     Command::builder("ls").build()
+    /// }
+    ///
+    /// let mut conf = Configuration::new();
+    /// conf.command(list).command(config);
+    ///
+    /// assert_eq!(
+    ///     conf.validate(),
+    ///     Err(vec![ConfigError::ShadowedRootCommand {
+    ///         name: "list".to_string(),
+    ///         root: CommandId::from(list as fn() -> _),
+    ///         subcommand: CommandId::from(config_list as fn() -> _),
+    ///     }])
+    /// );
+    ///
+    /// let mut conf = Configuration::new();
+    /// conf.command(list).command(show);
+    ///
+    /// assert_eq!(conf.validate(), Ok(()));
+    ///
+    /// let mut conf = Configuration::new();
+    /// conf.command(show).command(ls);
+    ///
+    /// assert_eq!(
+    ///     conf.validate(),
+    ///     Err(vec![ConfigError::DuplicateName {
+    ///         name: "ls".to_string(),
+    ///         command: CommandId::from(ls as fn() -> _),
+    ///         overwritten: CommandId::from(show as fn() -> _),
+    ///     }])
+    /// );
+    /// ```
+    ///
+    /// [`commands`]: Self::commands
+    /// [`allow_name_collisions`]: Self::allow_name_collisions
+    /// [`ConfigError::ShadowedRootCommand`]: crate::error::ConfigError::ShadowedRootCommand
+    /// [`ConfigError::DuplicateName`]: crate::error::ConfigError::DuplicateName
+    pub fn validate(&self) -> Result<(), Vec<ConfigError>> {
+        if self.allow_name_collisions {
+            return Ok(());
+        }
+
+        let is_subcommand =
+            |id: CommandId| self.commands.iter().any(|(_, cmd)| cmd.subcommands.contains(&id));
+
+        let mut errors = Vec::new();
+
+        for (id, command) in self.commands.iter() {
+            for name in &command.names {
+                let name = if self.case_insensitive { name.to_lowercase() } else { name.clone() };
+
+                let owner = match self.commands.get_id(&name) {
+                    Some(owner) if owner != *id => owner,
+                    _ => continue,
+                };
+
+                let root_and_subcommand = |root: CommandId, subcommand: CommandId| {
+                    self.root_level_commands.contains(&root)
+                        && !self.root_level_commands.contains(&subcommand)
+                        && is_subcommand(subcommand)
+                };
+
+                errors.push(if root_and_subcommand(owner, *id) {
+                    ConfigError::ShadowedRootCommand {
+                        name,
+                        root: owner,
+                        subcommand: *id,
+                    }
+                } else if root_and_subcommand(*id, owner) {
+                    ConfigError::ShadowedRootCommand {
+                        name,
+                        root: *id,
+                        subcommand: owner,
+                    }
+                } else {
+                    ConfigError::DuplicateName {
+                        name,
+                        command: owner,
+                        overwritten: *id,
+                    }
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            errors.sort_by(|a, b| error_name(a).cmp(error_name(b)));
+            Err(errors)
+        }
+    }
+
+    /// Returns the commands in the [`commands`] map that cannot be invoked.
+    ///
+    /// A command can be invoked if it is a [root level command][root], a command of
+    /// a [prefixed category][category], or a subcommand of a command that can be
+    /// invoked. Commands [added][cmd] to the configuration are always reachable, but
+    /// commands inserted into the map directly may not be.
+    ///
+    /// The identifiers are returned in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_framework::command::{Command, CommandId};
+    /// use serenity_framework::configuration::Configuration;
+    /// use serenity_framework::DefaultError;
+    ///
+    /// fn config() -> Command<(), DefaultError> {
+    ///     Command::builder("config").subcommand(config_list).build()
+    /// }
+    ///
+    /// fn config_list() -> Command<(), DefaultError> {
+    ///     Command::builder("list").build()
+    /// }
+    ///
+    /// fn remind() -> Command<(), DefaultError> {
+    ///     Command::builder("remind").subcommand(remind_list).build()
+    /// }
+    ///
+    /// fn remind_list() -> Command<(), DefaultError> {
+    ///     Command::builder("list").build()
+    /// }
+    ///
+    /// let mut conf = Configuration::new();
+    /// conf.command(config);
+    ///
+    /// assert!(conf.unreachable_commands().is_empty());
+    ///
+    /// // The parent of the subcommand is never registered.
+    /// let id = CommandId::from(remind_list as fn() -> _);
+    /// conf.commands.insert(id, remind_list());
+    ///
+    /// assert_eq!(conf.unreachable_commands(), vec![id]);
+    /// ```
+    ///
+    /// [`commands`]: Self::commands
+    /// [root]: Self::root_level_commands
+    /// [category]: Self::prefixed_category
+    /// [cmd]: Self::command
+    pub fn unreachable_commands(&self) -> Vec<CommandId> {
+        let mut stack = self
+            .root_level_commands
+            .iter()
+            .copied()
+            .chain(
+                self.categories
+                    .iter()
+                    .filter(|category| category.prefix.is_some())
+                    .flat_map(|category| category.commands.iter().copied()),
+            )
+            .collect::<Vec<_>>();
+
+        let mut reachable = HashSet::new();
+
+        while let Some(id) = stack.pop() {
+            if !reachable.insert(id) {
+                continue;
+            }
+
+            if let Some(command) = self.commands.get(id) {
+                stack.extend(command.subcommands.iter().copied());
+            }
+        }
+
+        let mut unreachable = self
+            .commands
+            .iter()
+            .map(|(id, _)| *id)
+            .filter(|id| !reachable.contains(id))
+            .collect::<Vec<_>>();
+
+        unreachable.sort();
+        unreachable
+    }
+
+    fn _command(&mut self, id: CommandId, command: CommandConstructor<D, E>) {
+        let mut command = command();
+        command.id = id;
+
+        for name in &command.names {
+            let name = if self.case_insensitive { name.to_lowercase() } else { name.clone() };
+
+            let ids = self.command_names.entry(name.clone()).or_default();
+
+            if !ids.contains(&command.id) {
+                ids.push(command.id);
+            }
+
+            self.commands.insert_name(name, command.id);
+        }
+
+        for id in &command.subcommands {
+            // Skip instantiating this subcommand if it already exists.
+            if self.commands.contains_id(*id) {
+                continue;
+            }
+
+            let ctor: CommandConstructor<D, E> = id.into_constructor();
+            self._command(*id, ctor);
+        }
+
+        self.commands.insert(command.id, command);
+    }
+}
+
+fn error_name(err: &ConfigError) -> &str {
+    match err {
+        ConfigError::DuplicateName {
+            name, ..
+        }
+        | ConfigError::ShadowedRootCommand {
+            name, ..
+        } => name,
+    }
+}
+
+impl<D, E> fmt::Debug for Configuration<D, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Configuration")
+            .field("prefixes", &self.prefixes)
+            .field("prefix_fn", &"<fn>")
+            .field("lazy_prefixes", &self.lazy_prefixes)
+            .field("dynamic_prefix", &"<fn>")
+            .field("prefix_matchers", &"<matchers>")
+            .field("content_preprocessor", &"<fn>")
+            .field("prefix_escape", &self.prefix_escape)
+            .field("trim_leading", &self.trim_leading)
+            .field("dynamic_delimiter", &"<fn>")
+            .field("locale_resolver", &"<fn>")
+            .field("before", &"<fn>")
+            .field("command_enabled", &"<fn>")
+            .field("normal_message", &"<fn>")
+            .field("unknown_command", &self.unknown_command)
+            .field("on_timing", &"<fn>")
+            .field("on_blocked", &"<fn>")
             .field("case_insensitive", &self.case_insensitive)
             .field("no_dm_prefix", &self.no_dm_prefix)
+            .field("prefix_requirement", &"<fn>")
+            .field("nsfw_in_dms", &self.nsfw_in_dms)
+            .field("require_send_permission", &self.require_send_permission)
+            .field("serialize_per_user", &self.serialize_per_user)
+            .field("auto_typing", &self.auto_typing)
+            .field("delete_command_messages", &self.delete_command_messages)
+            .field("reply_check_failures", &self.reply_check_failures)
+            .field("react_on_result", &self.react_on_result)
+            .field("success_reaction", &self.success_reaction)
+            .field("failure_reaction", &self.failure_reaction)
+            .field("dedupe_window", &self.dedupe_window)
+            .field("allow_name_collisions", &self.allow_name_collisions)
+            .field("subcommand_separator", &self.subcommand_separator)
+            .field("strict_quotes", &self.strict_quotes)
             .field("on_mention", &self.on_mention)
+            .field("mention_by_name", &self.mention_by_name)
+            .field("owners", &self.owners)
+            .field("maintenance", &self.maintenance)
+            .field("blocked_users", &self.blocked_users)
+            .field("blocked_channels", &self.blocked_channels)
+            .field("blocked_guilds", &self.blocked_guilds)
+            .field("global_checks", &self.global_checks)
             .field("categories", &self.categories)
             .field("root_level_commands", &self.root_level_commands)
             .field("commands", &self.commands)
+            .field("command_names", &self.command_names)
             .finish()
     }
 }
+
+/// A builder type for creating a [`Configuration`] in one expression.
+///
+/// Every method delegates to the [`Configuration`] method of the same name.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity::model::channel::Message;
+/// use serenity_framework::configuration::Configuration;
+/// use serenity_framework::prelude::*;
+///
+/// #[command]
+/// async fn ping(_ctx: FrameworkContext, _msg: &Message) {}
+///
+/// let framework: Framework = Framework::new(
+///     Configuration::builder().prefix("!").case_insensitive(true).command(ping).build(),
+/// );
+///
+/// let conf = serenity::futures::executor::block_on(framework.conf.read());
+/// assert_eq!(conf.prefixes, vec!["!".to_string()]);
+/// assert!(conf.commands.get_by_name("ping").is_some());
+/// ```
+pub struct ConfigurationBuilder<D, E> {
+    inner: Configuration<D, E>,
+}
+
+impl<D, E> ConfigurationBuilder<D, E> {
+    /// Constructs a new instance of the builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Assigns a prefix to the configuration.
+    ///
+    /// Refer to [`Configuration::prefix`].
+    pub fn prefix<I>(mut self, prefix: I) -> Self
+    where
+        I: Into<String>,
+    {
+        self.inner.prefix(prefix);
+        self
+    }
+
+    /// Assigns a function returning a list of static prefixes, evaluated on first use.
+    ///
+    /// Refer to [`Configuration::prefix_fn`].
+    pub fn prefix_fn(mut self, f: PrefixFn) -> Self {
+        self.inner.prefix_fn(f);
+        self
+    }
+
+    /// Assigns a function to dynamically parse the prefix.
+    ///
+    /// Refer to [`Configuration::dynamic_prefix`].
+    pub fn dynamic_prefix(mut self, prefix: DynamicPrefix<D, E>) -> Self {
+        self.inner.dynamic_prefix(prefix);
+        self
+    }
+
+    /// Assigns a custom strategy to parse the prefix.
+    ///
+    /// Refer to [`Configuration::prefix_matcher`].
+    pub fn prefix_matcher<M>(mut self, matcher: M) -> Self
+    where
+        M: PrefixMatcher<D, E> + 'static,
+    {
+        self.inner.prefix_matcher(matcher);
+        self
+    }
+
+    /// Assigns a function to transform the content of the message before it is parsed.
+    ///
+    /// Refer to [`Configuration::content_preprocessor`].
+    pub fn content_preprocessor(mut self, f: ContentPreprocessor) -> Self {
+        self.inner.content_preprocessor(f);
+        self
+    }
+
+    /// Assigns a character that escapes the prefix.
+    ///
+    /// Refer to [`Configuration::prefix_escape`].
+    pub fn prefix_escape(mut self, escape: char) -> Self {
+        self.inner.prefix_escape(escape);
+        self
+    }
+
+    /// Assigns a boolean indicating whether leading whitespace and invisible characters
+    /// are ignored before the prefix.
+    ///
+    /// Refer to [`Configuration::trim_leading`].
+    pub fn trim_leading(mut self, b: bool) -> Self {
+        self.inner.trim_leading(b);
+        self
+    }
+
+    /// Assigns a function to choose the delimiter of the arguments of a command invocation.
+    ///
+    /// Refer to [`Configuration::dynamic_delimiter`].
+    pub fn dynamic_delimiter(mut self, f: DynamicDelimiter) -> Self {
+        self.inner.dynamic_delimiter(f);
+        self
+    }
+
+    /// Assigns a function to resolve the locale of the author of a message.
+    ///
+    /// Refer to [`Configuration::locale_resolver`].
+    pub fn locale_resolver(mut self, f: LocaleResolver) -> Self {
+        self.inner.locale_resolver(f);
+        self
+    }
+
+    /// Assigns a function that is called before a command is invoked.
+    ///
+    /// Refer to [`Configuration::before`].
+    pub fn before(mut self, f: BeforeHook<D, E>) -> Self {
+        self.inner.before(f);
+        self
+    }
+
+    /// Assigns a function deciding whether a command is enabled for a message.
+    ///
+    /// Refer to [`Configuration::command_enabled`].
+    pub fn command_enabled(mut self, f: CommandEnabledHook) -> Self {
+        self.inner.command_enabled(f);
+        self
+    }
+
+    /// Assigns a function that is called for messages without a command invocation.
+    ///
+    /// Refer to [`Configuration::normal_message`].
+    pub fn normal_message(mut self, f: NormalMessageHook<D>) -> Self {
+        self.inner.normal_message(f);
+        self
+    }
+
+    /// Assigns the command that is invoked for unknown command names.
+    ///
+    /// Refer to [`Configuration::unknown_command`].
+    pub fn unknown_command(mut self, command: CommandConstructor<D, E>) -> Self {
+        self.inner.unknown_command(command);
+        self
+    }
+
+    /// Assigns a function to be called with the execution time of every command.
+    ///
+    /// Refer to [`Configuration::on_timing`].
+    pub fn on_timing(mut self, f: TimingHook) -> Self {
+        self.inner.on_timing(f);
+        self
+    }
+
+    /// Assigns a function to be called when a command invocation is blocked.
+    ///
+    /// Refer to [`Configuration::on_blocked`].
+    pub fn on_blocked(mut self, f: BlockedHook) -> Self {
+        self.inner.on_blocked(f);
+        self
+    }
+
+    /// Assigns a boolean indicating whether the casing of prefixes and command names does not matter.
+    ///
+    /// Refer to [`Configuration::case_insensitive`].
+    pub fn case_insensitive(mut self, b: bool) -> Self {
+        self.inner.case_insensitive(b);
+        self
+    }
+
+    /// Assigns a boolean indicating whether the prefix is not necessary in direct messages.
+    ///
+    /// Refer to [`Configuration::no_dm_prefix`].
+    pub fn no_dm_prefix(mut self, b: bool) -> Self {
+        self.inner.no_dm_prefix(b);
+        self
+    }
+
+    /// Assigns a function that decides whether the prefix is necessary in a type of channel.
+    ///
+    /// Refer to [`Configuration::prefix_requirement`].
+    pub fn prefix_requirement(mut self, f: PrefixRequirement) -> Self {
+        self.inner.prefix_requirement(f);
+        self
+    }
+
+    /// Assigns a boolean indicating whether NSFW commands can be run in direct messages.
+    ///
+    /// Refer to [`Configuration::nsfw_in_dms`].
+    pub fn nsfw_in_dms(mut self, b: bool) -> Self {
+        self.inner.nsfw_in_dms(b);
+        self
+    }
+
+    /// Assigns a boolean indicating whether commands are rejected in channels where
+    /// the bot cannot send messages.
+    ///
+    /// Refer to [`Configuration::require_send_permission`].
+    pub fn require_send_permission(mut self, b: bool) -> Self {
+        self.inner.require_send_permission(b);
+        self
+    }
+
+    /// Assigns a boolean indicating whether commands of the same user are run one at a time.
+    ///
+    /// Refer to [`Configuration::serialize_per_user`].
+    pub fn serialize_per_user(mut self, b: bool) -> Self {
+        self.inner.serialize_per_user(b);
+        self
+    }
+
+    /// Assigns a boolean indicating whether the typing indicator is shown while a
+    /// command runs.
+    ///
+    /// Refer to [`Configuration::auto_typing`].
+    pub fn auto_typing(mut self, b: bool) -> Self {
+        self.inner.auto_typing(b);
+        self
+    }
+
+    /// Assigns a boolean indicating whether the message that invoked a command is
+    /// deleted after the command succeeds.
+    ///
+    /// Refer to [`Configuration::delete_command_messages`].
+    pub fn delete_command_messages(mut self, b: bool) -> Self {
+        self.inner.delete_command_messages(b);
+        self
+    }
+
+    /// Assigns a boolean indicating whether the reason of a failed check is sent to
+    /// the channel of the message.
+    ///
+    /// Refer to [`Configuration::reply_check_failures`].
+    pub fn reply_check_failures(mut self, b: bool) -> Self {
+        self.inner.reply_check_failures(b);
+        self
+    }
+
+    /// Assigns a boolean indicating whether the message that invoked a command is
+    /// reacted to with the result of the command.
+    ///
+    /// Refer to [`Configuration::react_on_result`].
+    pub fn react_on_result(mut self, b: bool) -> Self {
+        self.inner.react_on_result(b);
+        self
+    }
+
+    /// Assigns the reaction added to the message of a successful command.
+    ///
+    /// Refer to [`Configuration::success_reaction`].
+    pub fn success_reaction<R>(mut self, reaction: R) -> Self
+    where
+        R: Into<ReactionType>,
+    {
+        self.inner.success_reaction(reaction);
+        self
+    }
+
+    /// Assigns the reaction added to the message of a failed command.
+    ///
+    /// Refer to [`Configuration::failure_reaction`].
+    pub fn failure_reaction<R>(mut self, reaction: R) -> Self
+    where
+        R: Into<ReactionType>,
+    {
+        self.inner.failure_reaction(reaction);
+        self
+    }
+
+    /// Assigns a duration in which identical command invocations of a user are rejected.
+    ///
+    /// Refer to [`Configuration::dedupe_window`].
+    pub fn dedupe_window(mut self, window: Duration) -> Self {
+        self.inner.dedupe_window(window);
+        self
+    }
+
+    /// Assigns a boolean indicating whether several commands may share a name.
+    ///
+    /// Refer to [`Configuration::allow_name_collisions`].
+    pub fn allow_name_collisions(mut self, b: bool) -> Self {
+        self.inner.allow_name_collisions(b);
+        self
+    }
+
+    /// Assigns a string that separates subcommands from their parent command.
+    ///
+    /// Refer to [`Configuration::subcommand_separator`].
+    pub fn subcommand_separator<I>(mut self, separator: I) -> Self
+    where
+        I: Into<String>,
+    {
+        self.inner.subcommand_separator(separator);
+        self
+    }
+
+    /// Assigns a boolean indicating whether arguments that start with a quotation mark
+    /// must terminate it.
+    ///
+    /// Refer to [`Configuration::strict_quotes`].
+    pub fn strict_quotes(mut self, b: bool) -> Self {
+        self.inner.strict_quotes(b);
+        self
+    }
+
+    /// Assigns a user id of the bot that will allow for mentions in prefix position.
+    ///
+    /// Refer to [`Configuration::on_mention`].
+    pub fn on_mention<I>(mut self, id: I) -> Self
+    where
+        I: Into<UserId>,
+    {
+        self.inner.on_mention(id);
+        self
+    }
+
+    /// Assigns a boolean indicating whether a textual mention of the name of the bot
+    /// is a prefix.
+    ///
+    /// Refer to [`Configuration::mention_by_name`].
+    pub fn mention_by_name(mut self, b: bool) -> Self {
+        self.inner.mention_by_name(b);
+        self
+    }
+
+    /// Assigns users who own the bot.
+    ///
+    /// Refer to [`Configuration::owners`].
+    pub fn owners<I, U>(mut self, owners: I) -> Self
+    where
+        I: IntoIterator<Item = U>,
+        U: Into<UserId>,
+    {
+        self.inner.owners(owners);
+        self
+    }
+
+    /// Assigns a boolean indicating whether the bot is in maintenance mode.
+    ///
+    /// Refer to [`Configuration::maintenance`].
+    pub fn maintenance(mut self, b: bool) -> Self {
+        self.inner.maintenance(b);
+        self
+    }
+
+    /// Assigns users who may not invoke commands.
+    ///
+    /// Refer to [`Configuration::blocked_users`].
+    pub fn blocked_users<I, U>(mut self, users: I) -> Self
+    where
+        I: IntoIterator<Item = U>,
+        U: Into<UserId>,
+    {
+        self.inner.blocked_users(users);
+        self
+    }
+
+    /// Assigns channels in which commands may not be invoked.
+    ///
+    /// Refer to [`Configuration::blocked_channels`].
+    pub fn blocked_channels<I, C>(mut self, channels: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<ChannelId>,
+    {
+        self.inner.blocked_channels(channels);
+        self
+    }
+
+    /// Assigns guilds in which commands may not be invoked.
+    ///
+    /// Refer to [`Configuration::blocked_guilds`].
+    pub fn blocked_guilds<I, G>(mut self, guilds: I) -> Self
+    where
+        I: IntoIterator<Item = G>,
+        G: Into<GuildId>,
+    {
+        self.inner.blocked_guilds(guilds);
+        self
+    }
+
+    /// Assigns a check that applies to every command.
+    ///
+    /// Refer to [`Configuration::global_check`].
+    pub fn global_check(mut self, check: CheckConstructor<D, E>) -> Self {
+        self.inner.global_check(check);
+        self
+    }
+
+    /// Assigns a category to the configuration.
+    ///
+    /// Refer to [`Configuration::category`].
+    pub fn category<I>(mut self, name: I, cmds: &[CommandConstructor<D, E>]) -> Self
+    where
+        I: Into<String>,
+    {
+        self.inner.category(name, cmds);
+        self
+    }
+
+    /// Assigns many categories to the configuration.
+    ///
+    /// Refer to [`Configuration::categories_from`].
+    pub fn categories_from(mut self, categories: &[(&str, &[CommandConstructor<D, E>])]) -> Self {
+        self.inner.categories_from(categories);
+        self
+    }
+
+    /// Assigns a category with a prefix to the configuration.
+    ///
+    /// Refer to [`Configuration::prefixed_category`].
+    pub fn prefixed_category<I, P>(
+        mut self,
+        name: I,
+        prefix: P,
+        cmds: &[CommandConstructor<D, E>],
+    ) -> Self
+    where
+        I: Into<String>,
+        P: Into<String>,
+    {
+        self.inner.prefixed_category(name, prefix, cmds);
+        self
+    }
+
+    /// Assigns a command to the configuration.
+    ///
+    /// Refer to [`Configuration::command`].
+    pub fn command(mut self, command: CommandConstructor<D, E>) -> Self {
+        self.inner.command(command);
+        self
+    }
+
+    /// Assigns many commands to the configuration.
+    ///
+    /// Refer to [`Configuration::commands`].
+    pub fn commands(mut self, cmds: &[CommandConstructor<D, E>]) -> Self {
+        self.inner.commands(cmds);
+        self
+    }
+
+    /// Assigns commands from an iterator to the configuration.
+    ///
+    /// Refer to [`Configuration::extend_commands`].
+    pub fn extend_commands<I>(mut self, cmds: I) -> Self
+    where
+        I: IntoIterator<Item = CommandConstructor<D, E>>,
+    {
+        self.inner.extend_commands(cmds);
+        self
+    }
+
+    /// Complete building a configuration.
+    pub fn build(self) -> Configuration<D, E> {
+        self.inner
+    }
+}
+
+impl<D, E> Clone for ConfigurationBuilder<D, E> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<D, E> Default for ConfigurationBuilder<D, E> {
+    fn default() -> Self {
+        Self {
+            inner: Configuration::default(),
+        }
+    }
+}
+
+impl<D, E> fmt::Debug for ConfigurationBuilder<D, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConfigurationBuilder").field("inner", &self.inner).finish()
+    }
+}
+
+/// Assigns many commands to a [`Configuration`].
+///
+/// The first argument is the configuration, followed by a list of commands.
+/// This expands to a chain of [`Configuration::command`] calls.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_framework::command::Command;
+/// use serenity_framework::configuration::Configuration;
+/// use serenity_framework::{register, DefaultError};
+///
+/// fn ping() -> Command<(), DefaultError> {
+///     Command::builder("ping").build()
+/// }
+///
+/// fn pong() -> Command<(), DefaultError> {
+///     Command::builder("pong").build()
+/// }
+///
+/// fn echo() -> Command<(), DefaultError> {
+///     Command::builder("echo").build()
+/// }
+///
+/// let mut conf = Configuration::new();
+/// register!(conf, ping, pong, echo);
+///
+/// assert_eq!(conf.root_level_commands.len(), 3);
+/// assert!(conf.commands.get_by_name("ping").is_some());
+/// ```
+#[macro_export]
+macro_rules! register {
+    ($conf:expr, $($cmd:expr),+ $(,)?) => {
+        $conf$(.command($cmd))+
+    };
+}
+
+/// Assigns many categories to a [`Configuration`].
+///
+/// The first argument is the configuration, followed by a list of categories,
+/// each a name and a bracketed list of commands. This expands to a chain of
+/// [`Configuration::category`] calls.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_framework::command::Command;
+/// use serenity_framework::configuration::Configuration;
+/// use serenity_framework::{categories, DefaultError};
+///
+/// fn ping() -> Command<(), DefaultError> {
+///     Command::builder("ping").build()
+/// }
+///
+/// fn pong() -> Command<(), DefaultError> {
+///     Command::builder("pong").build()
+/// }
+///
+/// fn ban() -> Command<(), DefaultError> {
+///     Command::builder("ban").build()
+/// }
+///
+/// fn kick() -> Command<(), DefaultError> {
+///     Command::builder("kick").build()
+/// }
+///
+/// let mut conf = Configuration::new();
+/// categories!(conf,
+///     "General" => [ping, pong],
+///     "Moderation" => [ban, kick],
+/// );
+///
+/// assert_eq!(conf.categories.len(), 2);
+/// assert_eq!(conf.categories[1].commands.len(), 2);
+/// assert_eq!(conf.root_level_commands.len(), 4);
+/// ```
+#[macro_export]
+macro_rules! categories {
+    ($conf:expr, $($name:expr => [$($cmd:expr),* $(,)?]),+ $(,)?) => {
+        $conf$(.category($name, &[$($cmd),*]))+
+    };
+}
@@ -8,14 +8,26 @@
 //! [msg]: serenity::model::channel::Message
 
 use std::sync::Arc;
+use std::time::Duration;
 
+use serenity::builder::CreateEmbed;
 use serenity::cache::Cache;
+use serenity::client::bridge::gateway::{ShardId, ShardManager};
 use serenity::client::Context as SerenityContext;
+#[cfg(feature = "collector")]
+use serenity::futures::StreamExt;
+use serenity::http::typing::Typing;
 use serenity::http::{CacheHttp, Http};
+#[cfg(feature = "collector")]
+use serenity::model::channel::ReactionType;
+use serenity::model::channel::{AttachmentType, Message};
+use serenity::model::id::ChannelId;
 use serenity::prelude::RwLock;
+use tokio::sync::RwLockReadGuard;
 
 use crate::command::CommandId;
 use crate::configuration::Configuration;
+use crate::utils::{ArgumentSegments, Tokenizer};
 use crate::{DefaultData, DefaultError};
 
 /// The final context type.
@@ -29,21 +41,80 @@ use crate::{DefaultData, DefaultError};
 #[non_exhaustive]
 pub struct Context<D = DefaultData, E = DefaultError> {
     /// User data.
-    pub data: Arc<D>,
+    pub data: Arc<RwLock<D>>,
     /// Framework configuration.
+    ///
+    /// For read-only access, prefer [`config`] or the methods returning owned
+    /// data, such as [`prefixes`] and [`command_names`].
+    ///
+    /// [`config`]: Self::config
+    /// [`prefixes`]: Self::prefixes
+    /// [`command_names`]: Self::command_names
     pub conf: Arc<RwLock<Configuration<D, E>>>,
     /// Serenity's context type.
     pub serenity_ctx: SerenityContext,
     /// The identifier of the command.
     pub command_id: CommandId,
+    /// The name of the command.
+    ///
+    /// This is the [primary name] of the command, unless the command is the
+    /// [unknown command], in which case it is the name that did not match any command.
+    ///
+    /// [primary name]: crate::command::Command::primary_name
+    /// [unknown command]: crate::configuration::Configuration::unknown_command
+    pub command_name: String,
     /// The [prefix] that was used to invoke this command.
     ///
     /// [prefix]: crate::parse::content
     pub prefix: String,
+    /// The byte offset in the content of the message at which the content after the
+    /// [prefix] begins.
+    ///
+    /// This is the position of the name of the command, after the prefix and any
+    /// whitespace following it. It allows slicing the original content, which is not
+    /// possible with the length of the prefix if the prefix is a mention, or if
+    /// [leading characters were ignored][trim].
+    ///
+    /// The offset is always into the content of the message, even if a
+    /// [content preprocessor][pre] is registered. If the preprocessor changed the content
+    /// after the prefix, so that it is no longer the end of the message, this is the
+    /// length of the content of the message.
+    ///
+    /// This is `0` if the command was [invoked by its name][invoke].
+    ///
+    /// [prefix]: Self::prefix
+    /// [trim]: crate::configuration::Configuration::trim_leading
+    /// [pre]: crate::configuration::Configuration::content_preprocessor
+    /// [invoke]: crate::Framework::invoke
+    pub content_offset: usize,
     /// The arguments of the command.
     ///
-    /// This is the content of the message after the command.
+    /// This is the content of the message after the command. Its casing is
+    /// preserved regardless of the [`case_insensitive`] option.
+    ///
+    /// [`case_insensitive`]: crate::configuration::Configuration::case_insensitive
     pub args: String,
+    /// The string that separates the arguments of the command.
+    ///
+    /// This is the delimiter chosen by the [`dynamic_delimiter`] hook, or the
+    /// [delimiter of the command][delimiter] otherwise.
+    ///
+    /// [`dynamic_delimiter`]: crate::configuration::Configuration::dynamic_delimiter
+    /// [delimiter]: crate::command::Command::delimiter
+    pub delimiter: String,
+    /// The tokenizer that splits the arguments of the command into segments.
+    ///
+    /// This is the [tokenizer of the command][tokenizer].
+    ///
+    /// [tokenizer]: crate::command::Command::tokenizer
+    pub tokenizer: &'static dyn Tokenizer,
+    /// A boolean indicating whether arguments that start with a quotation mark must
+    /// terminate it.
+    ///
+    /// This is the [`strict_quotes`] option of the configuration.
+    ///
+    /// [`strict_quotes`]: crate::configuration::Configuration::strict_quotes
+    pub strict_quotes: bool,
 }
 
 impl<D, E> Clone for Context<D, E> {
@@ -53,9 +124,299 @@ impl<D, E> Clone for Context<D, E> {
             conf: Arc::clone(&self.conf),
             serenity_ctx: self.serenity_ctx.clone(),
             command_id: self.command_id,
+            command_name: self.command_name.clone(),
             prefix: self.prefix.clone(),
+            content_offset: self.content_offset,
             args: self.args.clone(),
+            delimiter: self.delimiter.clone(),
+            tokenizer: self.tokenizer,
+            strict_quotes: self.strict_quotes,
+        }
+    }
+}
+
+impl<D, E> Context<D, E> {
+    /// Acquires read access to the framework configuration.
+    ///
+    /// # Notes
+    ///
+    /// The configuration cannot be modified while the returned guard is alive.
+    /// Holding the guard across an `.await` may deadlock the bot, as a task waiting
+    /// to write to the configuration blocks any new readers, including the framework
+    /// itself when it dispatches the next message. Drop the guard before awaiting,
+    /// or use the methods that return owned data instead.
+    pub async fn config(&self) -> RwLockReadGuard<'_, Configuration<D, E>> {
+        self.conf.read().await
+    }
+
+    /// Returns a copy of the static [prefixes] of the configuration, followed by
+    /// the [lazily evaluated prefixes][lazy].
+    ///
+    /// The configuration is only locked for the duration of the copy.
+    ///
+    /// [prefixes]: crate::configuration::Configuration::prefixes
+    /// [lazy]: crate::configuration::Configuration::prefix_fn
+    pub async fn prefixes(&self) -> Vec<String> {
+        let conf = self.conf.read().await;
+
+        conf.prefixes.iter().chain(conf.lazy_prefixes()).cloned().collect()
+    }
+
+    /// Returns the segments of the [arguments][args], separated by the [delimiter]
+    /// and split by the [tokenizer].
+    ///
+    /// [args]: Self::args
+    /// [delimiter]: Self::delimiter
+    /// [tokenizer]: Self::tokenizer
+    pub fn arguments(&self) -> ArgumentSegments<'_> {
+        ArgumentSegments::with_tokenizer(&self.args, &self.delimiter, self.tokenizer)
+    }
+
+    /// Returns a boolean indicating whether the author of the message is one of the
+    /// [owners] of the bot.
+    ///
+    /// The configuration is only locked for the duration of the lookup.
+    ///
+    /// [owners]: crate::configuration::Configuration::owners
+    pub async fn author_is_owner(&self, msg: &Message) -> bool {
+        self.conf.read().await.owners.contains(&msg.author.id)
+    }
+
+    /// Returns the identifier of the shard that received the message.
+    pub fn shard_id(&self) -> u64 {
+        self.serenity_ctx.shard_id
+    }
+
+    /// Returns the latency of the shard that received the message, which is the
+    /// duration between the last heartbeat and its acknowledgement.
+    ///
+    /// The latency is retrieved from the [`ShardManager`] of the client, which is
+    /// not part of the context. It is commonly stored in the [data] of Serenity's
+    /// context to access it from commands.
+    ///
+    /// Returns `None` if the manager does not run the shard, or if no heartbeat has
+    /// been acknowledged yet.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use std::sync::Arc;
+    ///
+    /// use serenity::client::bridge::gateway::ShardManager;
+    /// use serenity::model::channel::Message;
+    /// use serenity::prelude::{Mutex, TypeMapKey};
+    /// use serenity_framework::prelude::*;
+    ///
+    /// struct ShardManagerContainer;
+    ///
+    /// impl TypeMapKey for ShardManagerContainer {
+    ///     type Value = Arc<Mutex<ShardManager>>;
+    /// }
+    ///
+    /// #[command]
+    /// async fn ping(ctx: FrameworkContext, msg: &Message) -> CommandResult {
+    ///     let manager = ctx.serenity_ctx.data.read().await.get::<ShardManagerContainer>().cloned();
+    ///
+    ///     let latency = match manager {
+    ///         Some(manager) => ctx.shard_latency(&*manager.lock().await).await,
+    ///         None => None,
+    ///     };
+    ///
+    ///     let content = match latency {
+    ///         Some(latency) => {
+    ///             format!("Pong! ({} ms on shard {})", latency.as_millis(), ctx.shard_id())
+    ///         },
+    ///         None => format!("Pong! (shard {})", ctx.shard_id()),
+    ///     };
+    ///
+    ///     msg.channel_id.say(&ctx, content).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [data]: serenity::client::Context::data
+    pub async fn shard_latency(&self, manager: &ShardManager) -> Option<Duration> {
+        let runners = manager.runners.lock().await;
+
+        runners.get(&ShardId(self.serenity_ctx.shard_id)).and_then(|runner| runner.latency)
+    }
+
+    /// Starts showing the typing indicator in a channel.
+    ///
+    /// The indicator is shown until the returned [`Typing`] is stopped or dropped.
+    /// To show it for every command, enable [`auto_typing`] instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request to start typing fails.
+    ///
+    /// [`Typing`]: serenity::http::typing::Typing
+    /// [`auto_typing`]: crate::configuration::Configuration::auto_typing
+    pub fn start_typing(&self, channel: ChannelId) -> serenity::Result<Typing> {
+        channel.start_typing(&self.serenity_ctx.http)
+    }
+
+    /// Deletes the message that invoked the command.
+    ///
+    /// To delete the message of every successful command, enable
+    /// [`delete_command_messages`] instead.
+    ///
+    /// # Notes
+    ///
+    /// Deleting messages of other users requires the bot to have the
+    /// `MANAGE_MESSAGES` permission in the channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request to delete the message fails.
+    ///
+    /// [`delete_command_messages`]: crate::configuration::Configuration::delete_command_messages
+    pub async fn delete_invocation(&self, msg: &Message) -> serenity::Result<()> {
+        msg.delete(&self.serenity_ctx).await
+    }
+
+    /// Returns a copy of the names of all registered commands, in alphabetical order.
+    ///
+    /// The configuration is only locked for the duration of the copy.
+    pub async fn command_names(&self) -> Vec<String> {
+        let mut names = self.conf.read().await.commands.iter_names().cloned().collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    /// Sends an embed to a channel.
+    ///
+    /// The embed is built by the closure `f`, as in [`CreateMessage::embed`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if sending the message fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use serenity::model::channel::Message;
+    /// use serenity_framework::prelude::*;
+    ///
+    /// #[command]
+    /// async fn about(ctx: FrameworkContext, msg: &Message) -> CommandResult {
+    ///     ctx.send_embed(msg.channel_id, |e| {
+    ///         e.title("About");
+    ///         e.description("A bot built with the Serenity framework.")
+    ///     })
+    ///     .await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [`CreateMessage::embed`]: serenity::builder::CreateMessage::embed
+    pub async fn send_embed<F>(&self, channel: ChannelId, f: F) -> serenity::Result<Message>
+    where
+        F: FnOnce(&mut CreateEmbed) -> &mut CreateEmbed,
+    {
+        channel.send_message(&self.serenity_ctx.http, |m| m.embed(f)).await
+    }
+
+    /// Sends an embed to the channel of the message, as a reply to the message.
+    ///
+    /// Refer to [`send_embed`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if sending the message fails.
+    ///
+    /// [`send_embed`]: Self::send_embed
+    pub async fn reply_embed<F>(&self, msg: &Message, f: F) -> serenity::Result<Message>
+    where
+        F: FnOnce(&mut CreateEmbed) -> &mut CreateEmbed,
+    {
+        msg.channel_id
+            .send_message(&self.serenity_ctx.http, |m| m.reference_message(msg).embed(f))
+            .await
+    }
+
+    /// Sends files to a channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be read, or if sending the message fails.
+    pub async fn send_files<'a, T, It>(
+        &self,
+        channel: ChannelId,
+        files: It,
+    ) -> serenity::Result<Message>
+    where
+        T: Into<AttachmentType<'a>>,
+        It: IntoIterator<Item = T>,
+    {
+        channel.send_files(&self.serenity_ctx.http, files, |m| m).await
+    }
+}
+
+#[cfg(feature = "collector")]
+impl<D, E> Context<D, E>
+where
+    D: Send + Sync,
+    E: Send + Sync,
+{
+    /// Asks the author of the message to confirm an action.
+    ///
+    /// The `prompt` is sent to the channel of the message, and ✅ and ❌ reactions are
+    /// added to it. This waits until the author reacts with either of them, or until the
+    /// `timeout` elapses. Afterwards, the reactions of the bot are removed.
+    ///
+    /// Returns `Ok(true)` if the author reacted with ✅, and `Ok(false)` if the author
+    /// reacted with ❌ or did not react in time.
+    ///
+    /// This method is only available with the `collector` feature, which enables
+    /// the `collector` feature of Serenity.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if sending the prompt, or adding or removing the reactions fails.
+    pub async fn confirm(
+        &self,
+        msg: &Message,
+        prompt: &str,
+        timeout: Duration,
+    ) -> serenity::Result<bool> {
+        let yes = ReactionType::Unicode("✅".to_string());
+        let no = ReactionType::Unicode("❌".to_string());
+
+        let prompt = msg.channel_id.say(&self.serenity_ctx.http, prompt).await?;
+
+        let choices = [yes.clone(), no.clone()];
+
+        // Collect reactions before adding the choices, so that a reaction of the author
+        // made while they are being added is not missed.
+        let mut collector = prompt
+            .await_reactions(&self.serenity_ctx)
+            .author_id(msg.author.id)
+            .timeout(timeout)
+            .collect_limit(1)
+            .filter(move |reaction| choices.contains(&reaction.emoji))
+            .await;
+
+        prompt.react(self, yes.clone()).await?;
+        prompt.react(self, no.clone()).await?;
+
+        let reaction = collector.next().await;
+
+        let confirmed = match reaction {
+            Some(action) => action.as_inner_ref().emoji == yes,
+            None => false,
+        };
+
+        for emoji in [yes, no].iter() {
+            prompt
+                .channel_id
+                .delete_reaction(&self.serenity_ctx.http, prompt.id, None, emoji.clone())
+                .await?;
         }
+
+        Ok(confirmed)
     }
 }
 
@@ -93,7 +454,7 @@ where
 #[non_exhaustive]
 pub struct PrefixContext<'a, D = DefaultData, E = DefaultError> {
     /// User data.
-    pub data: &'a Arc<D>,
+    pub data: &'a Arc<RwLock<D>>,
     /// Framework configuration.
     pub conf: &'a Configuration<D, E>,
     /// Serenity's context type.
@@ -136,6 +497,54 @@ where
     }
 }
 
+/// The normal message context.
+///
+/// This is passed to the [normal message hook][hook]. As the message does not
+/// invoke a command, it only contains data that is independent of commands.
+///
+/// [hook]: crate::configuration::Configuration::normal_message
+#[non_exhaustive]
+pub struct NormalMessageContext<'a, D = DefaultData> {
+    /// User data.
+    pub data: &'a Arc<RwLock<D>>,
+    /// Serenity's context type.
+    pub serenity_ctx: &'a SerenityContext,
+}
+
+impl<'a, D> Clone for NormalMessageContext<'a, D> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data,
+            serenity_ctx: self.serenity_ctx,
+        }
+    }
+}
+
+impl<D> AsRef<Http> for NormalMessageContext<'_, D> {
+    fn as_ref(&self) -> &Http {
+        &self.serenity_ctx.http
+    }
+}
+
+impl<D> AsRef<Cache> for NormalMessageContext<'_, D> {
+    fn as_ref(&self) -> &Cache {
+        &self.serenity_ctx.cache
+    }
+}
+
+impl<D> CacheHttp for NormalMessageContext<'_, D>
+where
+    D: Send + Sync,
+{
+    fn http(&self) -> &Http {
+        &self.serenity_ctx.http
+    }
+
+    fn cache(&self) -> Option<&Arc<Cache>> {
+        Some(&self.serenity_ctx.cache)
+    }
+}
+
 /// The check context.
 ///
 /// This is passed to the [check function][fn].
@@ -144,13 +553,24 @@ where
 #[non_exhaustive]
 pub struct CheckContext<'a, D = DefaultData, E = DefaultError> {
     /// User data.
-    pub data: &'a Arc<D>,
+    pub data: &'a Arc<RwLock<D>>,
     /// Framework configuration.
     pub conf: &'a Configuration<D, E>,
     /// Serenity's context type.
     pub serenity_ctx: &'a SerenityContext,
     /// The identifier of the command that is being checked upon.
     pub command_id: CommandId,
+    /// The [prefix] that was used to invoke the command.
+    ///
+    /// [prefix]: crate::parse::content
+    pub prefix: &'a str,
+    /// The arguments of the command.
+    ///
+    /// This is the raw content of the message after the command. Checks that depend
+    /// on arguments may parse them on their own, such as by using [`ArgumentSegments`].
+    ///
+    /// [`ArgumentSegments`]: crate::utils::ArgumentSegments
+    pub args: &'a str,
 }
 
 impl<'a, D, E> Clone for CheckContext<'a, D, E> {
@@ -160,6 +580,8 @@ impl<'a, D, E> Clone for CheckContext<'a, D, E> {
             conf: self.conf,
             serenity_ctx: self.serenity_ctx,
             command_id: self.command_id,
+            prefix: self.prefix,
+            args: self.args,
         }
     }
 }
@@ -189,3 +611,27 @@ where
         Some(&self.serenity_ctx.cache)
     }
 }
+
+/// The parse context.
+///
+/// This is passed to the [argument parsing functions][args]. It wraps the message
+/// that contains the arguments, so that parsing functions may receive further
+/// information about where the arguments are parsed without changing their signature.
+///
+/// It is constructed from a message with [`From`].
+///
+/// [args]: crate::argument
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct ParseContext<'a> {
+    /// The message that contains the arguments.
+    pub msg: &'a Message,
+}
+
+impl<'a> From<&'a Message> for ParseContext<'a> {
+    fn from(msg: &'a Message) -> Self {
+        Self {
+            msg,
+        }
+    }
+}
@@ -3,12 +3,32 @@
 use std::error::Error as StdError;
 use std::fmt;
 
+use serenity::model::permissions::Permissions;
+
 use crate::check::Reason;
+use crate::command::CommandId;
+use crate::DefaultError;
 
 /// An error describing why [`dispatch`]ing failed.
 ///
+/// # Examples
+///
+/// Errors can be compared, including the [reason][Reason] of a failed check:
+///
+/// ```rust
+/// use serenity_framework::check::Reason;
+/// use serenity_framework::error::DispatchError;
+///
+/// let err = DispatchError::InvalidCommandName("x".to_string());
+/// assert_eq!(err, DispatchError::InvalidCommandName("x".into()));
+///
+/// let err = DispatchError::CheckFailed("admin".to_string(), Reason::User("no".to_string()));
+/// assert_eq!(err, DispatchError::CheckFailed("admin".into(), Reason::User("no".into())));
+/// assert_ne!(err, DispatchError::CheckFailed("admin".into(), Reason::Log("no".into())));
+/// ```
+///
 /// [`dispatch`]: crate::Framework::dispatch
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DispatchError {
     /// The message does not contain a command invocation.
     NormalMessage,
@@ -18,6 +38,78 @@ pub enum DispatchError {
     InvalidCommandName(String),
     /// A check failed. Contains its name and the reasoning why it failed.
     CheckFailed(String, Reason),
+    /// The bot lacks permissions required by the command. Contains the missing permissions.
+    BotMissingPermissions(Permissions),
+    /// The command can only be run in NSFW channels.
+    NsfwRequired,
+    /// The bot cannot send messages in the channel of the message.
+    ///
+    /// Refer to [`Configuration::require_send_permission`].
+    ///
+    /// [`Configuration::require_send_permission`]: crate::configuration::Configuration::require_send_permission
+    CannotRespond,
+    /// The [before hook][hook] aborted the command.
+    ///
+    /// [hook]: crate::configuration::Configuration::before
+    Aborted,
+    /// The [before hook][hook] redirected to a command that does not exist.
+    /// Contains the identifier of the command.
+    ///
+    /// [hook]: crate::configuration::Configuration::before
+    InvalidCommandId(CommandId),
+    /// The [before hook][hook] redirected more than [`MAX_REDIRECTS`] times.
+    ///
+    /// [hook]: crate::configuration::Configuration::before
+    /// [`MAX_REDIRECTS`]: crate::MAX_REDIRECTS
+    TooManyRedirects,
+    /// An argument of the command failed to parse.
+    Argument {
+        /// The name of the argument.
+        name: String,
+        /// The zero-based position of the argument among the command's arguments.
+        position: usize,
+        /// The reason why the argument failed.
+        kind: ArgumentErrorKind,
+    },
+    /// Several arguments of the command failed to parse. Contains every failure, in
+    /// the order of the arguments.
+    ///
+    /// This is only returned by commands marked with `#[command(collect_errors)]`,
+    /// instead of [`DispatchError::Argument`].
+    ArgumentErrors(Vec<ArgumentFailure>),
+    /// The command received more arguments than it accepts.
+    TooManyArguments,
+    /// An argument of the command starts with a quotation mark that is never terminated.
+    ///
+    /// Refer to [`Configuration::strict_quotes`].
+    ///
+    /// [`Configuration::strict_quotes`]: crate::configuration::Configuration::strict_quotes
+    UnterminatedQuote,
+    /// The author of the message recently invoked a command with the same content.
+    ///
+    /// Refer to [`Configuration::dedupe_window`].
+    ///
+    /// [`Configuration::dedupe_window`]: crate::configuration::Configuration::dedupe_window
+    DuplicateInvocation,
+    /// The bot is in maintenance mode, and the command is not available in it.
+    ///
+    /// Refer to [`Configuration::maintenance`].
+    ///
+    /// [`Configuration::maintenance`]: crate::configuration::Configuration::maintenance
+    Maintenance,
+    /// The command is disabled for the message. Contains the identifier of the command.
+    ///
+    /// Refer to [`Configuration::command_enabled`].
+    ///
+    /// [`Configuration::command_enabled`]: crate::configuration::Configuration::command_enabled
+    DisabledInGuild(CommandId),
+    /// The author, the channel or the guild of the message is blocked from invoking
+    /// commands.
+    ///
+    /// Refer to [`Configuration::blocked_users`].
+    ///
+    /// [`Configuration::blocked_users`]: crate::configuration::Configuration::blocked_users
+    Blocked,
 }
 
 impl fmt::Display for DispatchError {
@@ -33,16 +125,165 @@ impl fmt::Display for DispatchError {
                 write!(f, "name \"{}\" does not refer to any command", name)
             },
             DispatchError::CheckFailed(name, _) => write!(f, "\"{}\" check failed", name),
+            DispatchError::BotMissingPermissions(permissions) => {
+                write!(f, "bot is missing permissions: {:?}", permissions)
+            },
+            DispatchError::NsfwRequired => write!(f, "command can only be run in NSFW channels"),
+            DispatchError::CannotRespond => write!(f, "bot cannot send messages in the channel"),
+            DispatchError::Aborted => write!(f, "command was aborted"),
+            DispatchError::InvalidCommandId(id) => {
+                write!(f, "id {:?} does not refer to any command", id)
+            },
+            DispatchError::TooManyRedirects => write!(f, "command was redirected too many times"),
+            DispatchError::Argument {
+                name,
+                kind,
+                ..
+            } => fmt_argument_failure(f, name, kind),
+            DispatchError::ArgumentErrors(failures) => {
+                f.write_str("invalid arguments: ")?;
+
+                for (i, failure) in failures.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str("; ")?;
+                    }
+
+                    fmt::Display::fmt(failure, f)?;
+                }
+
+                Ok(())
+            },
+            DispatchError::TooManyArguments => write!(f, "too many arguments"),
+            DispatchError::UnterminatedQuote => write!(f, "unterminated quotation mark"),
+            DispatchError::DuplicateInvocation => write!(f, "command was invoked recently"),
+            DispatchError::Maintenance => write!(f, "bot is in maintenance mode"),
+            DispatchError::DisabledInGuild(id) => {
+                write!(f, "command {:?} is disabled in this guild", id)
+            },
+            DispatchError::Blocked => write!(f, "author, channel or guild is blocked"),
         }
     }
 }
 
 impl StdError for DispatchError {}
 
+fn fmt_argument_failure(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    kind: &ArgumentErrorKind,
+) -> fmt::Result {
+    match kind {
+        ArgumentErrorKind::Missing => write!(f, "missing required argument \"{}\"", name),
+        ArgumentErrorKind::Parse(err) => {
+            write!(f, "invalid value for argument \"{}\": {}", name, err)
+        },
+        ArgumentErrorKind::TooMany(max) => {
+            write!(f, "too many values for argument \"{}\" (at most {})", name, max)
+        },
+    }
+}
+
+/// An argument that failed to parse, one of several in [`DispatchError::ArgumentErrors`].
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_framework::error::{ArgumentErrorKind, ArgumentFailure, DispatchError};
+///
+/// let err = DispatchError::ArgumentErrors(vec![
+///     ArgumentFailure {
+///         name: "count".to_string(),
+///         position: 0,
+///         kind: ArgumentErrorKind::Parse("invalid digit found in string".to_string()),
+///     },
+///     ArgumentFailure {
+///         name: "user".to_string(),
+///         position: 1,
+///         kind: ArgumentErrorKind::Missing,
+///     },
+/// ]);
+///
+/// assert_eq!(
+///     err.to_string(),
+///     "invalid arguments: invalid value for argument \"count\": invalid digit found in \
+///      string; missing required argument \"user\""
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgumentFailure {
+    /// The name of the argument.
+    pub name: String,
+    /// The zero-based position of the argument among the command's arguments.
+    pub position: usize,
+    /// The reason why the argument failed.
+    pub kind: ArgumentErrorKind,
+}
+
+impl fmt::Display for ArgumentFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_argument_failure(f, &self.name, &self.kind)
+    }
+}
+
+/// The reason why an argument failed in [`DispatchError::Argument`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgumentErrorKind {
+    /// The argument is required, but was not provided.
+    Missing,
+    /// The argument failed to parse. Contains the description of the parsing error.
+    Parse(String),
+    /// The variadic argument received more elements than its maximum. Contains the maximum.
+    TooMany(usize),
+}
+
+/// An error describing an ambiguity in the [configuration][conf].
+///
+/// Refer to [`Configuration::validate`].
+///
+/// [conf]: crate::configuration::Configuration
+/// [`Configuration::validate`]: crate::configuration::Configuration::validate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// Two commands share a name. The name refers to `command`, as it overwrote
+    /// `overwritten`.
+    DuplicateName {
+        /// The shared name.
+        name: String,
+        /// The identifier of the command that the name refers to.
+        command: CommandId,
+        /// The identifier of the command that is no longer reachable by the name.
+        overwritten: CommandId,
+    },
+    /// A subcommand shares a name with a root level command.
+    ShadowedRootCommand {
+        /// The shared name.
+        name: String,
+        /// The identifier of the root level command.
+        root: CommandId,
+        /// The identifier of the subcommand.
+        subcommand: CommandId,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::DuplicateName {
+                name, ..
+            } => write!(f, "name \"{}\" belongs to multiple commands", name),
+            ConfigError::ShadowedRootCommand {
+                name, ..
+            } => write!(f, "subcommand name \"{}\" shadows a root level command", name),
+        }
+    }
+}
+
+impl StdError for ConfigError {}
+
 /// Returned when the call of [`dispatch`] fails.
 ///
 /// [`dispatch`]: crate::Framework::dispatch
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Error<E> {
     /// Failed to dispatch a command.
     Dispatch(DispatchError),
@@ -73,3 +314,79 @@ impl<E: StdError + 'static> StdError for Error<E> {
         }
     }
 }
+
+/// A conversion of a [`serenity::Error`] into the error type of commands.
+///
+/// The `?` operator converts errors with [`From`], which the framework cannot implement
+/// for every type implementing this trait. The [`from_serenity_error`] macro implements
+/// `From<serenity::Error>` for a type in terms of this trait, so that `?` may be used on
+/// the results of Serenity in commands returning that type.
+///
+/// This is implemented for the [default error type][DefaultError] and [`CommandError`].
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity::model::channel::Message;
+/// use serenity_framework::command::CommandResult;
+/// use serenity_framework::context::Context;
+/// use serenity_framework::error::FromSerenityError;
+/// use serenity_framework::from_serenity_error;
+/// use serenity_framework::prelude::command;
+///
+/// #[derive(Debug)]
+/// enum MyError {
+///     Discord(serenity::Error),
+///     NotFound,
+/// }
+///
+/// impl FromSerenityError for MyError {
+///     fn from_serenity_error(err: serenity::Error) -> Self {
+///         MyError::Discord(err)
+///     }
+/// }
+///
+/// from_serenity_error!(MyError);
+///
+/// #[command]
+/// async fn ping(ctx: Context<(), MyError>, msg: &Message) -> CommandResult<(), MyError> {
+///     msg.channel_id.say(&ctx.serenity_ctx.http, "pong").await?;
+///
+///     Err(MyError::NotFound)
+/// }
+/// ```
+///
+/// [`from_serenity_error`]: crate::from_serenity_error
+/// [DefaultError]: crate::DefaultError
+/// [`CommandError`]: crate::command::CommandError
+pub trait FromSerenityError {
+    /// Converts the error of Serenity.
+    fn from_serenity_error(err: serenity::Error) -> Self;
+}
+
+impl FromSerenityError for DefaultError {
+    fn from_serenity_error(err: serenity::Error) -> Self {
+        Box::new(err)
+    }
+}
+
+#[doc(hidden)]
+pub use serenity::Error as SerenityError;
+
+/// Implements `From<serenity::Error>` for types implementing [`FromSerenityError`].
+///
+/// Refer to [`FromSerenityError`] for an example.
+///
+/// [`FromSerenityError`]: crate::error::FromSerenityError
+#[macro_export]
+macro_rules! from_serenity_error {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl ::std::convert::From<$crate::error::SerenityError> for $ty {
+                fn from(err: $crate::error::SerenityError) -> Self {
+                    <$ty as $crate::error::FromSerenityError>::from_serenity_error(err)
+                }
+            }
+        )+
+    };
+}
@@ -0,0 +1,760 @@
+//! The core of the framework, which dispatches commands from messages.
+
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::Instant;
+
+use serenity::model::channel::Message;
+use serenity::model::id::UserId;
+use serenity::prelude::{Context as SerenityContext, RwLock};
+use tokio::sync::Mutex;
+
+use crate::check::Reason;
+use crate::command::{CommandFn, CommandId};
+use crate::configuration::{BeforeAction, Configuration};
+use crate::context::{CheckContext, Context, NormalMessageContext};
+use crate::error::{DispatchError, Error};
+use crate::utils::Segments;
+use crate::{parse, DefaultData, DefaultError};
+
+/// The maximum amount of times the [before hook][hook] may redirect to another command
+/// during the dispatch of a single message.
+///
+/// [hook]: crate::configuration::Configuration::before
+pub const MAX_REDIRECTS: usize = 8;
+
+/// The outcome of parsing a message: the context and function of the invoked command.
+type ParseResult<D, E> = Result<(Context<D, E>, CommandFn<D, E>), DispatchError>;
+
+/// The core of the framework.
+#[derive(Clone)]
+pub struct Framework<D = DefaultData, E = DefaultError> {
+    /// Configuration of the framework that dictates its behaviour.
+    pub conf: Arc<RwLock<Configuration<D, E>>>,
+    /// User data that is accessable in every command and function hook.
+    ///
+    /// The data is behind a lock, so that commands and hooks may mutate it.
+    pub data: Arc<RwLock<D>>,
+    /// Locks of users that are running a command, used if
+    /// [`serialize_per_user`] is enabled.
+    ///
+    /// [`serialize_per_user`]: crate::configuration::Configuration::serialize_per_user
+    user_locks: Arc<Mutex<HashMap<UserId, Arc<Mutex<()>>>>>,
+    /// Times of recent command invocations by their author and the hash of their
+    /// content, used if [`dedupe_window`] is set.
+    ///
+    /// [`dedupe_window`]: crate::configuration::Configuration::dedupe_window
+    recent_invocations: Arc<Mutex<RecentInvocations>>,
+}
+
+/// Times of recent command invocations by their author and the hash of their content.
+#[derive(Default)]
+struct RecentInvocations {
+    times: HashMap<(UserId, u64), Instant>,
+    /// The amount of records at which expired records are evicted next.
+    sweep_at: usize,
+}
+
+impl<D, E> Framework<D, E>
+where
+    D: Default,
+{
+    /// Creates a new instanstiation of the framework using a given configuration.
+    ///
+    /// The [`data`] field is [`Default`] initialized.
+    ///
+    /// [`data`]: Self::data
+    /// [`Default`]: std::default::Default
+    #[inline]
+    pub fn new(conf: Configuration<D, E>) -> Self {
+        Self::with_data(conf, D::default())
+    }
+}
+
+impl<D, E> Framework<D, E> {
+    /// Creates new instanstiation of the framework using a given configuration and data.
+    ///
+    /// # Notes
+    ///
+    /// This consumes the data.
+    ///
+    /// If you need to retain ownership of the data, consider using [`with_arc_data`].
+    ///
+    /// [`with_arc_data`]: Self::with_arc_data
+    #[inline]
+    pub fn with_data(conf: Configuration<D, E>, data: D) -> Self {
+        Self::with_arc_data(conf, Arc::new(RwLock::new(data)))
+    }
+
+    /// Creates new instanstiation of the framework using a given configuration and data.
+    ///
+    /// The data is shared with the caller, who may read or mutate it alongside
+    /// the framework.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    ///
+    /// use serenity::futures::executor::block_on;
+    /// use serenity::prelude::RwLock;
+    /// use serenity_framework::prelude::*;
+    ///
+    /// let data = Arc::new(RwLock::new(0u64));
+    ///
+    /// let framework: Framework<u64> =
+    ///     Framework::with_arc_data(Configuration::new(), Arc::clone(&data));
+    ///
+    /// *block_on(framework.data.write()) += 1;
+    ///
+    /// assert_eq!(*block_on(data.read()), 1);
+    /// ```
+    #[inline]
+    pub fn with_arc_data(conf: Configuration<D, E>, data: Arc<RwLock<D>>) -> Self {
+        Self {
+            conf: Arc::new(RwLock::new(conf)),
+            data,
+            user_locks: Arc::default(),
+            recent_invocations: Arc::default(),
+        }
+    }
+
+    /// Dispatches a command from a message if one is present.
+    ///
+    /// If the message does not contain a command invocation, and the
+    /// [normal message hook][hook] is set, the hook is called instead.
+    /// If the first name in the invocation does not belong to any command, and the
+    /// [unknown command][unknown] is set, the unknown command is invoked instead.
+    /// If [`delete_command_messages`] is enabled, the message is deleted after the
+    /// command succeeds. If [`reply_check_failures`] is enabled, the reason of a failed
+    /// check is sent to the channel of the message. If [`react_on_result`] is enabled,
+    /// the message is reacted to with the result of the command.
+    ///
+    /// # Errors
+    ///
+    /// - If the message does not contain a command invocation, and the normal message hook
+    ///   is not set, [`DispatchError::NormalMessage`] is returned.
+    /// - If the [`dedupe_window`] is set and the author of the message has invoked a
+    ///   command with the same content within it, [`DispatchError::DuplicateInvocation`]
+    ///   is returned.
+    /// - If the author, the channel or the guild of the message is [blocked][blocked],
+    ///   [`DispatchError::Blocked`] is returned.
+    ///
+    /// [hook]: crate::configuration::Configuration::normal_message
+    /// [unknown]: crate::configuration::Configuration::unknown_command
+    /// [blocked]: crate::configuration::Configuration::blocked_users
+    /// [`delete_command_messages`]: crate::configuration::Configuration::delete_command_messages
+    /// [`reply_check_failures`]: crate::configuration::Configuration::reply_check_failures
+    /// [`react_on_result`]: crate::configuration::Configuration::react_on_result
+    /// [`dedupe_window`]: crate::configuration::Configuration::dedupe_window
+    #[inline]
+    pub async fn dispatch(&self, ctx: &SerenityContext, msg: &Message) -> Result<(), Error<E>> {
+        let parsed = self.parse(ctx, msg).await;
+
+        self.dispatch_parsed(ctx, msg, parsed).await
+    }
+
+    /// Dispatches commands from a batch of messages, such as when processing
+    /// the history of a channel.
+    ///
+    /// The configuration is locked once for parsing all of the messages, and the lock is
+    /// released before any of the commands are invoked. Consequently, changes made to the
+    /// configuration by a command in the batch are not visible to the rest of the batch.
+    ///
+    /// Commands are invoked sequentially in the order of the messages. Each message
+    /// produces a result at the same index in the returned list, regardless of whether
+    /// dispatching any other message failed. Apart from parsing, every message is handled
+    /// as in [`dispatch`].
+    ///
+    /// [`dispatch`]: Self::dispatch
+    pub async fn dispatch_many<I>(
+        &self,
+        ctx: &SerenityContext,
+        msgs: I,
+    ) -> Vec<Result<(), Error<E>>>
+    where
+        I: IntoIterator<Item = Message>,
+    {
+        let msgs = msgs.into_iter().collect::<Vec<_>>();
+
+        let parsed = {
+            let conf = self.conf.read().await;
+
+            let mut parsed = Vec::with_capacity(msgs.len());
+
+            for msg in &msgs {
+                parsed.push(self.parse_with(&conf, ctx, msg).await);
+            }
+
+            parsed
+        };
+
+        let mut results = Vec::with_capacity(msgs.len());
+
+        for (msg, parsed) in msgs.iter().zip(parsed) {
+            results.push(self.dispatch_parsed(ctx, msg, parsed).await);
+        }
+
+        results
+    }
+
+    /// Runs the command parsed from the message, or handles the failure to parse one.
+    ///
+    /// Refer to [`dispatch`] for the steps taken.
+    ///
+    /// [`dispatch`]: Self::dispatch
+    async fn dispatch_parsed(
+        &self,
+        ctx: &SerenityContext,
+        msg: &Message,
+        parsed: ParseResult<D, E>,
+    ) -> Result<(), Error<E>> {
+        let (command_ctx, func) = match parsed {
+            Ok(pair) => pair,
+            Err(DispatchError::NormalMessage) => return self.normal_message(ctx, msg).await,
+            Err(err) => {
+                self.reply_check_failure(ctx, msg, &err).await;
+                return Err(Error::Dispatch(err));
+            },
+        };
+
+        self.dedupe(msg).await?;
+
+        let result = self.run(command_ctx, func, msg).await;
+
+        self.react_to_result(ctx, msg, &result).await;
+
+        match &result {
+            Ok(()) => self.delete_invocation(ctx, msg).await,
+            Err(Error::Dispatch(err)) => self.reply_check_failure(ctx, msg, err).await,
+            Err(_) => {},
+        }
+
+        result
+    }
+
+    /// Invokes a command by its name, bypassing parsing of the prefix and the command.
+    ///
+    /// The name is looked up in the [command map][map], respecting the
+    /// [`case_insensitive`] option. The command is verified as if it were parsed from the
+    /// message, including the [global checks][global] and the [checks] of the command. The
+    /// [`Context::prefix`] of the command is empty, and the [`Context::args`] are the
+    /// provided `args`.
+    ///
+    /// The [before hook][hook] is not called.
+    ///
+    /// This is useful for invoking commands from scheduled tasks or from other commands.
+    ///
+    /// # Errors
+    ///
+    /// - If the name does not belong to any command, [`DispatchError::InvalidCommandName`]
+    ///   is returned.
+    /// - If the author, channel or guild of the message is [blocked][blocked],
+    ///   [`DispatchError::Blocked`] is returned.
+    /// - If the command may not be run in [maintenance mode][maintenance],
+    ///   [`DispatchError::Maintenance`] is returned.
+    /// - If the command is not [enabled][enabled] for the message,
+    ///   [`DispatchError::DisabledInGuild`] is returned.
+    /// - If the bot is missing permissions or the command is NSFW outside of an NSFW
+    ///   channel, the error of [`parse::verify`] is returned.
+    /// - If a check fails, [`DispatchError::CheckFailed`] is returned.
+    /// - If the command fails, its error is returned.
+    ///
+    /// [map]: crate::configuration::Configuration::commands
+    /// [blocked]: crate::parse::is_blocked
+    /// [maintenance]: crate::parse::maintenance
+    /// [enabled]: crate::configuration::Configuration::command_enabled
+    /// [`case_insensitive`]: crate::configuration::Configuration::case_insensitive
+    /// [global]: crate::configuration::Configuration::global_checks
+    /// [checks]: crate::command::Command::checks
+    /// [hook]: crate::configuration::Configuration::before
+    pub async fn invoke(
+        &self,
+        ctx: &SerenityContext,
+        msg: &Message,
+        name: &str,
+        args: &str,
+    ) -> Result<(), Error<E>> {
+        self.invoke_with(ctx, msg, name, args, true).await
+    }
+
+    /// Invokes a command by its name without running any checks.
+    ///
+    /// Refer to [`invoke`] for more information.
+    ///
+    /// [`invoke`]: Self::invoke
+    pub async fn invoke_unchecked(
+        &self,
+        ctx: &SerenityContext,
+        msg: &Message,
+        name: &str,
+        args: &str,
+    ) -> Result<(), Error<E>> {
+        self.invoke_with(ctx, msg, name, args, false).await
+    }
+
+    async fn invoke_with(
+        &self,
+        ctx: &SerenityContext,
+        msg: &Message,
+        name: &str,
+        args: &str,
+        checked: bool,
+    ) -> Result<(), Error<E>> {
+        let (command_id, command_name, delimiter, tokenizer, strict_quotes, func) = {
+            let conf = self.conf.read().await;
+
+            let name = if conf.case_insensitive { name.to_lowercase() } else { name.to_string() };
+
+            let command = match conf.commands.get_by_name(&name) {
+                Some(command) => command,
+                None => return Err(Error::Dispatch(DispatchError::InvalidCommandName(name))),
+            };
+
+            if checked {
+                parse::is_blocked(&conf, msg).await?;
+                parse::verify(&self.data, &conf, ctx, msg, command, true, "", args).await?;
+            }
+
+            let delimiter = conf.delimiter(command, msg, args);
+
+            let name = command.primary_name().to_string();
+
+            (command.id, name, delimiter, command.tokenizer, conf.strict_quotes, command.function)
+        };
+
+        let ctx = Context {
+            data: Arc::clone(&self.data),
+            conf: Arc::clone(&self.conf),
+            serenity_ctx: ctx.clone(),
+            command_id,
+            command_name,
+            prefix: String::new(),
+            content_offset: 0,
+            args: args.to_string(),
+            delimiter,
+            tokenizer,
+            strict_quotes,
+        };
+
+        self.call(ctx, func, msg).await
+    }
+
+    /// Calls the [before hook][hook], if one is registered, and invokes the command.
+    ///
+    /// The configuration is not locked while either the hook or the command run.
+    ///
+    /// [hook]: crate::configuration::Configuration::before
+    async fn run(
+        &self,
+        mut ctx: Context<D, E>,
+        mut func: CommandFn<D, E>,
+        msg: &Message,
+    ) -> Result<(), Error<E>> {
+        let before = self.conf.read().await.before;
+
+        let before = match before {
+            Some(before) => before,
+            None => return self.call(ctx, func, msg).await,
+        };
+
+        for _ in 0..=MAX_REDIRECTS {
+            let (command_id, args) = match before(&ctx, msg).await {
+                BeforeAction::Proceed => return self.call(ctx, func, msg).await,
+                BeforeAction::Abort => return Err(Error::Dispatch(DispatchError::Aborted)),
+                BeforeAction::Redirect {
+                    command,
+                    args,
+                } => (command, args),
+            };
+
+            let conf = self.conf.read().await;
+
+            let command = match conf.commands.get(command_id) {
+                Some(command) => command,
+                None => return Err(Error::Dispatch(DispatchError::InvalidCommandId(command_id))),
+            };
+
+            parse::is_blocked(&conf, msg).await?;
+            parse::verify(
+                &self.data,
+                &conf,
+                &ctx.serenity_ctx,
+                msg,
+                command,
+                true,
+                &ctx.prefix,
+                &args,
+            )
+            .await?;
+
+            ctx.command_id = command_id;
+            ctx.command_name = command.primary_name().to_string();
+            ctx.delimiter = conf.delimiter(command, msg, &args);
+            ctx.tokenizer = command.tokenizer;
+            ctx.args = args;
+            func = command.function;
+        }
+
+        Err(Error::Dispatch(DispatchError::TooManyRedirects))
+    }
+
+    /// Calls the [normal message hook][hook], if one is registered.
+    ///
+    /// [hook]: crate::configuration::Configuration::normal_message
+    async fn normal_message(&self, ctx: &SerenityContext, msg: &Message) -> Result<(), Error<E>> {
+        let hook = match self.conf.read().await.normal_message {
+            Some(hook) => hook,
+            None => return Err(Error::Dispatch(DispatchError::NormalMessage)),
+        };
+
+        let ctx = NormalMessageContext {
+            data: &self.data,
+            serenity_ctx: ctx,
+        };
+
+        hook(&ctx, msg).await;
+
+        Ok(())
+    }
+
+    /// Deletes the message that invoked a command if [`delete_command_messages`] is enabled.
+    ///
+    /// Failing to delete the message is ignored, as the command has already succeeded.
+    ///
+    /// [`delete_command_messages`]: crate::configuration::Configuration::delete_command_messages
+    async fn delete_invocation(&self, ctx: &SerenityContext, msg: &Message) {
+        if self.conf.read().await.delete_command_messages {
+            let _ = msg.delete(ctx).await;
+        }
+    }
+
+    /// Reacts to the message that invoked a command with the result of the command
+    /// if [`react_on_result`] is enabled.
+    ///
+    /// Failing to add the reaction is ignored, as the result of the command is returned
+    /// regardless.
+    ///
+    /// [`react_on_result`]: crate::configuration::Configuration::react_on_result
+    async fn react_to_result(
+        &self,
+        ctx: &SerenityContext,
+        msg: &Message,
+        result: &Result<(), Error<E>>,
+    ) {
+        let reaction = self.conf.read().await.result_reaction(result).cloned();
+
+        if let Some(reaction) = reaction {
+            let _ = msg.react(ctx, reaction).await;
+        }
+    }
+
+    /// Sends the information for the user of a failed check to the channel of the message
+    /// if [`reply_check_failures`] is enabled.
+    ///
+    /// Failing to send the information is ignored, as the error of the check is returned
+    /// regardless.
+    ///
+    /// [`reply_check_failures`]: crate::configuration::Configuration::reply_check_failures
+    async fn reply_check_failure(&self, ctx: &SerenityContext, msg: &Message, err: &DispatchError) {
+        let user = match err {
+            DispatchError::CheckFailed(_, Reason::User(user))
+            | DispatchError::CheckFailed(
+                _,
+                Reason::UserAndLog {
+                    user, ..
+                },
+            ) => user,
+            _ => return,
+        };
+
+        if self.conf.read().await.reply_check_failures {
+            let _ = msg.channel_id.say(&ctx.http, user).await;
+        }
+    }
+
+    /// Records the invocation of a command by the message, returning an error if
+    /// an identical invocation was recorded within the [`dedupe_window`].
+    ///
+    /// Records older than the window are evicted once the amount of records has doubled
+    /// since they were last evicted, so that recording an invocation takes amortized
+    /// constant time.
+    ///
+    /// [`dedupe_window`]: crate::configuration::Configuration::dedupe_window
+    async fn dedupe(&self, msg: &Message) -> Result<(), DispatchError> {
+        let window = match self.conf.read().await.dedupe_window {
+            Some(window) => window,
+            None => return Ok(()),
+        };
+
+        let mut hasher = DefaultHasher::new();
+        msg.content.hash(&mut hasher);
+        let key = (msg.author.id, hasher.finish());
+
+        let now = Instant::now();
+        let mut recent = self.recent_invocations.lock().await;
+
+        if let Some(time) = recent.times.get(&key) {
+            if now.duration_since(*time) < window {
+                return Err(DispatchError::DuplicateInvocation);
+            }
+        }
+
+        recent.times.insert(key, now);
+
+        if recent.times.len() >= recent.sweep_at {
+            recent.times.retain(|_, time| now.duration_since(*time) < window);
+            recent.sweep_at = (recent.times.len() * 2).max(16);
+        }
+
+        Ok(())
+    }
+
+    /// Calls the command function, showing the typing indicator in the channel of
+    /// the message if [`auto_typing`] is enabled.
+    ///
+    /// [`auto_typing`]: crate::configuration::Configuration::auto_typing
+    async fn call(
+        &self,
+        ctx: Context<D, E>,
+        func: CommandFn<D, E>,
+        msg: &Message,
+    ) -> Result<(), Error<E>> {
+        let typing = if self.conf.read().await.auto_typing {
+            msg.channel_id.start_typing(&ctx.serenity_ctx.http).ok()
+        } else {
+            None
+        };
+
+        let result = self.call_serialized(ctx, func, msg).await;
+
+        if let Some(typing) = typing {
+            typing.stop();
+        }
+
+        result
+    }
+
+    /// Calls the command function, holding the lock of the author of the message
+    /// if [`serialize_per_user`] is enabled.
+    ///
+    /// [`serialize_per_user`]: crate::configuration::Configuration::serialize_per_user
+    async fn call_serialized(
+        &self,
+        ctx: Context<D, E>,
+        func: CommandFn<D, E>,
+        msg: &Message,
+    ) -> Result<(), Error<E>> {
+        if !self.conf.read().await.serialize_per_user {
+            return self.call_timed(ctx, func, msg).await;
+        }
+
+        let lock = {
+            let mut locks = self.user_locks.lock().await;
+            Arc::clone(locks.entry(msg.author.id).or_default())
+        };
+
+        let result = {
+            let _guard = lock.lock().await;
+            self.call_timed(ctx, func, msg).await
+        };
+
+        // Remove the lock if no other command of the user holds or awaits it,
+        // so that the map does not grow with every user that has ever run a command.
+        let mut locks = self.user_locks.lock().await;
+
+        if Arc::strong_count(&lock) == 2 {
+            locks.remove(&msg.author.id);
+        }
+
+        result
+    }
+
+    /// Calls the command function, passing its execution time to the [timing hook][hook],
+    /// if one is registered.
+    ///
+    /// [hook]: crate::configuration::Configuration::on_timing
+    async fn call_timed(
+        &self,
+        ctx: Context<D, E>,
+        func: CommandFn<D, E>,
+        msg: &Message,
+    ) -> Result<(), Error<E>> {
+        let on_timing = self.conf.read().await.on_timing;
+        let command_id = ctx.command_id;
+
+        let start = Instant::now();
+        let result = func(ctx, msg).await;
+
+        if let Some(on_timing) = on_timing {
+            on_timing(command_id, start.elapsed());
+        }
+
+        result
+    }
+
+    /// Returns the commands that the author of the message is able to run.
+    ///
+    /// A command is available if it is [available in help commands][help], and all of the
+    /// [global checks][global] and its [checks] pass for the message. Checks that do not
+    /// [apply in help commands][in_help] are not run. The prefix and the arguments provided to the checks are empty.
+    ///
+    /// The commands are ordered by their [help order][order].
+    ///
+    /// # Notes
+    ///
+    /// This runs the checks of every command, which may be expensive if checks make
+    /// requests to Discord. Any side effects of the checks occur as they would when
+    /// invoking the commands. The configuration is locked for reading until all checks
+    /// are completed.
+    ///
+    /// [help]: crate::command::Command::help_available
+    /// [global]: crate::configuration::Configuration::global_checks
+    /// [checks]: crate::command::Command::checks
+    /// [in_help]: crate::check::Check::check_in_help
+    /// [order]: crate::command::Command::help_order
+    pub async fn available_commands(&self, ctx: &SerenityContext, msg: &Message) -> Vec<CommandId> {
+        let conf = self.conf.read().await;
+
+        let mut commands = Vec::new();
+
+        for (id, command) in conf.commands.iter() {
+            if !command.help_available {
+                continue;
+            }
+
+            let check_ctx = CheckContext {
+                data: &self.data,
+                conf: &conf,
+                serenity_ctx: ctx,
+                command_id: *id,
+                prefix: "",
+                args: "",
+            };
+
+            let mut passed = true;
+
+            let checks = conf.global_checks.iter().chain(&command.checks);
+
+            for check in checks.filter(|c| c.check_in_help) {
+                if (check.function)(&check_ctx, msg).await.is_err() {
+                    passed = false;
+                    break;
+                }
+            }
+
+            if !passed {
+                continue;
+            }
+
+            commands.push((command.help_order(), *id));
+        }
+
+        commands.sort();
+        commands.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// Returns a boolean indicating whether the message invokes a command.
+    ///
+    /// This is the case if the message starts with a prefix, and the first name after
+    /// it belongs to a command. No checks are run and no command is invoked, so this is
+    /// cheap enough to be called on every message, such as in spam filters. Names
+    /// of subcommands and the [unknown command][unknown] are not considered.
+    ///
+    /// # Notes
+    ///
+    /// The [content preprocessor][pre] and hooks parsing the prefix, such as the
+    /// [dynamic prefix][dynamic], are still called.
+    ///
+    /// [unknown]: crate::configuration::Configuration::unknown_command
+    /// [pre]: crate::configuration::Configuration::content_preprocessor
+    /// [dynamic]: crate::configuration::Configuration::dynamic_prefix
+    pub async fn is_command(&self, ctx: &SerenityContext, msg: &Message) -> bool {
+        let conf = self.conf.read().await;
+
+        let text = match conf.content_preprocessor {
+            Some(preprocessor) => preprocessor(msg),
+            None => Cow::Borrowed(msg.content.as_str()),
+        };
+
+        let content = match parse::content(&self.data, &conf, ctx, msg, &text).await {
+            Some((_, content)) => content,
+            None => return false,
+        };
+
+        let mut segments = Segments::new(content, " ", conf.case_insensitive);
+
+        matches!(parse::commands(&conf, &mut segments).next(), Some(Ok(_)))
+    }
+
+    /// Parses a command out of a message, if one is present.
+    pub async fn parse(&self, ctx: &SerenityContext, msg: &Message) -> ParseResult<D, E> {
+        let conf = self.conf.read().await;
+
+        self.parse_with(&conf, ctx, msg).await
+    }
+
+    async fn parse_with(
+        &self,
+        conf: &Configuration<D, E>,
+        ctx: &SerenityContext,
+        msg: &Message,
+    ) -> ParseResult<D, E> {
+        let text = match conf.content_preprocessor {
+            Some(preprocessor) => preprocessor(msg),
+            None => Cow::Borrowed(msg.content.as_str()),
+        };
+
+        let (prefix, content) = match parse::content(&self.data, conf, &ctx, &msg, &text).await {
+            Some(pair) => pair,
+            None => return Err(DispatchError::NormalMessage),
+        };
+
+        // The content is the rest of the text after the prefix. The text may differ from
+        // the message if it was preprocessed, so the content is located in the message.
+        let content_offset = if msg.content.ends_with(content) {
+            msg.content.len() - content.len()
+        } else {
+            msg.content.len()
+        };
+
+        parse::is_blocked(conf, msg).await?;
+
+        let (command, name, args) =
+            match parse::command(&self.data, conf, &ctx, &msg, prefix, content).await {
+                Ok(Some((command, args))) => (command, command.primary_name().to_string(), args),
+                Ok(None) => return Err(DispatchError::PrefixOnly(prefix.to_string())),
+                Err(DispatchError::InvalidCommandName(name)) => match &conf.unknown_command {
+                    Some(command) => {
+                        parse::verify(&self.data, conf, ctx, msg, command, true, prefix, content)
+                            .await?;
+
+                        (command, name, content.to_string())
+                    },
+                    None => return Err(DispatchError::InvalidCommandName(name)),
+                },
+                Err(err) => return Err(err),
+            };
+
+        let delimiter = conf.delimiter(command, msg, &args);
+
+        let ctx = Context {
+            data: Arc::clone(&self.data),
+            conf: Arc::clone(&self.conf),
+            serenity_ctx: ctx.clone(),
+            command_id: command.id,
+            command_name: name,
+            prefix: prefix.to_string(),
+            content_offset,
+            args,
+            delimiter,
+            tokenizer: command.tokenizer,
+            strict_quotes: conf.strict_quotes,
+        };
+
+        Ok((ctx, command.function))
+    }
+}
@@ -37,26 +37,37 @@
 #![warn(missing_docs)]
 
 use std::error::Error as StdError;
-use std::sync::Arc;
-
-use serenity::model::channel::Message;
-use serenity::prelude::{Context as SerenityContext, RwLock};
 
+#[cfg(feature = "framework")]
 pub mod argument;
+#[cfg(feature = "framework")]
 pub mod category;
+#[cfg(feature = "framework")]
 pub mod check;
+#[cfg(feature = "framework")]
 pub mod command;
+#[cfg(feature = "framework")]
 pub mod configuration;
+#[cfg(feature = "framework")]
 pub mod context;
+#[cfg(feature = "framework")]
 pub mod error;
+#[cfg(feature = "framework")]
+mod framework;
+#[cfg(feature = "framework")]
 pub mod parse;
+#[cfg(feature = "framework")]
+pub mod prefix;
+#[cfg(feature = "framework")]
 pub mod prelude;
+#[cfg(feature = "slash")]
+pub mod slash;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 pub mod utils;
 
-use command::CommandFn;
-use configuration::Configuration;
-use context::Context;
-use error::{DispatchError, Error};
+#[cfg(feature = "framework")]
+pub use framework::{Framework, MAX_REDIRECTS};
 
 /// The default type for [user data][data] when it is unspecified.
 ///
@@ -67,96 +78,3 @@ pub type DefaultData = ();
 ///
 /// [errors]: crate::command::CommandResult
 pub type DefaultError = Box<dyn StdError + Send + Sync>;
-
-/// The core of the framework.
-#[derive(Clone)]
-pub struct Framework<D = DefaultData, E = DefaultError> {
-    /// Configuration of the framework that dictates its behaviour.
-    pub conf: Arc<RwLock<Configuration<D, E>>>,
-    /// User data that is accessable in every command and function hook.
-    pub data: Arc<D>,
-}
-
-impl<D, E> Framework<D, E>
-where
-    D: Default,
-{
-    /// Creates a new instanstiation of the framework using a given configuration.
-    ///
-    /// The [`data`] field is [`Default`] initialized.
-    ///
-    /// [`data`]: Self::data
-    /// [`Default`]: std::default::Default
-    #[inline]
-    pub fn new(conf: Configuration<D, E>) -> Self {
-        Self::with_data(conf, D::default())
-    }
-}
-
-impl<D, E> Framework<D, E> {
-    /// Creates new instanstiation of the framework using a given configuration and data.
-    ///
-    /// # Notes
-    ///
-    /// This consumes the data.
-    ///
-    /// If you need to retain ownership of the data, consider using [`with_arc_data`].
-    ///
-    /// [`with_arc_data`]: Self::with_arc_data
-    #[inline]
-    pub fn with_data(conf: Configuration<D, E>, data: D) -> Self {
-        Self::with_arc_data(conf, Arc::new(data))
-    }
-
-    /// Creates new instanstiation of the framework using a given configuration and data.
-    #[inline]
-    pub fn with_arc_data(conf: Configuration<D, E>, data: Arc<D>) -> Self {
-        Self {
-            conf: Arc::new(RwLock::new(conf)),
-            data,
-        }
-    }
-
-    /// Dispatches a command from a message if one is present.
-    #[inline]
-    pub async fn dispatch(&self, ctx: &SerenityContext, msg: &Message) -> Result<(), Error<E>> {
-        let (ctx, func) = self.parse(ctx, msg).await?;
-
-        func(ctx, msg).await.map_err(Error::User)
-    }
-
-    /// Parses a command out of a message, if one is present.
-    pub async fn parse(
-        &self,
-        ctx: &SerenityContext,
-        msg: &Message,
-    ) -> Result<(Context<D, E>, CommandFn<D, E>), DispatchError> {
-        let (func, command_id, prefix, args) = {
-            let conf = self.conf.read().await;
-
-            let (prefix, content) = match parse::content(&self.data, &conf, &ctx, &msg).await {
-                Some(pair) => pair,
-                None => return Err(DispatchError::NormalMessage),
-            };
-
-            let (command, args) =
-                match parse::command(&self.data, &conf, &ctx, &msg, content).await? {
-                    Some(pair) => pair,
-                    None => return Err(DispatchError::PrefixOnly(prefix.to_string())),
-                };
-
-            (command.function, command.id, prefix.to_string(), args)
-        };
-
-        let ctx = Context {
-            data: Arc::clone(&self.data),
-            conf: Arc::clone(&self.conf),
-            serenity_ctx: ctx.clone(),
-            command_id,
-            prefix,
-            args,
-        };
-
-        Ok((ctx, func))
-    }
-}
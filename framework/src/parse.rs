@@ -2,16 +2,21 @@
 //!
 //! Refer to the [`content`] function for the definition of a prefix.
 
+use std::borrow::Cow;
 use std::sync::Arc;
 
 use serenity::client::Context as SerenityContext;
-use serenity::model::channel::Message;
+use serenity::model::channel::{ChannelType, Message};
+use serenity::model::permissions::Permissions;
+use serenity::prelude::RwLock;
 
-use crate::command::Command;
+use crate::category::Category;
+use crate::check::Check;
+use crate::command::{Command, CommandId};
 use crate::configuration::Configuration;
 use crate::context::{CheckContext, PrefixContext};
 use crate::error::DispatchError;
-use crate::utils::Segments;
+use crate::utils::{self, Segments};
 
 /// Parses a mention from the message.
 ///
@@ -26,41 +31,121 @@ use crate::utils::Segments;
 ///
 /// Returns the mention and the rest of the message after the mention, with trimmed
 /// whitespace.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_framework::parse::mention;
+///
+/// let id = "110372470472613888";
+///
+/// assert_eq!(mention("<@110372470472613888> ping", id), Some(("110372470472613888>", "ping")));
+/// assert_eq!(mention("<@!110372470472613888> ping", id), Some(("110372470472613888>", "ping")));
+/// assert_eq!(mention("<@!!110372470472613888> ping", id), None);
+/// assert_eq!(mention("<@110372470472613888 ping", id), None);
+/// assert_eq!(mention("<@1> ping", id), None);
+/// ```
 pub fn mention<'a>(msg: &'a str, id: &str) -> Option<(&'a str, &'a str)> {
-    if !msg.starts_with("<@") {
+    let msg = msg.strip_prefix("<@")?;
+    let msg = msg.strip_prefix('!').unwrap_or(msg);
+
+    let index = msg.find('>')?;
+
+    if &msg[..index] != id {
         return None;
     }
 
-    let msg = msg[2..].trim_start_matches('!');
+    // + 1 to remove the angle bracket
+    let (mention, rest) = msg.split_at(index + 1);
+    Some((mention, rest.trim_start()))
+}
 
-    let index = msg.find('>').unwrap_or(0);
-    let mention = &msg[..index];
+/// Parses a textual mention of a name from the message.
+///
+/// A textual mention is an `@`, directly followed by the name, and then by whitespace
+/// or the end of the message. This is how a mention appears if the user did not
+/// select the bot from the autocompletion of their client.
+///
+/// Returns the mention and the rest of the message after the mention, with trimmed
+/// whitespace.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_framework::parse::name_mention;
+///
+/// assert_eq!(name_mention("@Bot ping", "Bot"), Some(("@Bot", "ping")));
+/// assert_eq!(name_mention("@Bot", "Bot"), Some(("@Bot", "")));
+/// assert_eq!(name_mention("@Botty ping", "Bot"), None);
+/// assert_eq!(name_mention("Bot ping", "Bot"), None);
+/// assert_eq!(name_mention("@ ping", ""), None);
+/// ```
+pub fn name_mention<'a>(msg: &'a str, name: &str) -> Option<(&'a str, &'a str)> {
+    if name.is_empty() {
+        return None;
+    }
 
-    if mention == id {
-        // + 1 to remove the angle bracket
-        let (mention, mut rest) = msg.split_at(index + 1);
-        rest = rest.trim_start();
-        Some((mention, rest))
-    } else {
-        None
+    let rest = msg.strip_prefix('@')?.strip_prefix(name)?;
+
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
     }
+
+    Some((&msg[..name.len() + 1], rest.trim_start()))
 }
 
-/// Parses a prefix from the message dynamically using the [`Configuration::dynamic_prefix`]
+/// Parses a [textual mention][name] of the bot from the message.
+///
+/// The names of the bot are its nickname in the guild of the message, and its username.
+/// The longer name is tried first.
+///
+/// # Notes
+///
+/// The names are retrieved from the cache. The nickname is only known if the member
+/// of the bot in the guild is cached, and the username is only known once the bot
+/// has connected to Discord.
+///
+/// [name]: name_mention
+pub async fn bot_name_mention<'a>(
+    ctx: &SerenityContext,
+    msg: &Message,
+    text: &'a str,
+) -> Option<(&'a str, &'a str)> {
+    let user = ctx.cache.current_user().await;
+
+    let nick = match msg.guild_id {
+        Some(guild_id) => ctx.cache.member(guild_id, user.id).await.and_then(|m| m.nick),
+        None => None,
+    };
+
+    let mut names = vec![user.name];
+    names.extend(nick);
+    names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+
+    names.iter().find_map(|name| name_mention(text, name))
+}
+
+/// Parses a prefix from the content dynamically using the [`Configuration::dynamic_prefix`]
 /// hook.
 ///
-/// If the hook is not registered, or the hook returned `None`, `None` is returned.
-/// Otherwise, the prefix and the rest of the message after the prefix is returned.
+/// If the hook is not registered, the hook returned `None`, or the index returned by the hook
+/// is not within the content, `None` is returned. Otherwise, the prefix and the rest of the content
+/// after the prefix is returned.
 ///
 /// [`Configuration::dynamic_prefix`]: crate::configuration::Configuration::dynamic_prefix
-#[allow(clippy::needless_lifetimes)]
 pub async fn dynamic_prefix<'a, D, E>(
     ctx: PrefixContext<'_, D, E>,
-    msg: &'a Message,
+    msg: &Message,
+    content: &'a str,
 ) -> Option<(&'a str, &'a str)> {
     if let Some(dynamic_prefix) = ctx.conf.dynamic_prefix {
         let index = dynamic_prefix(ctx, msg).await?;
-        Some(msg.content.split_at(index))
+
+        if content.is_char_boundary(index) {
+            Some(content.split_at(index))
+        } else {
+            None
+        }
     } else {
         None
     }
@@ -69,9 +154,68 @@ pub async fn dynamic_prefix<'a, D, E>(
 /// Parses a prefix from the message statically from a list of prefixes.
 ///
 /// If none of the prefixes stored in the list are found in the message, `None` is returned.
-/// Otherwise, the prefix and the rest of the message after the prefix is returned.
+/// Otherwise, the prefix and the rest of the message after the prefix is returned, with
+/// trimmed leading whitespace.
+///
+/// A prefix ending in an alphanumeric character must be followed by whitespace or the end
+/// of the message. This prevents a prefix such as `hey bot` from matching the beginning
+/// of a word in `hey botping`.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_framework::parse::static_prefix;
+///
+/// let prefixes = vec!["!".to_string(), "hey bot ".to_string(), "bot".to_string()];
+///
+/// assert_eq!(static_prefix("!ping", &prefixes), Some(("!", "ping")));
+/// assert_eq!(static_prefix("!  ping", &prefixes), Some(("!", "ping")));
+/// assert_eq!(static_prefix("hey bot ping", &prefixes), Some(("hey bot ", "ping")));
+/// assert_eq!(static_prefix("hey botping", &prefixes), None);
+/// assert_eq!(static_prefix("bot ping", &prefixes), Some(("bot", "ping")));
+/// assert_eq!(static_prefix("bot", &prefixes), Some(("bot", "")));
+/// assert_eq!(static_prefix("botping", &prefixes), None);
+/// assert_eq!(static_prefix("ping", &prefixes), None);
+/// ```
 pub fn static_prefix<'a>(msg: &'a str, prefixes: &[String]) -> Option<(&'a str, &'a str)> {
-    prefixes.iter().find(|p| msg.starts_with(p.as_str())).map(|p| msg.split_at(p.len()))
+    prefixes.iter().find_map(|p| {
+        let rest = msg.strip_prefix(p.as_str())?;
+
+        if p.ends_with(char::is_alphanumeric)
+            && !rest.is_empty()
+            && !rest.starts_with(char::is_whitespace)
+        {
+            return None;
+        }
+
+        Some((&msg[..p.len()], rest.trim_start()))
+    })
+}
+
+/// Returns a boolean indicating whether a static prefix in the message is escaped.
+///
+/// A prefix is escaped if the message starts with the `escape` character, directly
+/// followed by one of the prefixes.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_framework::parse::escaped_prefix;
+///
+/// let prefixes = vec!["!".to_string()];
+///
+/// assert!(escaped_prefix("\\!ping", '\\', &prefixes));
+/// assert!(!escaped_prefix("!ping", '\\', &prefixes));
+///
+/// // A doubled prefix acts as an escape if the escape character is the prefix.
+/// assert!(escaped_prefix("!!ping", '!', &prefixes));
+/// assert!(!escaped_prefix("!ping", '!', &prefixes));
+/// ```
+pub fn escaped_prefix(msg: &str, escape: char, prefixes: &[String]) -> bool {
+    match msg.strip_prefix(escape) {
+        Some(rest) => static_prefix(rest, prefixes).is_some(),
+        None => false,
+    }
 }
 
 /// Returns the content of the message after parsing a prefix.
@@ -80,37 +224,84 @@ pub fn static_prefix<'a>(msg: &'a str, prefixes: &[String]) -> Option<(&'a str,
 /// If the [`Configuration::no_dm_prefix`] option is enabled, the content is
 /// the whole message.
 ///
+/// The `text` is the content of the message, as transformed by the
+/// [`Configuration::content_preprocessor`] hook if one is registered.
+///
 /// The prefix is defined as:
-/// 1. a [mention]
-/// 2. a [statically defined prefix from a list][prefixes]
-/// 3. or a [dynamically chosen prefix][dyn_prefix]
+/// 1. a [mention], or a [textual mention of the name of the bot][name] if
+///    [`Configuration::mention_by_name`] is enabled
+/// 2. a [statically defined prefix from a list][prefixes], or from the lazily evaluated
+///    [`Configuration::prefix_fn`]
+/// 3. a [dynamically chosen prefix][dyn_prefix]
+/// 4. or a prefix recognised by one of the [custom prefix matchers][matchers]
 ///
 /// It is parsed in that order.
 ///
+/// If [`Configuration::trim_leading`] is enabled, [leading whitespace and invisible
+/// characters][trim] are removed from the text before the prefix is parsed.
+///
 /// If [`Configuration::no_dm_prefix`] is `false` and no prefix is found,
-/// `None` is returned. Otherwise, the prefix and the content are returned.
+/// `None` is returned, unless the prefix is [not required][required] in the
+/// [type of the channel][type] of the message, in which case the content is the
+/// whole message. Otherwise, the prefix and the content are returned.
 ///
+/// If the [`Configuration::prefix_escape`] character is set and a static prefix is
+/// [escaped][esc], `None` is returned, as the message is not a command invocation.
+///
+/// [`Configuration::trim_leading`]: crate::configuration::Configuration::trim_leading
+/// [trim]: trim_leading
 /// [`Configuration::no_dm_prefix`]: crate::configuration::Configuration::no_dm_prefix
+/// [required]: crate::configuration::Configuration::prefix_requirement
+/// [type]: channel_type
+/// [`Configuration::prefix_escape`]: crate::configuration::Configuration::prefix_escape
+/// [`Configuration::mention_by_name`]: crate::configuration::Configuration::mention_by_name
+/// [`Configuration::prefix_fn`]: crate::configuration::Configuration::prefix_fn
+/// [name]: bot_name_mention
+/// [esc]: escaped_prefix
+/// [`Configuration::content_preprocessor`]: crate::configuration::Configuration::content_preprocessor
 /// [prefixes]: static_prefix
 /// [dyn_prefix]: dynamic_prefix
-#[allow(clippy::needless_lifetimes)]
+/// [matchers]: crate::configuration::Configuration::prefix_matchers
 pub async fn content<'a, D, E>(
-    data: &Arc<D>,
+    data: &Arc<RwLock<D>>,
     conf: &Configuration<D, E>,
     serenity_ctx: &SerenityContext,
-    msg: &'a Message,
+    msg: &Message,
+    text: &'a str,
 ) -> Option<(&'a str, &'a str)> {
+    let text = if conf.trim_leading { trim_leading(text) } else { text };
+
     if msg.is_private() && conf.no_dm_prefix {
-        return Some(("", &msg.content));
+        return Some(("", text));
     }
 
     if let Some(on_mention) = &conf.on_mention {
-        if let Some(pair) = mention(&msg.content, &on_mention) {
+        if let Some(pair) = mention(text, &on_mention) {
+            return Some(pair);
+        }
+    }
+
+    if conf.mention_by_name {
+        if let Some(pair) = bot_name_mention(serenity_ctx, msg, text).await {
             return Some(pair);
         }
     }
 
-    if let Some(pair) = static_prefix(&msg.content, &conf.prefixes) {
+    let lazy_prefixes = conf.lazy_prefixes();
+
+    if let Some(escape) = conf.prefix_escape {
+        if escaped_prefix(text, escape, &conf.prefixes)
+            || escaped_prefix(text, escape, lazy_prefixes)
+        {
+            return None;
+        }
+    }
+
+    if let Some(pair) = static_prefix(text, &conf.prefixes) {
+        return Some(pair);
+    }
+
+    if let Some(pair) = static_prefix(text, lazy_prefixes) {
         return Some(pair);
     }
 
@@ -120,7 +311,59 @@ pub async fn content<'a, D, E>(
         serenity_ctx,
     };
 
-    dynamic_prefix(ctx, msg).await
+    if let Some(pair) = dynamic_prefix(ctx.clone(), msg, text).await {
+        return Some(pair);
+    }
+
+    for matcher in &conf.prefix_matchers {
+        if let Some(pair) = matcher.match_prefix(&ctx, msg, text).await {
+            return Some(pair);
+        }
+    }
+
+    if conf.prefix_requirement.is_some()
+        && !conf.prefix_required(channel_type(serenity_ctx, msg).await)
+    {
+        return Some(("", text));
+    }
+
+    None
+}
+
+/// Returns the text without its leading whitespace and invisible characters.
+///
+/// Invisible characters are the zero width space (`U+200B`), the zero width
+/// non-joiner (`U+200C`), the zero width joiner (`U+200D`), the word joiner
+/// (`U+2060`) and the byte order mark (`U+FEFF`), which some clients insert
+/// at the start of messages.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_framework::parse::trim_leading;
+///
+/// assert_eq!(trim_leading(" !ping"), "!ping");
+/// assert_eq!(trim_leading("\t\n!ping a"), "!ping a");
+/// assert_eq!(trim_leading("\u{200B}\u{FEFF} !ping"), "!ping");
+/// assert_eq!(trim_leading("!ping "), "!ping ");
+/// ```
+pub fn trim_leading(text: &str) -> &str {
+    text.trim_start_matches(|c: char| {
+        c.is_whitespace()
+            || matches!(c, '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}')
+    })
+}
+
+/// Returns the type of the channel of the message.
+///
+/// Direct message channels are recognised without the cache. The type of a guild
+/// channel is retrieved from the cache. `None` is returned if the channel is not cached.
+pub async fn channel_type(ctx: &SerenityContext, msg: &Message) -> Option<ChannelType> {
+    if msg.is_private() {
+        return Some(ChannelType::Private);
+    }
+
+    ctx.cache.guild_channel(msg.channel_id).await.map(|channel| channel.kind)
 }
 
 /// Command parsing iterator.
@@ -134,48 +377,165 @@ pub struct CommandIterator<'a, 'b, 'c, D, E> {
     conf: &'a Configuration<D, E>,
     segments: &'b mut Segments<'c>,
     command: Option<&'a Command<D, E>>,
+    category: Option<&'a Category>,
+    candidates: Vec<&'a Command<D, E>>,
+    separated: bool,
 }
 
-impl<'a, 'b, 'c, D, E> Iterator for CommandIterator<'a, 'b, 'c, D, E> {
-    type Item = Result<&'a Command<D, E>, DispatchError>;
+impl<'a, 'b, 'c, D, E> CommandIterator<'a, 'b, 'c, D, E> {
+    /// Returns the rest of the segments after the last parsed command.
+    ///
+    /// These are the arguments of the last parsed command.
+    pub fn args(&self) -> &'c str {
+        self.segments.source()
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let checkpoint = self.segments.source();
-        let name = self.segments.next()?;
+    /// Returns every reachable command under the name of the last parsed command.
+    ///
+    /// The last parsed command is the first of the candidates. Other candidates
+    /// are only present if [`Configuration::allow_name_collisions`] is enabled.
+    ///
+    /// [`Configuration::allow_name_collisions`]: crate::configuration::Configuration::allow_name_collisions
+    pub fn candidates(&self) -> &[&'a Command<D, E>] {
+        &self.candidates
+    }
 
-        let cmd = match self.conf.commands.get_by_name(&*name) {
-            Some(cmd) => cmd,
-            None => {
-                self.segments.set_source(checkpoint);
+    /// Returns all commands registered under the name.
+    fn commands_by_name(&self, name: &str) -> Vec<&'a Command<D, E>> {
+        if self.conf.allow_name_collisions {
+            match self.conf.command_names.get(name) {
+                Some(ids) => ids.iter().filter_map(|id| self.conf.commands.get(*id)).collect(),
+                None => Vec::new(),
+            }
+        } else {
+            self.conf.commands.get_by_name(name).into_iter().collect()
+        }
+    }
 
-                // At least one valid command must be present in the message.
-                // After the first command, we do not care if the "name" is invalid,
-                // as it may be the argument to the command at that point.
-                if self.command.is_none() {
-                    return Some(Err(DispatchError::InvalidCommandName(name.into_owned())));
-                }
+    /// Returns a boolean indicating whether the command may follow the last parsed command.
+    fn reachable(&self, cmd: &Command<D, E>) -> bool {
+        match self.command {
+            // We received a command, but it may not be a subcommand of the previously
+            // parsed command. Interpret it as an argument instead.
+            //
+            // This enables user-defined `help` commands.
+            Some(command) => command.subcommands.contains(&cmd.id),
+            // Commands of a prefixed category are only reachable after its prefix.
+            None => match self.category {
+                Some(category) => category.commands.contains(&cmd.id),
+                None => self.conf.root_level_commands.contains(&cmd.id),
+            },
+        }
+    }
 
-                return None;
+    /// Returns the next command name and the rest of the source after it, without
+    /// advancing the segments.
+    ///
+    /// If the [subcommand separator] is set, names are also delimited by it, and names
+    /// after the first command must follow it. The separator is not part of the rest
+    /// of the source, and the returned boolean indicates whether the name was followed
+    /// by it.
+    ///
+    /// [subcommand separator]: crate::configuration::Configuration::subcommand_separator
+    fn peek_name(&self) -> Option<(Cow<'c, str>, &'c str, bool)> {
+        let separator = match self.conf.subcommand_separator.as_deref() {
+            Some(separator) if !separator.is_empty() => separator,
+            _ => {
+                let mut segments = self.segments.clone();
+                let name = segments.next()?;
+                return Some((name, segments.source(), false));
             },
         };
 
-        if self.command.is_none() && !self.conf.root_level_commands.contains(&cmd.id) {
-            self.segments.set_source(checkpoint);
+        // The subcommand must directly follow the separator. Otherwise,
+        // the arguments of the command begin.
+        if self.command.is_some() && !self.separated {
             return None;
         }
 
-        if let Some(command) = self.command {
-            if !command.subcommands.contains(&cmd.id) {
-                // We received a command, but it's not a subcommand of the previously
-                // parsed command. Interpret it as an argument instead.
-                //
-                // This enables user-defined `help` commands.
-                self.segments.set_source(checkpoint);
-                return None;
+        let src = self.segments.source();
+        let segment = utils::segment(src, self.segments.delimiter())?;
+
+        let (name, rest, separated) = match segment.find(separator) {
+            Some(index) => (&segment[..index], &src[index + separator.len()..], true),
+            None => {
+                let (name, rest) = utils::segment_split(src, self.segments.delimiter())?;
+                (name, rest, false)
+            },
+        };
+
+        let name = if self.segments.case_insensitive() {
+            Cow::Owned(name.to_lowercase())
+        } else {
+            Cow::Borrowed(name)
+        };
+
+        Some((name, rest, separated))
+    }
+
+    /// Returns the category whose [prefix] is equal to the name.
+    ///
+    /// [prefix]: crate::category::Category::prefix
+    fn category_by_prefix(&self, name: &str) -> Option<&'a Category> {
+        self.conf.categories.iter().find(|category| match &category.prefix {
+            Some(prefix) if self.conf.case_insensitive => prefix.to_lowercase() == name,
+            Some(prefix) => prefix == name,
+            None => false,
+        })
+    }
+}
+
+impl<'a, 'b, 'c, D, E> Iterator for CommandIterator<'a, 'b, 'c, D, E> {
+    type Item = Result<&'a Command<D, E>, DispatchError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (mut name, mut rest, mut separated) = self.peek_name()?;
+
+        if self.command.is_none() && self.category.is_none() {
+            if let Some(category) = self.category_by_prefix(&name) {
+                self.category = Some(category);
+                self.segments.set_source(rest);
+
+                // The prefix of a category must be followed by one of its commands.
+                let (next_name, next_rest, next_separated) = match self.peek_name() {
+                    Some(next) => next,
+                    None => return Some(Err(DispatchError::InvalidCommandName(name.into_owned()))),
+                };
+                name = next_name;
+                rest = next_rest;
+                separated = next_separated;
             }
         }
 
+        let candidates = self.commands_by_name(&name);
+
+        if candidates.is_empty() {
+            // At least one valid command must be present in the message.
+            // After the first command, we do not care if the "name" is invalid,
+            // as it may be the argument to the command at that point.
+            if self.command.is_none() {
+                return Some(Err(DispatchError::InvalidCommandName(name.into_owned())));
+            }
+
+            return None;
+        }
+
+        let candidates: Vec<_> = candidates.into_iter().filter(|cmd| self.reachable(cmd)).collect();
+
+        let cmd = match candidates.first() {
+            Some(cmd) => *cmd,
+            // The first command must be reachable, such as a command of a prefixed
+            // category only after the prefix of the category.
+            None if self.command.is_none() => {
+                return Some(Err(DispatchError::InvalidCommandName(name.into_owned())));
+            },
+            None => return None,
+        };
+
+        self.segments.set_source(rest);
         self.command = Some(cmd);
+        self.candidates = candidates;
+        self.separated = separated;
 
         Some(Ok(cmd))
     }
@@ -193,6 +553,14 @@ impl<'a, 'b, 'c, D, E> Iterator for CommandIterator<'a, 'b, 'c, D, E> {
 /// The `Result` signifies whether a given name for the first command exists.
 /// If it is not the case, the [`InvalidCommandName`] error is returned.
 ///
+/// If the first segment is the [prefix of a category][prefix], the first command
+/// is looked up among the commands of that category, instead of the root level commands.
+/// The [`InvalidCommandName`] error is also returned if the first name belongs to a command
+/// that is not reachable this way, or if the prefix of a category is not followed by a name.
+///
+/// If the [subcommand separator] is set, subcommands are separated from their parent
+/// command by it, instead of by the delimiter of the segments.
+///
 /// The `Option` returned from calling [`Iterator::next`] will signify whether the content had a
 /// command, did not have a command, or was empty.
 ///
@@ -200,6 +568,8 @@ impl<'a, 'b, 'c, D, E> Iterator for CommandIterator<'a, 'b, 'c, D, E> {
 /// [`Command`]: crate::command::Command
 /// [`DispatchError`]: crate::error::DispatchError
 /// [`InvalidCommandName`]: crate::error::DispatchError::InvalidCommandName
+/// [prefix]: crate::category::Category::prefix
+/// [subcommand separator]: crate::configuration::Configuration::subcommand_separator
 pub fn commands<'a, 'b, 'c, D, E>(
     conf: &'a Configuration<D, E>,
     segments: &'b mut Segments<'c>,
@@ -208,55 +578,356 @@ pub fn commands<'a, 'b, 'c, D, E>(
         conf,
         segments,
         command: None,
+        category: None,
+        candidates: Vec::new(),
+        separated: false,
+    }
+}
+
+/// Returns the permissions of the bot in the channel of the message.
+///
+/// The channel is [resolved from the cache, or from HTTP][channel] if it is not cached,
+/// and so are [the permissions][perms]. `None` is returned if the message was sent in a
+/// direct message channel, or if the channel or the permissions could not be resolved.
+/// No request is made for direct messages.
+///
+/// [channel]: crate::utils::guild_channel
+/// [perms]: crate::utils::permissions_in
+pub async fn bot_permissions(ctx: &SerenityContext, msg: &Message) -> Option<Permissions> {
+    if msg.is_private() {
+        return None;
+    }
+
+    let channel = utils::guild_channel(ctx, msg.channel_id).await.ok()??;
+    let bot_id = ctx.cache.current_user_id().await;
+
+    utils::permissions_in(ctx, &channel, bot_id).await.ok()
+}
+
+/// Returns a boolean indicating whether [NSFW commands][nsfw] can be run in the channel
+/// of the message.
+///
+/// In direct messages, this is the [`Configuration::nsfw_in_dms`] option. In guilds, the
+/// channel is [resolved from the cache, or from HTTP][channel] if it is not cached. If the
+/// channel cannot be resolved, it is not considered NSFW.
+///
+/// [nsfw]: crate::command::Command::nsfw
+/// [`Configuration::nsfw_in_dms`]: crate::configuration::Configuration::nsfw_in_dms
+/// [channel]: crate::utils::guild_channel
+pub async fn nsfw_allowed<D, E>(
+    conf: &Configuration<D, E>,
+    ctx: &SerenityContext,
+    msg: &Message,
+) -> bool {
+    if msg.is_private() {
+        return conf.nsfw_in_dms;
+    }
+
+    match utils::guild_channel(ctx, msg.channel_id).await {
+        Ok(Some(channel)) => channel.nsfw,
+        _ => false,
+    }
+}
+
+/// Ensures that a command can be run by the author of the message if the bot is in
+/// [maintenance mode][maintenance].
+///
+/// # Errors
+///
+/// If the bot is in maintenance mode, the command is not [available in it][available],
+/// and the author is not one of the [owners], [`DispatchError::Maintenance`] is returned.
+///
+/// [maintenance]: crate::configuration::Configuration::maintenance
+/// [available]: crate::command::Command::available_in_maintenance
+/// [owners]: crate::configuration::Configuration::owners
+pub fn maintenance<D, E>(
+    conf: &Configuration<D, E>,
+    msg: &Message,
+    command: &Command<D, E>,
+) -> Result<(), DispatchError> {
+    if conf.maintenance
+        && !command.available_in_maintenance
+        && !conf.owners.contains(&msg.author.id)
+    {
+        return Err(DispatchError::Maintenance);
+    }
+
+    Ok(())
+}
+
+/// Ensures that the author, the channel and the guild of the message are not
+/// [blocked][blocked] from invoking commands.
+///
+/// If any of them is blocked, the [blocked hook][hook] is called, if one is registered.
+///
+/// # Errors
+///
+/// If any of them is blocked, [`DispatchError::Blocked`] is returned.
+///
+/// [blocked]: crate::configuration::Configuration::blocked_users
+/// [hook]: crate::configuration::Configuration::on_blocked
+pub async fn is_blocked<D, E>(
+    conf: &Configuration<D, E>,
+    msg: &Message,
+) -> Result<(), DispatchError> {
+    let blocked = conf.blocked_users.contains(&msg.author.id)
+        || conf.blocked_channels.contains(&msg.channel_id)
+        || matches!(msg.guild_id, Some(id) if conf.blocked_guilds.contains(&id));
+
+    if !blocked {
+        return Ok(());
+    }
+
+    let err = DispatchError::Blocked;
+
+    if let Some(hook) = conf.on_blocked {
+        hook(msg, &err).await;
+    }
+
+    Err(err)
+}
+
+/// Ensures that a command is enabled for the message by calling the
+/// [command enabled hook][hook], if one is registered.
+///
+/// # Errors
+///
+/// If the hook returns `false`, [`DispatchError::DisabledInGuild`] is returned.
+///
+/// [hook]: crate::configuration::Configuration::command_enabled
+pub async fn command_enabled<D, E>(
+    conf: &Configuration<D, E>,
+    msg: &Message,
+    command: &Command<D, E>,
+) -> Result<(), DispatchError> {
+    match conf.command_enabled {
+        Some(hook) if !hook(msg, command.id).await => {
+            Err(DispatchError::DisabledInGuild(command.id))
+        },
+        _ => Ok(()),
     }
 }
 
+/// Calls the [`checks`] of a command in order.
+///
+/// The `prefix` and `args` are provided to the checks in the [`CheckContext`].
+///
+/// # Errors
+///
+/// If a check fails, [`DispatchError::CheckFailed`] is returned, and the remaining
+/// checks are not called.
+///
+/// [`checks`]: crate::command::Command::checks
+pub async fn check<D, E>(
+    data: &Arc<RwLock<D>>,
+    conf: &Configuration<D, E>,
+    serenity_ctx: &SerenityContext,
+    msg: &Message,
+    command: &Command<D, E>,
+    prefix: &str,
+    args: &str,
+) -> Result<(), DispatchError> {
+    for check in &command.checks {
+        run_check(data, conf, serenity_ctx, msg, check, command.id, prefix, args).await?;
+    }
+
+    Ok(())
+}
+
+/// Calls the [global checks][checks] in order for a command.
+///
+/// The `prefix` and `args` are provided to the checks in the [`CheckContext`].
+///
+/// # Errors
+///
+/// If a check fails, [`DispatchError::CheckFailed`] is returned, and the remaining
+/// checks are not called.
+///
+/// [checks]: crate::configuration::Configuration::global_checks
+pub async fn global_checks<D, E>(
+    data: &Arc<RwLock<D>>,
+    conf: &Configuration<D, E>,
+    serenity_ctx: &SerenityContext,
+    msg: &Message,
+    command_id: CommandId,
+    prefix: &str,
+    args: &str,
+) -> Result<(), DispatchError> {
+    for check in &conf.global_checks {
+        run_check(data, conf, serenity_ctx, msg, check, command_id, prefix, args).await?;
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_check<D, E>(
+    data: &Arc<RwLock<D>>,
+    conf: &Configuration<D, E>,
+    serenity_ctx: &SerenityContext,
+    msg: &Message,
+    check: &Check<D, E>,
+    command_id: CommandId,
+    prefix: &str,
+    args: &str,
+) -> Result<(), DispatchError> {
+    let ctx = CheckContext {
+        data,
+        conf,
+        serenity_ctx,
+        command_id,
+        prefix,
+        args,
+    };
+
+    match (check.function)(&ctx, msg).await {
+        Ok(()) => Ok(()),
+        Err(reason) => Err(DispatchError::CheckFailed(check.name.clone(), reason)),
+    }
+}
+
+/// Ensures that a command may be run for the message.
+///
+/// This enforces the [maintenance mode][maintenance] and the [command enabled hook][enabled],
+/// verifies that the bot has the [permissions required by the command][perms] and that
+/// [NSFW commands][nsfw] are [allowed][nsfw_allowed] in the channel, and calls the
+/// [`checks`] of the command. If `first` is `true`, the [global checks][global] are called
+/// before the checks of the command.
+///
+/// # Errors
+///
+/// The error of the first requirement that is not met is returned.
+///
+/// [maintenance]: maintenance
+/// [enabled]: command_enabled
+/// [perms]: crate::command::Command::bot_required_permissions
+/// [nsfw]: crate::command::Command::nsfw
+/// [`checks`]: crate::command::Command::checks
+/// [global]: crate::configuration::Configuration::global_checks
+#[allow(clippy::too_many_arguments)]
+pub async fn verify<D, E>(
+    data: &Arc<RwLock<D>>,
+    conf: &Configuration<D, E>,
+    ctx: &SerenityContext,
+    msg: &Message,
+    cmd: &Command<D, E>,
+    first: bool,
+    prefix: &str,
+    args: &str,
+) -> Result<(), DispatchError> {
+    maintenance(conf, msg, cmd)?;
+    command_enabled(conf, msg, cmd).await?;
+
+    if conf.require_send_permission || !cmd.bot_required_permissions.is_empty() {
+        if let Some(permissions) = bot_permissions(ctx, msg).await {
+            if conf.require_send_permission && !permissions.send_messages() {
+                return Err(DispatchError::CannotRespond);
+            }
+
+            let missing = cmd.bot_required_permissions - permissions;
+
+            if !missing.is_empty() {
+                return Err(DispatchError::BotMissingPermissions(missing));
+            }
+        }
+    }
+
+    if cmd.nsfw && !nsfw_allowed(conf, ctx, msg).await {
+        return Err(DispatchError::NsfwRequired);
+    }
+
+    if first {
+        global_checks(data, conf, ctx, msg, cmd.id, prefix, args).await?;
+    }
+
+    check(data, conf, ctx, msg, cmd, prefix, args).await
+}
+
 /// Parses and checks all valid commands in a message after the prefix.
 ///
-/// This parses commands from `content` using [`commands`]. For each valid command,
-/// it calls its [`check`] function if it has one configured. Commands are
-/// parsed from space-delimited [`Segments`].
+/// This parses commands from `content`, which follows the `prefix`, using [`commands`].
+/// For each valid command, it ensures that the command may be run in
+/// [maintenance mode][maintenance], that it is [enabled][enabled], that the bot has the [permissions required by the
+/// command][perms] and that [NSFW commands][nsfw] are [allowed][nsfw_allowed] in the
+/// channel, and calls its [`check`] function if it has one configured. Commands
+/// are parsed from space-delimited [`Segments`], or subcommands from segments delimited
+/// by the [subcommand separator][separator] if it is set. The [global checks][global]
+/// are called before the check of the first command.
+///
+/// If [`Configuration::allow_name_collisions`] is enabled and a command fails any of
+/// these, the other [candidates] under its name are tried in order. The first candidate
+/// that passes is chosen. If none pass, the error of the first candidate is returned.
+///
+/// The permissions of the bot are not enforced in direct messages, or if they cannot be
+/// [resolved][bot_permissions].
 ///
 /// ## Return type
 ///
 /// This returns the last valid command and its arguments if parsing
 /// and checking commands went successfully.
 ///
+/// The arguments are a slice of the original `content`. They retain their casing
+/// even if [`Configuration::case_insensitive`] is enabled, as it only applies to
+/// command names.
+///
 /// It may be `None` if no command was found in `content` (it is empty); or
-/// it may be `Err(...)` if either the first segment is an invalid command
-/// name or the check function returned an error.
+/// it may be `Err(...)` if the first segment is an invalid command name, the bot is in
+/// maintenance mode, the command is disabled, the bot is missing permissions, the command is NSFW outside of an
+/// NSFW channel, or the check function returned an error.
 ///
-/// [`check`]: crate::command::Command::check
+/// [maintenance]: maintenance
+/// [enabled]: command_enabled
+/// [`check`]: crate::command::Command::checks
+/// [perms]: crate::command::Command::bot_required_permissions
+/// [nsfw]: crate::command::Command::nsfw
+/// [global]: crate::configuration::Configuration::global_checks
 /// [`Segments`]: crate::utils::Segments
+/// [`Configuration::case_insensitive`]: crate::configuration::Configuration::case_insensitive
+/// [`Configuration::allow_name_collisions`]: crate::configuration::Configuration::allow_name_collisions
+/// [candidates]: CommandIterator::candidates
+/// [separator]: crate::configuration::Configuration::subcommand_separator
 #[allow(clippy::needless_lifetimes)]
 pub async fn command<'a, D, E>(
-    data: &Arc<D>,
+    data: &Arc<RwLock<D>>,
     conf: &'a Configuration<D, E>,
     ctx: &SerenityContext,
     msg: &Message,
+    prefix: &str,
     content: &str,
 ) -> Result<Option<(&'a Command<D, E>, String)>, DispatchError> {
     let mut segments = Segments::new(content, " ", conf.case_insensitive);
 
     let mut command = None;
 
-    for cmd in commands(conf, &mut segments) {
-        let cmd = cmd?;
+    let mut iter = commands(conf, &mut segments);
+
+    while let Some(cmd) = iter.next() {
+        cmd?;
 
-        if let Some(check) = &cmd.check {
-            let ctx = CheckContext {
-                data,
-                conf,
-                serenity_ctx: &ctx,
-                command_id: cmd.id,
-            };
+        let candidates = iter.candidates().to_vec();
+        let mut error = None;
 
-            if let Err(reason) = (check.function)(&ctx, msg).await {
-                return Err(DispatchError::CheckFailed(check.name.clone(), reason));
+        for cmd in candidates {
+            let first = command.is_none();
+
+            match verify(data, conf, ctx, msg, cmd, first, prefix, iter.args()).await {
+                Ok(()) => {
+                    iter.command = Some(cmd);
+                    error = None;
+                    break;
+                },
+                // If every candidate fails, the error of the first candidate is returned.
+                Err(err) => {
+                    error.get_or_insert(err);
+                },
             }
         }
 
-        command = Some(cmd);
+        if let Some(err) = error {
+            return Err(err);
+        }
+
+        command = iter.command;
     }
 
     let args = segments.source();
@@ -0,0 +1,212 @@
+//! Functions and types relating to prefix matching.
+//!
+//! The prefixes of the framework are defined by the [`prefixes`], [`on_mention`] and
+//! [`dynamic_prefix`] options of the configuration. If these are not sufficient, a
+//! [`PrefixMatcher`] can be [registered][register] to recognise prefixes in any other
+//! way, such as by a regular expression or by the nickname of the bot.
+//!
+//! The [`MentionMatcher`], [`StaticMatcher`] and [`DynamicMatcher`] types implement
+//! the built-in prefixes as matchers.
+//!
+//! [`prefixes`]: crate::configuration::Configuration::prefixes
+//! [`on_mention`]: crate::configuration::Configuration::on_mention
+//! [`dynamic_prefix`]: crate::configuration::Configuration::dynamic_prefix
+//! [register]: crate::configuration::Configuration::prefix_matcher
+
+use std::fmt;
+
+use serenity::futures::future::BoxFuture;
+use serenity::model::channel::Message;
+
+use crate::configuration::DynamicPrefix;
+use crate::context::PrefixContext;
+use crate::parse;
+use crate::{DefaultData, DefaultError};
+
+/// A strategy to parse the prefix out of the content of a message.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity::futures::future::{self, BoxFuture};
+/// use serenity::model::channel::Message;
+/// use serenity_framework::context::PrefixContext;
+/// use serenity_framework::prefix::PrefixMatcher;
+///
+/// /// Matches a prefix regardless of its casing.
+/// struct Insensitive(String);
+///
+/// impl<D, E> PrefixMatcher<D, E> for Insensitive {
+///     fn match_prefix<'a, 'fut>(
+///         &'fut self,
+///         _ctx: &'fut PrefixContext<'_, D, E>,
+///         _msg: &'fut Message,
+///         content: &'a str,
+///     ) -> BoxFuture<'fut, Option<(&'a str, &'a str)>>
+///     where
+///         'a: 'fut,
+///     {
+///         let len = self.0.len();
+///
+///         let pair = match content.get(..len) {
+///             Some(prefix) if prefix.eq_ignore_ascii_case(&self.0) => {
+///                 Some((prefix, content[len..].trim_start()))
+///             },
+///             _ => None,
+///         };
+///
+///         Box::pin(future::ready(pair))
+///     }
+/// }
+/// ```
+pub trait PrefixMatcher<D = DefaultData, E = DefaultError>: Send + Sync {
+    /// Parses a prefix from the content.
+    ///
+    /// Returns `None` if the content does not start with a prefix. Otherwise, the prefix
+    /// and the rest of the content after the prefix is returned.
+    fn match_prefix<'a, 'fut>(
+        &'fut self,
+        ctx: &'fut PrefixContext<'_, D, E>,
+        msg: &'fut Message,
+        content: &'a str,
+    ) -> BoxFuture<'fut, Option<(&'a str, &'a str)>>
+    where
+        'a: 'fut;
+}
+
+/// A matcher for [mentions] of the bot.
+///
+/// This is the matcher equivalent of the [`on_mention`] option.
+///
+/// [mentions]: crate::parse::mention
+/// [`on_mention`]: crate::configuration::Configuration::on_mention
+#[derive(Debug, Clone)]
+pub struct MentionMatcher {
+    /// The user id of the bot.
+    pub id: String,
+}
+
+impl MentionMatcher {
+    /// Creates a matcher for mentions of the user id.
+    pub fn new<I>(id: I) -> Self
+    where
+        I: Into<String>,
+    {
+        Self {
+            id: id.into(),
+        }
+    }
+}
+
+impl<D, E> PrefixMatcher<D, E> for MentionMatcher {
+    fn match_prefix<'a, 'fut>(
+        &'fut self,
+        _ctx: &'fut PrefixContext<'_, D, E>,
+        _msg: &'fut Message,
+        content: &'a str,
+    ) -> BoxFuture<'fut, Option<(&'a str, &'a str)>>
+    where
+        'a: 'fut,
+    {
+        Box::pin(async move { parse::mention(content, &self.id) })
+    }
+}
+
+/// A matcher for a [list of static prefixes][static].
+///
+/// This is the matcher equivalent of the [`prefixes`] option.
+///
+/// [static]: crate::parse::static_prefix
+/// [`prefixes`]: crate::configuration::Configuration::prefixes
+#[derive(Debug, Clone)]
+pub struct StaticMatcher {
+    /// The list of prefixes.
+    pub prefixes: Vec<String>,
+}
+
+impl StaticMatcher {
+    /// Creates a matcher for a list of prefixes.
+    pub fn new<I>(prefixes: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self {
+            prefixes: prefixes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl<D, E> PrefixMatcher<D, E> for StaticMatcher {
+    fn match_prefix<'a, 'fut>(
+        &'fut self,
+        _ctx: &'fut PrefixContext<'_, D, E>,
+        _msg: &'fut Message,
+        content: &'a str,
+    ) -> BoxFuture<'fut, Option<(&'a str, &'a str)>>
+    where
+        'a: 'fut,
+    {
+        Box::pin(async move { parse::static_prefix(content, &self.prefixes) })
+    }
+}
+
+/// A matcher for a [dynamically chosen prefix][dyn_prefix].
+///
+/// This is the matcher equivalent of the [`dynamic_prefix`] option.
+///
+/// [dyn_prefix]: crate::parse::dynamic_prefix
+/// [`dynamic_prefix`]: crate::configuration::Configuration::dynamic_prefix
+pub struct DynamicMatcher<D = DefaultData, E = DefaultError> {
+    /// The function returning the length of the prefix.
+    pub function: DynamicPrefix<D, E>,
+}
+
+impl<D, E> DynamicMatcher<D, E> {
+    /// Creates a matcher for a dynamic prefix function.
+    pub fn new(function: DynamicPrefix<D, E>) -> Self {
+        Self {
+            function,
+        }
+    }
+}
+
+impl<D, E> Clone for DynamicMatcher<D, E> {
+    fn clone(&self) -> Self {
+        Self {
+            function: self.function,
+        }
+    }
+}
+
+impl<D, E> fmt::Debug for DynamicMatcher<D, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynamicMatcher").field("function", &"<fn>").finish()
+    }
+}
+
+impl<D, E> PrefixMatcher<D, E> for DynamicMatcher<D, E>
+where
+    D: Send + Sync,
+    E: Send + Sync,
+{
+    fn match_prefix<'a, 'fut>(
+        &'fut self,
+        ctx: &'fut PrefixContext<'_, D, E>,
+        msg: &'fut Message,
+        content: &'a str,
+    ) -> BoxFuture<'fut, Option<(&'a str, &'a str)>>
+    where
+        'a: 'fut,
+    {
+        Box::pin(async move {
+            let index = (self.function)(ctx.clone(), msg).await?;
+
+            if content.is_char_boundary(index) {
+                Some(content.split_at(index))
+            } else {
+                None
+            }
+        })
+    }
+}
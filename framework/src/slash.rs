@@ -0,0 +1,195 @@
+//! Functions and types relating to the export of commands as slash commands.
+//!
+//! A [`Command`] can be converted into the definition of an application command
+//! with [`Command::to_application_command`]. The definition is derived from the
+//! [primary name][primary], the [description] and the [arguments] of the command,
+//! which allows the commands of the framework to remain the source of truth when
+//! registering slash commands with Discord.
+//!
+//! This module is only available with the `slash` feature, which enables the
+//! `unstable_discord_api` feature of Serenity.
+//!
+//! [primary]: Command::primary_name
+//! [description]: Command::description
+//! [arguments]: Command::arguments
+
+use std::error::Error as StdError;
+use std::fmt;
+
+use serenity::builder::CreateApplicationCommand;
+use serenity::model::interactions::ApplicationCommandOptionType;
+
+use crate::command::{ArgumentInfo, ArgumentKind, Command};
+
+/// An error describing why a command cannot be converted into an application command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConversionError {
+    /// The command has no static description, which application commands require.
+    MissingDescription,
+    /// An argument is of a kind that cannot be expressed as an option, such as
+    /// a variadic or an attachment argument.
+    UnsupportedKind {
+        /// The name of the argument.
+        argument: String,
+        /// The kind of the argument.
+        kind: ArgumentKind,
+    },
+    /// An argument is of a type that does not correspond to any option type.
+    UnsupportedType {
+        /// The name of the argument.
+        argument: String,
+        /// The name of the type of the argument.
+        type_name: String,
+    },
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConversionError::MissingDescription => f.write_str("command has no description"),
+            ConversionError::UnsupportedKind {
+                argument,
+                kind,
+            } => write!(f, "argument \"{}\" is of unsupported kind {:?}", argument, kind),
+            ConversionError::UnsupportedType {
+                argument,
+                type_name,
+            } => write!(f, "argument \"{}\" is of unsupported type `{}`", argument, type_name),
+        }
+    }
+}
+
+impl StdError for ConversionError {}
+
+impl<D, E> Command<D, E> {
+    /// Converts the command into the definition of an application command.
+    ///
+    /// The name of the application command is the [primary name][primary] of the
+    /// command, and its description is the [static description][description].
+    /// Every argument becomes an option, named and described by the name of the
+    /// argument. The type of the option is inferred from the name of the
+    /// argument's type:
+    ///
+    /// - `String`, `str` and `char` are strings.
+    /// - The primitive integer types are integers.
+    /// - `bool` is a boolean.
+    /// - `User`, `UserId` and `Member` are users.
+    /// - `Channel`, `ChannelId` and `GuildChannel` are channels.
+    /// - `Role` and `RoleId` are roles.
+    ///
+    /// Required arguments become required options, whereas optional and rest
+    /// arguments become optional options.
+    ///
+    /// # Errors
+    ///
+    /// - If the command has no static description, [`ConversionError::MissingDescription`]
+    ///   is returned.
+    /// - If an argument is variadic or binds attachments, stickers or embeds,
+    ///   [`ConversionError::UnsupportedKind`] is returned.
+    /// - If the type of an argument is not listed above, [`ConversionError::UnsupportedType`]
+    ///   is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::model::channel::{Attachment, Message};
+    /// use serenity::model::id::UserId;
+    /// use serenity_framework::command::ArgumentKind;
+    /// use serenity_framework::prelude::*;
+    /// use serenity_framework::slash::ConversionError;
+    ///
+    /// #[command]
+    /// #[description("Warns a user")]
+    /// async fn warn(_ctx: FrameworkContext, _msg: &Message, user: UserId, #[rest] reason: String) {}
+    ///
+    /// let command = warn().to_application_command().unwrap();
+    ///
+    /// assert_eq!(command.0["name"], "warn");
+    /// assert_eq!(command.0["description"], "Warns a user");
+    ///
+    /// let options = command.0["options"].as_array().unwrap();
+    ///
+    /// assert_eq!(options.len(), 2);
+    /// assert_eq!(options[0]["name"], "user");
+    /// assert_eq!(options[0]["type"], 6);
+    /// assert_eq!(options[0]["required"], true);
+    /// assert_eq!(options[1]["name"], "reason");
+    /// assert_eq!(options[1]["type"], 3);
+    /// assert_eq!(options[1]["required"], false);
+    ///
+    /// #[command]
+    /// #[description("Sums numbers")]
+    /// async fn sum(_ctx: FrameworkContext, _msg: &Message, numbers: Vec<u32>) {}
+    ///
+    /// assert_eq!(sum().to_application_command().unwrap_err(), ConversionError::UnsupportedKind {
+    ///     argument: "numbers".to_string(),
+    ///     kind: ArgumentKind::Variadic,
+    /// });
+    ///
+    /// #[command]
+    /// #[description("Uploads a file")]
+    /// async fn upload(_ctx: FrameworkContext, _msg: &Message, #[attachment] file: Attachment) {}
+    ///
+    /// assert_eq!(upload().to_application_command().unwrap_err(), ConversionError::UnsupportedKind {
+    ///     argument: "file".to_string(),
+    ///     kind: ArgumentKind::Attachment,
+    /// });
+    /// ```
+    ///
+    /// [primary]: Command::primary_name
+    /// [description]: Command::description
+    pub fn to_application_command(&self) -> Result<CreateApplicationCommand, ConversionError> {
+        let description = self.description.as_ref().ok_or(ConversionError::MissingDescription)?;
+
+        let mut command = CreateApplicationCommand::default();
+        command.name(self.primary_name()).description(description);
+
+        for argument in &self.arguments {
+            let required = match argument.kind {
+                ArgumentKind::Required | ArgumentKind::RequiredRest => true,
+                ArgumentKind::Optional | ArgumentKind::Rest => false,
+                kind => {
+                    return Err(ConversionError::UnsupportedKind {
+                        argument: argument.name.clone(),
+                        kind,
+                    })
+                },
+            };
+
+            let kind = option_type(argument)?;
+
+            command.create_option(|option| {
+                option
+                    .name(&argument.name)
+                    .description(&argument.name)
+                    .kind(kind)
+                    .required(required)
+            });
+        }
+
+        Ok(command)
+    }
+}
+
+/// Infers the option type of an argument from the name of its type.
+fn option_type(argument: &ArgumentInfo) -> Result<ApplicationCommandOptionType, ConversionError> {
+    // Ignore the path to the type.
+    let name = argument.type_name.rsplit("::").next().unwrap_or_default();
+
+    Ok(match name {
+        "String" | "str" | "char" => ApplicationCommandOptionType::String,
+        "i8" | "i16" | "i32" | "i64" | "isize" | "u8" | "u16" | "u32" | "u64" | "usize" => {
+            ApplicationCommandOptionType::Integer
+        },
+        "bool" => ApplicationCommandOptionType::Boolean,
+        "User" | "UserId" | "Member" => ApplicationCommandOptionType::User,
+        "Channel" | "ChannelId" | "GuildChannel" => ApplicationCommandOptionType::Channel,
+        "Role" | "RoleId" => ApplicationCommandOptionType::Role,
+        _ => {
+            return Err(ConversionError::UnsupportedType {
+                argument: argument.name.clone(),
+                type_name: argument.type_name.clone(),
+            })
+        },
+    })
+}
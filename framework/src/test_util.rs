@@ -0,0 +1,500 @@
+//! Utilities for testing commands without a connection to Discord.
+//!
+//! This module is only available with the `test-util` feature.
+//!
+//! # Examples
+//!
+//! Dispatching a command from a mock message:
+//!
+//! ```rust
+//! use std::sync::atomic::{AtomicBool, Ordering};
+//!
+//! use serenity::futures::executor::block_on;
+//! use serenity::model::channel::Message;
+//! use serenity_framework::prelude::*;
+//! use serenity_framework::test_util;
+//!
+//! static INVOKED: AtomicBool = AtomicBool::new(false);
+//!
+//! #[command]
+//! async fn ping(ctx: FrameworkContext, _msg: &Message) -> CommandResult {
+//!     assert_eq!(ctx.args, "pong");
+//!     INVOKED.store(true, Ordering::SeqCst);
+//!     Ok(())
+//! }
+//!
+//! let mut conf = Configuration::new();
+//! conf.prefix("!").command(ping);
+//!
+//! let framework: Framework = Framework::new(conf);
+//!
+//! let ctx = test_util::context();
+//! let msg = test_util::message("!ping pong");
+//!
+//! block_on(framework.dispatch(&ctx, &msg)).unwrap();
+//! assert!(INVOKED.load(Ordering::SeqCst));
+//! ```
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use serde_json::json;
+use serenity::cache::Cache;
+use serenity::client::bridge::gateway::ShardMessenger;
+use serenity::client::Context as SerenityContext;
+use serenity::http::{Http, HttpBuilder};
+use serenity::model::channel::Message;
+use serenity::model::guild::{Member, Role};
+use serenity::model::id::{ChannelId, GuildId, MessageId, RoleId, UserId};
+use serenity::prelude::{RwLock, TypeMap};
+
+/// Constructs a [`Message`] with the content, sent by a user in a guild channel.
+///
+/// Refer to [`MessageBuilder`] for the default values of the message.
+pub fn message<I>(content: I) -> Message
+where
+    I: Into<String>,
+{
+    MessageBuilder::new(content).build()
+}
+
+/// Constructs a Serenity [`Context`][SerenityContext] that is not connected to Discord.
+///
+/// The cache is empty and the HTTP client has no token. Any request made with the
+/// context fails, and any message sent to the shard is discarded.
+pub fn context() -> SerenityContext {
+    let (tx, _rx) = serenity::futures::channel::mpsc::unbounded();
+
+    SerenityContext {
+        data: Arc::new(RwLock::new(TypeMap::new())),
+        shard: ShardMessenger::new(tx),
+        shard_id: 0,
+        http: Arc::new(Http::new_with_token("")),
+        cache: Arc::new(Cache::new()),
+    }
+}
+
+/// A local HTTP server that stands in for the Discord API, recording the requests
+/// made to it.
+///
+/// Every request is answered with `404 Not Found`, so requests made with the
+/// [context][Self::context] of the server fail without reaching Discord.
+///
+/// The server runs on a background thread until the process exits.
+pub struct MockHttp {
+    url: String,
+    requests: Arc<Mutex<Vec<MockRequest>>>,
+}
+
+/// A request received by [`MockHttp`].
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    /// The method of the request, such as `POST`.
+    pub method: String,
+    /// The path of the request, such as `/api/v8/channels/1/messages`.
+    pub path: String,
+    /// The body of the request.
+    pub body: String,
+}
+
+impl MockHttp {
+    /// Starts the server on a free local port.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no local port can be bound.
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind the mock server");
+        let url =
+            format!("http://{}", listener.local_addr().expect("failed to bind the mock server"));
+
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&requests);
+
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                if let Some(request) = read_request(&stream) {
+                    recorded.lock().unwrap().push(request);
+                }
+
+                let _ = stream.write_all(
+                    b"HTTP/1.1 404 Not Found\r\nContent-Type: application/json\r\n\
+                      Content-Length: 2\r\nConnection: close\r\n\r\n{}",
+                );
+            }
+        });
+
+        Self {
+            url,
+            requests,
+        }
+    }
+
+    /// Constructs a Serenity [`Context`][SerenityContext] like [`context`], whose HTTP
+    /// client sends its requests to this server.
+    ///
+    /// # Panics
+    ///
+    /// Panics if Serenity fails to construct the HTTP client.
+    pub async fn context(&self) -> SerenityContext {
+        let http = HttpBuilder::new("")
+            .proxy(self.url.as_str())
+            .expect("failed to parse the URL of the mock server")
+            .ratelimiter_disabled(true)
+            .await
+            .expect("failed to construct the HTTP client");
+
+        SerenityContext {
+            http: Arc::new(http),
+            ..context()
+        }
+    }
+
+    /// Returns the requests received so far, in the order they were received.
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// Reads a request from the stream, returning `None` if it is malformed.
+fn read_request(stream: &TcpStream) -> Option<MockRequest> {
+    let mut reader = BufReader::new(stream);
+
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let path = parts.next()?.to_string();
+
+    let mut length = 0;
+
+    loop {
+        line.clear();
+        reader.read_line(&mut line).ok()?;
+
+        let header = line.trim_end();
+
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().ok()?;
+            }
+        }
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).ok()?;
+
+    Some(MockRequest {
+        method,
+        path,
+        body: String::from_utf8(body).ok()?,
+    })
+}
+
+/// Constructs a [`Role`] of the guild `1` with the identifier and the name.
+///
+/// # Panics
+///
+/// Panics if Serenity fails to deserialize the role.
+pub fn role<I, N>(id: I, name: N) -> Role
+where
+    I: Into<RoleId>,
+    N: Into<String>,
+{
+    let payload = json!({
+        "id": id.into().to_string(),
+        "guild_id": "1",
+        "name": name.into(),
+        "color": 0,
+        "hoist": false,
+        "managed": false,
+        "mentionable": false,
+        "permissions": "0",
+        "position": 0,
+    });
+
+    serde_json::from_value(payload).expect("failed to deserialize a mock role")
+}
+
+/// Constructs a [`Member`] of the guild `1` with the identifier, the username and
+/// the discriminator of its user, and its nickname.
+///
+/// # Panics
+///
+/// Panics if Serenity fails to deserialize the member.
+pub fn member<I, N>(id: I, name: N, discriminator: u16, nick: Option<&str>) -> Member
+where
+    I: Into<UserId>,
+    N: Into<String>,
+{
+    let payload = json!({
+        "guild_id": "1",
+        "user": {
+            "id": id.into().to_string(),
+            "username": name.into(),
+            "discriminator": format!("{:04}", discriminator),
+            "avatar": null,
+            "bot": false,
+        },
+        "nick": nick,
+        "roles": [],
+        "joined_at": "2021-01-01T00:00:00+00:00",
+        "deaf": false,
+        "mute": false,
+    });
+
+    serde_json::from_value(payload).expect("failed to deserialize a mock member")
+}
+
+/// A builder type for creating a mock [`Message`].
+///
+/// By default, the message is sent by the user `1` in the channel `1` of the guild `1`.
+#[derive(Debug, Clone)]
+pub struct MessageBuilder {
+    id: MessageId,
+    content: String,
+    author_id: UserId,
+    author_name: String,
+    bot: bool,
+    channel_id: ChannelId,
+    guild_id: Option<GuildId>,
+    attachments: Vec<String>,
+    stickers: Vec<String>,
+    embeds: Vec<String>,
+    member_roles: Option<Vec<RoleId>>,
+}
+
+impl MessageBuilder {
+    /// Constructs a new instance of the builder.
+    ///
+    /// Argument is the content of the message.
+    pub fn new<I>(content: I) -> Self
+    where
+        I: Into<String>,
+    {
+        Self {
+            id: MessageId(1),
+            content: content.into(),
+            author_id: UserId(1),
+            author_name: "user".to_string(),
+            bot: false,
+            channel_id: ChannelId(1),
+            guild_id: Some(GuildId(1)),
+            attachments: Vec::new(),
+            stickers: Vec::new(),
+            embeds: Vec::new(),
+            member_roles: None,
+        }
+    }
+
+    /// Assigns the identifier of the message.
+    pub fn id<I>(mut self, id: I) -> Self
+    where
+        I: Into<MessageId>,
+    {
+        self.id = id.into();
+        self
+    }
+
+    /// Assigns the content of the message.
+    pub fn content<I>(mut self, content: I) -> Self
+    where
+        I: Into<String>,
+    {
+        self.content = content.into();
+        self
+    }
+
+    /// Assigns the identifier of the author of the message.
+    pub fn author<I>(mut self, id: I) -> Self
+    where
+        I: Into<UserId>,
+    {
+        self.author_id = id.into();
+        self
+    }
+
+    /// Assigns the name of the author of the message.
+    pub fn author_name<I>(mut self, name: I) -> Self
+    where
+        I: Into<String>,
+    {
+        self.author_name = name.into();
+        self
+    }
+
+    /// Assigns a boolean indicating whether the author of the message is a bot.
+    pub fn bot(mut self, b: bool) -> Self {
+        self.bot = b;
+        self
+    }
+
+    /// Assigns the identifier of the channel the message was sent in.
+    pub fn channel<I>(mut self, id: I) -> Self
+    where
+        I: Into<ChannelId>,
+    {
+        self.channel_id = id.into();
+        self
+    }
+
+    /// Assigns the identifier of the guild the message was sent in.
+    ///
+    /// If `None`, the message is sent in a direct message channel.
+    pub fn guild<I>(mut self, id: Option<I>) -> Self
+    where
+        I: Into<GuildId>,
+    {
+        self.guild_id = id.map(Into::into);
+        self
+    }
+
+    /// Assigns an attachment to the message.
+    ///
+    /// The attachment is added to the list of attachments. Argument is its file name.
+    pub fn attachment<I>(mut self, filename: I) -> Self
+    where
+        I: Into<String>,
+    {
+        self.attachments.push(filename.into());
+        self
+    }
+
+    /// Assigns a sticker to the message.
+    ///
+    /// The sticker is added to the list of stickers. Argument is its name.
+    pub fn sticker<I>(mut self, name: I) -> Self
+    where
+        I: Into<String>,
+    {
+        self.stickers.push(name.into());
+        self
+    }
+
+    /// Assigns an embed to the message.
+    ///
+    /// The embed is added to the list of embeds. Argument is its title.
+    pub fn embed<I>(mut self, title: I) -> Self
+    where
+        I: Into<String>,
+    {
+        self.embeds.push(title.into());
+        self
+    }
+
+    /// Assigns the roles of the author of the message in the guild.
+    ///
+    /// By default, the message does not contain the member of the author, as if
+    /// it had been fetched rather than received from the gateway.
+    pub fn member_roles<I, R>(mut self, roles: I) -> Self
+    where
+        I: IntoIterator<Item = R>,
+        R: Into<RoleId>,
+    {
+        self.member_roles = Some(roles.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Complete building a message.
+    ///
+    /// # Panics
+    ///
+    /// Panics if Serenity fails to deserialize the message.
+    pub fn build(self) -> Message {
+        let attachments = self
+            .attachments
+            .iter()
+            .enumerate()
+            .map(|(i, filename)| {
+                let id = i + 1;
+                let url = format!(
+                    "https://cdn.discordapp.com/attachments/{}/{}/{}",
+                    self.channel_id, id, filename
+                );
+
+                json!({
+                    "id": id.to_string(),
+                    "filename": filename,
+                    "size": 0,
+                    "url": url,
+                    "proxy_url": url,
+                    "height": null,
+                    "width": null,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let sticker_items = self
+            .stickers
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                json!({
+                    "id": (i + 1).to_string(),
+                    "name": name,
+                    "format_type": 1,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let embeds = self
+            .embeds
+            .iter()
+            .map(|title| {
+                json!({
+                    "title": title,
+                    "type": "rich",
+                    "color": 0,
+                    "fields": [],
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let member = self.member_roles.as_ref().map(|roles| {
+            json!({
+                "roles": roles.iter().map(|id| id.to_string()).collect::<Vec<_>>(),
+                "joined_at": "2021-01-01T00:00:00+00:00",
+                "deaf": false,
+                "mute": false,
+                "nick": null,
+                "pending": false,
+            })
+        });
+
+        let payload = json!({
+            "id": self.id.to_string(),
+            "channel_id": self.channel_id.to_string(),
+            "guild_id": self.guild_id.map(|id| id.to_string()),
+            "author": {
+                "id": self.author_id.to_string(),
+                "username": self.author_name,
+                "discriminator": "0001",
+                "avatar": null,
+                "bot": self.bot,
+            },
+            "content": self.content,
+            "timestamp": "2021-01-01T00:00:00+00:00",
+            "edited_timestamp": null,
+            "tts": false,
+            "mention_everyone": false,
+            "mentions": [],
+            "mention_roles": [],
+            "attachments": attachments,
+            "embeds": embeds,
+            "sticker_items": sticker_items,
+            "member": member,
+            "reactions": [],
+            "pinned": false,
+            "type": 0,
+        });
+
+        serde_json::from_value(payload).expect("failed to deserialize a mock message")
+    }
+}
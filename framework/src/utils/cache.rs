@@ -0,0 +1,82 @@
+//! Functions for resolving data from the cache, with a fallback to HTTP.
+//!
+//! The cache of a bot may be missing data, such as when the bot has just connected,
+//! or when the relevant gateway intents are disabled. These functions try the cache
+//! first, and only make a request to Discord if the cache does not contain the data.
+
+use std::future::Future;
+
+use serenity::client::Context as SerenityContext;
+use serenity::model::channel::GuildChannel;
+use serenity::model::id::{ChannelId, UserId};
+use serenity::model::permissions::Permissions;
+
+/// Resolves data from the cache, or from HTTP if the cache does not contain it.
+///
+/// The `http` future is only awaited if the `cache` future returns `None`.
+///
+/// # Errors
+///
+/// Returns the error of the `http` future if it fails.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity::futures::executor::block_on;
+/// use serenity_framework::utils::cache_or_http;
+///
+/// // The cache contains the data, so the request is not made.
+/// let hit = block_on(cache_or_http(async { Some(1) }, async { panic!("requested") }));
+/// assert_eq!(hit.unwrap(), 1);
+///
+/// // The cache is missing the data, so the request is made.
+/// let miss = block_on(cache_or_http(async { None }, async { Ok(2) }));
+/// assert_eq!(miss.unwrap(), 2);
+/// ```
+pub async fn cache_or_http<T, C, H>(cache: C, http: H) -> serenity::Result<T>
+where
+    C: Future<Output = Option<T>>,
+    H: Future<Output = serenity::Result<T>>,
+{
+    match cache.await {
+        Some(value) => Ok(value),
+        None => http.await,
+    }
+}
+
+/// Resolves a guild channel from the cache, or from HTTP if the cache does not contain it.
+///
+/// Returns `None` if the channel is not a guild channel.
+///
+/// # Errors
+///
+/// Returns an error if the request to Discord fails.
+pub async fn guild_channel(
+    ctx: &SerenityContext,
+    id: ChannelId,
+) -> serenity::Result<Option<GuildChannel>> {
+    cache_or_http(async { ctx.cache.guild_channel(id).await.map(Some) }, async {
+        ctx.http.get_channel(id.0).await.map(|channel| channel.guild())
+    })
+    .await
+}
+
+/// Computes the permissions of a user in a guild channel from the cache, or from HTTP
+/// if the cache does not contain the guild or the member.
+///
+/// # Errors
+///
+/// Returns an error if a request to Discord fails.
+pub async fn permissions_in(
+    ctx: &SerenityContext,
+    channel: &GuildChannel,
+    user_id: UserId,
+) -> serenity::Result<Permissions> {
+    cache_or_http(async { channel.permissions_for_user(&ctx.cache, user_id).await.ok() }, async {
+        let guild = ctx.http.get_guild(channel.guild_id.0).await?;
+        let member = ctx.http.get_member(channel.guild_id.0, user_id.0).await?;
+
+        guild.user_permissions_in(channel, &member)
+    })
+    .await
+}
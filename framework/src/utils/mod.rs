@@ -1,9 +1,21 @@
 //! A set of abstraction utilities used by the framework to simplify its code.
 //!
 //! Usable outside of the framework.
+//!
+//! The [`id_map`], [`segments`], [`text`] and [`tokenizer`] modules are pure string and
+//! collection processing, and are available without the `framework` feature. This allows
+//! sharing the syntax of commands with programs that do not depend on Serenity.
 
+#[cfg(feature = "framework")]
+pub mod cache;
 pub mod id_map;
 pub mod segments;
+pub mod text;
+pub mod tokenizer;
 
+#[cfg(feature = "framework")]
+pub use cache::*;
 pub use id_map::*;
 pub use segments::*;
+pub use text::*;
+pub use tokenizer::*;
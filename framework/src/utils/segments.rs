@@ -5,6 +5,8 @@
 
 use std::borrow::Cow;
 
+use crate::utils::{DefaultTokenizer, QuoteError, Tokenizer};
+
 /// Returns the index to the end of a segment in the source.
 ///
 /// If the delimiter could not be found in the source, the length of the source
@@ -68,6 +70,48 @@ pub fn segment_split<'a>(src: &'a str, delimiter: &str) -> Option<(&'a str, &'a
     }
 }
 
+/// Returns the index to the start of the last segment in the source.
+///
+/// If the delimiter could not be found in the source, `0` is returned instead.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_framework::utils::segment_index_from_end;
+///
+/// assert_eq!(segment_index_from_end("hello world", " "), 6);
+/// assert_eq!(segment_index_from_end("world", " "), 0);
+/// assert_eq!(segment_index_from_end("héllo wörld", " "), "héllo ".len());
+/// ```
+pub fn segment_index_from_end(src: &str, delimiter: &str) -> usize {
+    src.rfind(delimiter).map_or(0, |index| index + delimiter.len())
+}
+
+/// Returns the rest of the source before the delimiter and the last segment.
+///
+/// If the delimiter appears many times before the segment, all instances of it
+/// are removed.
+///
+/// If the source is empty, `None` is returned.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_framework::utils::rsegment_split;
+///
+/// assert_eq!(rsegment_split("new name   123", " "), Some(("new name", "123")));
+/// assert_eq!(rsegment_split("123", " "), Some(("", "123")));
+/// assert_eq!(rsegment_split("", " "), None);
+/// ```
+pub fn rsegment_split<'a>(src: &'a str, delimiter: &str) -> Option<(&'a str, &'a str)> {
+    if src.is_empty() {
+        None
+    } else {
+        let (rest, segment) = src.split_at(segment_index_from_end(src, delimiter));
+        Some((rest.trim_end_matches(delimiter), segment))
+    }
+}
+
 /// An iterator type that splits a string into segments using a delimiter.
 ///
 /// It returns [`Cow`] values to handle case sensitivity.
@@ -94,12 +138,31 @@ pub fn segment_split<'a>(src: &'a str, delimiter: &str) -> Option<(&'a str, &'a
 /// let mut iter = Segments::new("hElLo WOrLd", " ", true);
 ///
 /// assert_eq!(iter.next(), Some(Cow::Owned("hello".to_string())));
+/// // Only the returned segments are lowercased. The source retains its casing.
+/// assert_eq!(iter.source(), "WOrLd");
 /// assert_eq!(iter.next(), Some(Cow::Owned("world".to_string())));
 /// assert_eq!(iter.next(), None);
 /// ```
 ///
+/// The [size hint] is an upper bound based on the occurrences of the delimiter,
+/// which allows collecting the segments without reallocating:
+///
+/// ```rust
+/// use serenity_framework::utils::Segments;
+///
+/// for src in &["", "hello", "hello world", "hello   world ", " hello"] {
+///     let iter = Segments::new(src, " ", false);
+///     let (_, upper) = iter.size_hint();
+///
+///     assert!(iter.count() <= upper.unwrap());
+/// }
+///
+/// assert_eq!(Segments::new("a b c", " ", false).size_hint(), (0, Some(3)));
+/// ```
+///
 /// [`Cow`]: std::borrow::Cow
 /// [`case_insensitive`]: Segments::case_insensitive
+/// [size hint]: Iterator::size_hint
 #[derive(Debug, Clone)]
 pub struct Segments<'a> {
     src: &'a str,
@@ -142,21 +205,50 @@ impl<'a> Segments<'a> {
     pub fn is_empty(&self) -> bool {
         self.src.is_empty()
     }
+
+    /// Returns the next segment without advancing the iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::borrow::Cow;
+    ///
+    /// use serenity_framework::utils::Segments;
+    ///
+    /// let mut iter = Segments::new("hello world", " ", false);
+    ///
+    /// assert_eq!(iter.peek(), Some(Cow::Borrowed("hello")));
+    /// assert_eq!(iter.peek(), Some(Cow::Borrowed("hello")));
+    /// assert_eq!(iter.source(), "hello world");
+    /// assert_eq!(iter.next(), Some(Cow::Borrowed("hello")));
+    /// assert_eq!(iter.peek(), Some(Cow::Borrowed("world")));
+    /// assert_eq!(iter.next(), Some(Cow::Borrowed("world")));
+    /// assert_eq!(iter.peek(), None);
+    /// ```
+    pub fn peek(&self) -> Option<Cow<'a, str>> {
+        let segment = segment(self.src, self.delimiter)?;
+
+        Some(if self.case_insensitive {
+            Cow::Owned(segment.to_lowercase())
+        } else {
+            Cow::Borrowed(segment)
+        })
+    }
 }
 
 impl<'a> Iterator for Segments<'a> {
     type Item = Cow<'a, str>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let (segment, rest) = segment_split(self.src, self.delimiter)?;
+        let segment = self.peek()?;
 
-        self.src = rest;
+        self.src = segment_split(self.src, self.delimiter)?.1;
 
-        Some(if self.case_insensitive {
-            Cow::Owned(segment.to_lowercase())
-        } else {
-            Cow::Borrowed(segment)
-        })
+        Some(segment)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, segment_count_bound(self.src, self.delimiter))
     }
 }
 
@@ -218,6 +310,21 @@ pub fn quoted_segment(src: &str) -> Option<&str> {
     quoted_segment_split(src).map(|(seg, _)| seg)
 }
 
+/// Returns an upper bound of the number of segments in the source.
+///
+/// Every segment but the last ends at an occurrence of the delimiter, so there are at
+/// most one more segments than occurrences. An empty delimiter yields an endless
+/// number of segments, for which no bound exists.
+pub(crate) fn segment_count_bound(src: &str, delimiter: &str) -> Option<usize> {
+    if src.is_empty() {
+        Some(0)
+    } else if delimiter.is_empty() {
+        None
+    } else {
+        Some(src.matches(delimiter).count() + 1)
+    }
+}
+
 /// Returns an argument segment and the rest of the source.
 ///
 /// An argument segment is either [a quoted segment][qseg]
@@ -280,35 +387,77 @@ pub fn argument_segment<'a>(src: &'a str, delimiter: &str) -> Option<&'a str> {
     argument_segment_split(src, delimiter).map(|(seg, _)| seg)
 }
 
-/// An iterator type that splits a string into [argument segments][aseg] using a delimiter and quotes.
+/// An iterator type that splits a string into tokens using a [tokenizer].
+///
+/// By default, the tokens are [argument segments][aseg], split using a delimiter
+/// and quotes. Tokens are returned as [`Cow`] values, as a tokenizer may need to
+/// modify a segment, such as to remove escapes.
 ///
 /// # Examples
 ///
 /// ```rust
 /// // Used example strings are from the YouTube video https://www.youtube.com/watch?v=1edPxKqiptw
-/// use serenity_framework::utils::ArgumentSegments;
+/// use std::borrow::Cow;
+///
+/// use serenity_framework::utils::{ArgumentSegments, ShellTokenizer};
 ///
 /// let mut iter = ArgumentSegments::new("Marriage, \"foliage, mirage\", \"and age.", ", ");
 ///
-/// assert_eq!(iter.next(), Some("Marriage"));
-/// assert_eq!(iter.next(), Some("foliage, mirage"));
-/// assert_eq!(iter.next(), Some("and age."));
+/// assert_eq!(iter.next(), Some(Cow::Borrowed("Marriage")));
+/// assert_eq!(iter.next(), Some(Cow::Borrowed("foliage, mirage")));
+/// assert_eq!(iter.next(), Some(Cow::Borrowed("and age.")));
 /// assert_eq!(iter.next(), None);
+///
+/// let mut iter =
+///     ArgumentSegments::with_tokenizer(r#""\"Hush\" and bush" too"#, " ", &ShellTokenizer);
+///
+/// assert_eq!(iter.next(), Some(Cow::Owned("\"Hush\" and bush".to_string())));
+/// assert_eq!(iter.next(), Some(Cow::Borrowed("too")));
+/// assert_eq!(iter.next(), None);
+/// ```
+///
+/// The [size hint] is provided by the [tokenizer][tok]. The framework's tokenizers give
+/// an upper bound based on the occurrences of the delimiter and of quotation marks:
+///
+/// ```rust
+/// use serenity_framework::utils::{ArgumentSegments, ShellTokenizer};
+///
+/// for src in &["", "a", "a b  c", "\"a b\"c", "\"a\"\"b\" \"c", r#"a\ b \"c"#] {
+///     let iter = ArgumentSegments::new(src, " ");
+///     let (_, upper) = iter.size_hint();
+///     assert!(iter.count() <= upper.unwrap());
+///
+///     let iter = ArgumentSegments::with_tokenizer(src, " ", &ShellTokenizer);
+///     let (_, upper) = iter.size_hint();
+///     assert!(iter.count() <= upper.unwrap());
+/// }
 /// ```
 ///
+/// [size hint]: Iterator::size_hint
+/// [tok]: crate::utils::Tokenizer::size_hint
+///
+/// [tokenizer]: crate::utils::Tokenizer
 /// [aseg]: argument_segment_split
+/// [`Cow`]: std::borrow::Cow
 #[derive(Debug, Clone)]
 pub struct ArgumentSegments<'a> {
     src: &'a str,
     delimiter: &'a str,
+    tokenizer: &'a dyn Tokenizer,
 }
 
 impl<'a> ArgumentSegments<'a> {
-    /// Creates a new `ArgumentSegments` instance.
+    /// Creates a new `ArgumentSegments` instance using the [`DefaultTokenizer`].
     pub fn new(src: &'a str, delimiter: &'a str) -> Self {
+        Self::with_tokenizer(src, delimiter, &DefaultTokenizer)
+    }
+
+    /// Creates a new `ArgumentSegments` instance using the specified tokenizer.
+    pub fn with_tokenizer(src: &'a str, delimiter: &'a str, tokenizer: &'a dyn Tokenizer) -> Self {
         Self {
             src,
             delimiter,
+            tokenizer,
         }
     }
 
@@ -328,20 +477,148 @@ impl<'a> ArgumentSegments<'a> {
         self.delimiter
     }
 
+    /// Returns the tokenizer that is used to split the source string into segments.
+    pub fn tokenizer(&self) -> &'a dyn Tokenizer {
+        self.tokenizer
+    }
+
     /// Returns a boolean indicating that the source string is empty.
     pub fn is_empty(&self) -> bool {
         self.src.is_empty()
     }
+
+    /// Returns the next argument segment without advancing the iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::borrow::Cow;
+    ///
+    /// use serenity_framework::utils::ArgumentSegments;
+    ///
+    /// let mut iter = ArgumentSegments::new("\"hello world\" again", " ");
+    ///
+    /// assert_eq!(iter.peek(), Some(Cow::Borrowed("hello world")));
+    /// assert_eq!(iter.peek(), Some(Cow::Borrowed("hello world")));
+    /// assert_eq!(iter.next(), Some(Cow::Borrowed("hello world")));
+    /// assert_eq!(iter.peek(), Some(Cow::Borrowed("again")));
+    /// assert_eq!(iter.next(), Some(Cow::Borrowed("again")));
+    /// assert_eq!(iter.peek(), None);
+    /// ```
+    pub fn peek(&self) -> Option<Cow<'a, str>> {
+        self.tokenizer.token_split(self.src, self.delimiter).map(|(segment, _)| segment)
+    }
+
+    /// Returns the next argument segment, or an error if it starts with a quotation
+    /// mark that is never terminated.
+    ///
+    /// Unlike [`next`], an unterminated quotation mark is not treated as a segment
+    /// spanning the rest of the source. If an error is returned, the iterator is not
+    /// advanced. Whether a quotation mark is terminated is decided by the
+    /// [tokenizer][tok].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::borrow::Cow;
+    ///
+    /// use serenity_framework::utils::{ArgumentSegments, QuoteError};
+    ///
+    /// let mut iter = ArgumentSegments::new("\"hello world\" \"again", " ");
+    ///
+    /// assert_eq!(iter.try_next(), Ok(Some(Cow::Borrowed("hello world"))));
+    /// assert_eq!(iter.try_next(), Err(QuoteError));
+    /// assert_eq!(iter.source(), "\"again");
+    ///
+    /// let mut iter = ArgumentSegments::new("hello", " ");
+    ///
+    /// assert_eq!(iter.try_next(), Ok(Some(Cow::Borrowed("hello"))));
+    /// assert_eq!(iter.try_next(), Ok(None));
+    /// ```
+    ///
+    /// [`next`]: Iterator::next
+    /// [tok]: crate::utils::Tokenizer::try_token_split
+    pub fn try_next(&mut self) -> Result<Option<Cow<'a, str>>, QuoteError> {
+        let (segment, rest) = match self.tokenizer.try_token_split(self.src, self.delimiter)? {
+            Some(pair) => pair,
+            None => return Ok(None),
+        };
+
+        self.src = rest;
+
+        Ok(Some(segment))
+    }
+
+    /// Runs the function on a copy of the iterator, and advances the iterator to the
+    /// position of the copy only if the function succeeds.
+    ///
+    /// If the function returns `None`, the iterator is left unchanged. This allows
+    /// trying one way of parsing the segments, and trying another on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::borrow::Cow;
+    ///
+    /// use serenity_framework::utils::ArgumentSegments;
+    ///
+    /// let mut iter = ArgumentSegments::new("12 apples", " ");
+    ///
+    /// let digit = iter.try_parse(|iter| iter.next()?.parse::<u32>().ok().filter(|n| *n < 10));
+    /// assert_eq!(digit, None);
+    /// assert_eq!(iter.source(), "12 apples");
+    ///
+    /// let number = iter.try_parse(|iter| iter.next()?.parse::<u32>().ok());
+    /// assert_eq!(number, Some(12));
+    /// assert_eq!(iter.next(), Some(Cow::Borrowed("apples")));
+    /// ```
+    pub fn try_parse<T, F>(&mut self, f: F) -> Option<T>
+    where
+        F: FnOnce(&mut Self) -> Option<T>,
+    {
+        let mut lookahead = self.clone();
+        let value = f(&mut lookahead)?;
+
+        *self = lookahead;
+
+        Some(value)
+    }
 }
 
 impl<'a> Iterator for ArgumentSegments<'a> {
-    type Item = &'a str;
+    type Item = Cow<'a, str>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let (segment, rest) = argument_segment_split(self.src, self.delimiter)?;
+        let (segment, rest) = self.tokenizer.token_split(self.src, self.delimiter)?;
 
         self.src = rest;
 
         Some(segment)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.tokenizer.size_hint(self.src, self.delimiter)
+    }
+}
+
+/// Returns an iterator over the items of a list contained in a single segment.
+///
+/// The items are separated by the `separator` and trimmed of whitespace.
+/// Empty items, such as after a trailing separator, are skipped.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_framework::utils::list_segments;
+///
+/// assert_eq!(list_segments("", ",").count(), 0);
+/// assert_eq!(list_segments("1", ",").collect::<Vec<_>>(), vec!["1"]);
+/// assert_eq!(list_segments("1,2,3", ",").collect::<Vec<_>>(), vec!["1", "2", "3"]);
+/// assert_eq!(list_segments("1,2,", ",").collect::<Vec<_>>(), vec!["1", "2"]);
+/// ```
+pub fn list_segments<'a: 'b, 'b>(
+    src: &'a str,
+    separator: &'b str,
+) -> impl Iterator<Item = &'a str> + 'b {
+    src.split(separator).map(str::trim).filter(|item| !item.is_empty())
 }
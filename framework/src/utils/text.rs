@@ -0,0 +1,59 @@
+//! Functions for preparing text to be sent to Discord.
+//!
+//! Discord limits the length of the content of messages and of the fields of
+//! embeds. Text generated for help and usage of commands may exceed these limits.
+
+/// Returns the source truncated to at most `max_bytes` bytes, with the ellipsis
+/// appended if the source was truncated.
+///
+/// The source is cut on a character boundary, so that no character is split in
+/// half. The ellipsis is included in the byte limit. If the ellipsis alone exceeds
+/// the limit, it is truncated in turn.
+///
+/// If the source does not exceed the limit, it is returned as is.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_framework::utils::truncate;
+///
+/// assert_eq!(truncate("hello", 5, "..."), "hello");
+/// assert_eq!(truncate("hello world", 8, "..."), "hello...");
+/// assert_eq!(truncate("hello world", 8, ""), "hello wo");
+///
+/// // `é` and `…` are two and three bytes long. Without the ellipsis, the
+/// // limit falls in between the bytes of `é`.
+/// assert_eq!(truncate("café au lait", 7, "…"), "caf…");
+/// assert_eq!(truncate("café au lait", 8, "…"), "café…");
+///
+/// // The limit is smaller than the ellipsis.
+/// assert_eq!(truncate("hello world", 2, "..."), "..");
+/// assert_eq!(truncate("hello world", 2, "…"), "");
+/// ```
+pub fn truncate(src: &str, max_bytes: usize, ellipsis: &str) -> String {
+    if src.len() <= max_bytes {
+        return src.to_string();
+    }
+
+    if ellipsis.len() >= max_bytes {
+        return ellipsis[..floor_char_boundary(ellipsis, max_bytes)].to_string();
+    }
+
+    let end = floor_char_boundary(src, max_bytes - ellipsis.len());
+
+    let mut truncated = String::with_capacity(end + ellipsis.len());
+    truncated.push_str(&src[..end]);
+    truncated.push_str(ellipsis);
+
+    truncated
+}
+
+/// Returns the largest index not exceeding `index` that is on a character boundary
+/// of the source.
+fn floor_char_boundary(src: &str, index: usize) -> usize {
+    if index >= src.len() {
+        return src.len();
+    }
+
+    (0..=index).rev().find(|&i| src.is_char_boundary(i)).unwrap_or_default()
+}
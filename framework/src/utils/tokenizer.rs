@@ -0,0 +1,261 @@
+//! Functions and types for splitting arguments into *tokens*.
+//!
+//! A token is an argument segment after the syntax of the [tokenizer] has been
+//! applied, such as the removal of quotation marks. Tokens are produced by
+//! [`ArgumentSegments`], which delegates to the tokenizer of a command.
+//!
+//! [tokenizer]: Tokenizer
+//! [`ArgumentSegments`]: crate::utils::ArgumentSegments
+
+use std::borrow::Cow;
+use std::error::Error as StdError;
+use std::fmt;
+
+use crate::utils::{argument_segment_split, segment_count_bound};
+
+/// A type that defines the syntax of the arguments of a command.
+///
+/// A tokenizer splits the source of the arguments into tokens one at a time.
+/// The framework provides the [`DefaultTokenizer`] and the [`ShellTokenizer`],
+/// but any type implementing this trait may be [assigned to a command][cmd].
+///
+/// [cmd]: crate::command::CommandBuilder::tokenizer
+pub trait Tokenizer: fmt::Debug + Send + Sync {
+    /// Returns the next token of the source and the rest of the source after it.
+    ///
+    /// Instances of the `delimiter` that follow the token should be removed from
+    /// the rest of the source.
+    ///
+    /// If the source is empty, `None` must be returned.
+    fn token_split<'a>(&self, src: &'a str, delimiter: &str) -> Option<(Cow<'a, str>, &'a str)>;
+
+    /// Returns the next token of the source and the rest of the source after it, or
+    /// an error if the token starts with a quotation mark that is never terminated.
+    ///
+    /// By default, this never fails and returns the result of [`token_split`].
+    ///
+    /// [`token_split`]: Self::token_split
+    fn try_token_split<'a>(
+        &self,
+        src: &'a str,
+        delimiter: &str,
+    ) -> Result<Option<(Cow<'a, str>, &'a str)>, QuoteError> {
+        Ok(self.token_split(src, delimiter))
+    }
+
+    /// Returns the bounds on the number of tokens in the source, as in
+    /// [`Iterator::size_hint`].
+    ///
+    /// By default, no bounds are known and `(0, None)` is returned. The upper bound,
+    /// if given, must not be less than the actual number of tokens.
+    fn size_hint(&self, _src: &str, _delimiter: &str) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
+
+/// Returns an upper bound of the number of tokens in the source for tokenizers whose
+/// tokens end at the delimiter, at a closing quotation mark, or at the end of the source.
+///
+/// A quoted token consumes two quotation marks, so there are at most half as many
+/// quoted tokens as quotation marks.
+fn token_count_bound(src: &str, delimiter: &str) -> Option<usize> {
+    segment_count_bound(src, delimiter).map(|bound| bound + src.matches('"').count() / 2)
+}
+
+/// An error returned when a token starts with a quotation mark that is never terminated.
+///
+/// Refer to [`Tokenizer::try_token_split`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuoteError;
+
+impl fmt::Display for QuoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unterminated quotation mark")
+    }
+}
+
+impl StdError for QuoteError {}
+
+/// The tokenizer used by commands by default.
+///
+/// Its tokens are [argument segments][aseg]: They are separated by the delimiter,
+/// unless they are encompassed by quotation marks.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_framework::utils::{DefaultTokenizer, QuoteError, Tokenizer};
+///
+/// let tokenizer = DefaultTokenizer;
+///
+/// assert_eq!(tokenizer.token_split("", " "), None);
+/// assert_eq!(tokenizer.token_split("a b", " "), Some(("a".into(), "b")));
+/// assert_eq!(tokenizer.token_split("\"a b\" c", " "), Some(("a b".into(), "c")));
+///
+/// // An unterminated quote spans the rest of the source, unless it is rejected.
+/// assert_eq!(tokenizer.token_split("\"a b c", " "), Some(("a b c".into(), "")));
+/// assert_eq!(tokenizer.try_token_split("\"a b c", " "), Err(QuoteError));
+///
+/// // The upper bound counts the delimiters and the quotation marks.
+/// assert_eq!(tokenizer.size_hint("\"a b\"c d", " "), (0, Some(4)));
+/// ```
+///
+/// [aseg]: crate::utils::argument_segment_split
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultTokenizer;
+
+impl Tokenizer for DefaultTokenizer {
+    fn token_split<'a>(&self, src: &'a str, delimiter: &str) -> Option<(Cow<'a, str>, &'a str)> {
+        argument_segment_split(src, delimiter).map(|(token, rest)| (Cow::Borrowed(token), rest))
+    }
+
+    fn try_token_split<'a>(
+        &self,
+        src: &'a str,
+        delimiter: &str,
+    ) -> Result<Option<(Cow<'a, str>, &'a str)>, QuoteError> {
+        if src.starts_with('"') && !src[1..].contains('"') {
+            return Err(QuoteError);
+        }
+
+        Ok(self.token_split(src, delimiter))
+    }
+
+    fn size_hint(&self, src: &str, delimiter: &str) -> (usize, Option<usize>) {
+        (0, token_count_bound(src, delimiter))
+    }
+}
+
+/// A tokenizer that supports escaping with backslashes, similar to shells.
+///
+/// Like the [`DefaultTokenizer`], tokens are separated by the delimiter, unless they
+/// are encompassed by quotation marks. In addition, a backslash escapes a following
+/// quotation mark, backslash or delimiter, which are then part of the token. Other
+/// backslashes are preserved.
+///
+/// A token is only borrowed from the source if it contains no escapes.
+///
+/// # Notes
+///
+/// Rest arguments take the remaining source as is, so escapes are not removed
+/// from them.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_framework::utils::{QuoteError, ShellTokenizer, Tokenizer};
+///
+/// let tokenizer = ShellTokenizer;
+///
+/// assert_eq!(tokenizer.token_split("", " "), None);
+/// assert_eq!(tokenizer.token_split("a b", " "), Some(("a".into(), "b")));
+/// assert_eq!(
+///     tokenizer.token_split(r#""say \"hi\"" to them"#, " "),
+///     Some((r#"say "hi""#.into(), "to them"))
+/// );
+/// assert_eq!(tokenizer.token_split(r"a\ b c", " "), Some(("a b".into(), "c")));
+/// assert_eq!(tokenizer.token_split(r#"\"a b"#, " "), Some(("\"a".into(), "b")));
+/// assert_eq!(tokenizer.token_split(r"C:\dir\\ x", " "), Some((r"C:\dir\".into(), "x")));
+/// assert_eq!(
+///     tokenizer.token_split(r#""unclosed \" quote"#, " "),
+///     Some((r#"unclosed " quote"#.into(), ""))
+/// );
+/// assert_eq!(tokenizer.try_token_split(r#""unclosed \" quote"#, " "), Err(QuoteError));
+/// assert_eq!(
+///     tokenizer.try_token_split(r#""closed \"" x"#, " "),
+///     Ok(Some((r#"closed ""#.into(), "x")))
+/// );
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ShellTokenizer;
+
+impl ShellTokenizer {
+    /// Returns the next token, the rest of the source after it, and a boolean indicating
+    /// whether the token is either unquoted or its quotation mark is terminated.
+    fn split<'a>(src: &'a str, delimiter: &str) -> Option<(Cow<'a, str>, &'a str, bool)> {
+        if src.is_empty() {
+            return None;
+        }
+
+        let quoted = src.starts_with('"');
+        let body = if quoted { &src[1..] } else { src };
+
+        let mut token = String::new();
+        // The start of the part of the body that has not been copied into `token` yet.
+        let mut copied = 0;
+        let mut escaped = false;
+        let mut index = 0;
+
+        let end = loop {
+            let rest = &body[index..];
+
+            let c = match rest.chars().next() {
+                Some(c) => c,
+                None => break None,
+            };
+
+            if c == '\\' {
+                let next = &rest[1..];
+
+                let escape_len = if next.starts_with('"') || next.starts_with('\\') {
+                    1
+                } else if !quoted && !delimiter.is_empty() && next.starts_with(delimiter) {
+                    delimiter.len()
+                } else {
+                    0
+                };
+
+                if escape_len > 0 {
+                    token.push_str(&body[copied..index]);
+                    copied = index + 1;
+                    index += 1 + escape_len;
+                    escaped = true;
+
+                    continue;
+                }
+            } else if quoted && c == '"' {
+                break Some((index, index + 1));
+            } else if !quoted && !delimiter.is_empty() && rest.starts_with(delimiter) {
+                break Some((index, index));
+            }
+
+            index += c.len_utf8();
+        };
+
+        let (token_end, rest) = match end {
+            Some((token_end, rest_start)) => (token_end, &body[rest_start..]),
+            None => (body.len(), ""),
+        };
+
+        let token = if escaped {
+            token.push_str(&body[copied..token_end]);
+            Cow::Owned(token)
+        } else {
+            Cow::Borrowed(&body[..token_end])
+        };
+
+        Some((token, rest.trim_start_matches(delimiter), !quoted || end.is_some()))
+    }
+}
+
+impl Tokenizer for ShellTokenizer {
+    fn token_split<'a>(&self, src: &'a str, delimiter: &str) -> Option<(Cow<'a, str>, &'a str)> {
+        Self::split(src, delimiter).map(|(token, rest, _)| (token, rest))
+    }
+
+    fn try_token_split<'a>(
+        &self,
+        src: &'a str,
+        delimiter: &str,
+    ) -> Result<Option<(Cow<'a, str>, &'a str)>, QuoteError> {
+        match Self::split(src, delimiter) {
+            Some((_, _, false)) => Err(QuoteError),
+            Some((token, rest, true)) => Ok(Some((token, rest))),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self, src: &str, delimiter: &str) -> (usize, Option<usize>) {
+        (0, token_count_bound(src, delimiter))
+    }
+}
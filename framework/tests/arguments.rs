@@ -0,0 +1,639 @@
+//! Tests of parsing the arguments of commands.
+
+#![cfg(feature = "test-util")]
+
+use std::sync::Mutex;
+
+use serenity::futures::executor::block_on;
+use serenity::model::channel::{Attachment, Embed, Message};
+use serenity::model::guild::Member;
+use serenity::model::sticker::StickerItem;
+use serenity_framework::argument::{MemberArg, MemberParseError, NumRange};
+use serenity_framework::error::{ArgumentErrorKind, DispatchError, Error};
+use serenity_framework::prelude::*;
+use serenity_framework::test_util::{self, MessageBuilder};
+
+#[test]
+fn attachment_arguments() {
+    #[command]
+    async fn upload(
+        _ctx: FrameworkContext,
+        _msg: &Message,
+        #[attachment] file: Attachment,
+        name: String,
+        #[attachment] thumbnail: Option<Attachment>,
+    ) {
+        assert_eq!(file.filename, "cat.png");
+        assert_eq!(name, "whiskers");
+        assert!(thumbnail.is_none());
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(upload);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+
+    let msg = MessageBuilder::new("!upload whiskers").attachment("cat.png").build();
+    block_on(framework.dispatch(&ctx, &msg)).unwrap();
+
+    let msg = test_util::message("!upload whiskers");
+    let result = block_on(framework.dispatch(&ctx, &msg));
+    assert!(matches!(
+        result,
+        Err(Error::Dispatch(DispatchError::Argument {
+            position: 0,
+            kind: ArgumentErrorKind::Missing,
+            ..
+        }))
+    ));
+}
+
+#[test]
+fn argument_parse_failure() {
+    #[command]
+    async fn add(_ctx: FrameworkContext, _msg: &Message, a: u32, b: u32) -> CommandResult {
+        unreachable!("{} + {}", a, b)
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(add);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+
+    let result = block_on(framework.dispatch(&ctx, &test_util::message("!add 1 x")));
+    assert!(matches!(
+        result,
+        Err(Error::Dispatch(DispatchError::Argument {
+            position: 1,
+            kind: ArgumentErrorKind::Parse(_),
+            ..
+        }))
+    ));
+
+    let result = block_on(framework.dispatch(&ctx, &test_util::message("!add 1")));
+    assert!(matches!(
+        result,
+        Err(Error::Dispatch(DispatchError::Argument {
+            position: 1,
+            kind: ArgumentErrorKind::Missing,
+            ..
+        }))
+    ));
+}
+
+#[test]
+fn collect_errors() {
+    #[command(collect_errors)]
+    async fn add(_ctx: FrameworkContext, _msg: &Message, a: u32, b: u32, c: u32) {
+        assert!(a + b + c > 0);
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(add);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |content: &str| block_on(framework.dispatch(&ctx, &test_util::message(content)));
+
+    assert!(run("!add 1 2 3").is_ok());
+
+    let failures = match run("!add one 2 three") {
+        Err(Error::Dispatch(DispatchError::ArgumentErrors(failures))) => failures,
+        result => panic!("unexpected result: {:?}", result),
+    };
+
+    let names = failures.iter().map(|failure| failure.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, vec!["a", "c"]);
+    assert!(matches!(failures[0].kind, ArgumentErrorKind::Parse(_)));
+    assert_eq!(failures[1].position, 2);
+
+    // Missing arguments are reported alongside invalid ones.
+    match run("!add one") {
+        Err(Error::Dispatch(DispatchError::ArgumentErrors(failures))) => {
+            let kinds = failures.iter().map(|failure| &failure.kind).collect::<Vec<_>>();
+
+            assert_eq!(failures.len(), 3);
+            assert_eq!(kinds[1], &ArgumentErrorKind::Missing);
+            assert_eq!(kinds[2], &ArgumentErrorKind::Missing);
+        },
+        result => panic!("unexpected result: {:?}", result),
+    }
+}
+
+#[test]
+fn variadic_skip_invalid() {
+    static SUM: Mutex<Option<u32>> = Mutex::new(None);
+
+    #[command]
+    async fn sum(_ctx: FrameworkContext, _msg: &Message, numbers: Vec<u32>) {
+        *SUM.lock().unwrap() = Some(numbers.iter().sum());
+    }
+
+    #[command]
+    async fn lenient_sum(
+        _ctx: FrameworkContext,
+        _msg: &Message,
+        #[variadic(skip_invalid)] numbers: Vec<u32>,
+    ) {
+        *SUM.lock().unwrap() = Some(numbers.iter().sum());
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(sum).command(lenient_sum);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |content: &str| block_on(framework.dispatch(&ctx, &test_util::message(content)));
+
+    assert!(matches!(
+        run("!sum 4 x 8"),
+        Err(Error::Dispatch(DispatchError::Argument {
+            kind: ArgumentErrorKind::Parse(_),
+            ..
+        }))
+    ));
+    assert_eq!(SUM.lock().unwrap().take(), None);
+
+    assert!(run("!lenient_sum 4 x 8").is_ok());
+    assert_eq!(SUM.lock().unwrap().take(), Some(12));
+
+    assert!(run("!lenient_sum x y").is_ok());
+    assert_eq!(SUM.lock().unwrap().take(), Some(0));
+}
+
+#[test]
+fn variadic_max() {
+    static SUM: Mutex<Option<u32>> = Mutex::new(None);
+
+    #[command]
+    async fn sum(_ctx: FrameworkContext, _msg: &Message, #[variadic(max = 3)] numbers: Vec<u32>) {
+        *SUM.lock().unwrap() = Some(numbers.iter().sum());
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(sum);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |content: &str| block_on(framework.dispatch(&ctx, &test_util::message(content)));
+
+    assert!(run("!sum 1 2 3").is_ok());
+    assert_eq!(SUM.lock().unwrap().take(), Some(6));
+
+    assert!(matches!(
+        run("!sum 1 2 3 4"),
+        Err(Error::Dispatch(DispatchError::Argument {
+            kind: ArgumentErrorKind::TooMany(3),
+            ..
+        }))
+    ));
+    assert_eq!(SUM.lock().unwrap().take(), None);
+}
+
+#[test]
+fn optional_skip_sentinel() {
+    static ARGS: Mutex<Option<(Option<u32>, Option<u32>)>> = Mutex::new(None);
+
+    #[command]
+    async fn roll(
+        _ctx: FrameworkContext,
+        _msg: &Message,
+        #[optional(skip = "-")] dice: Option<u32>,
+        sides: Option<u32>,
+    ) {
+        *ARGS.lock().unwrap() = Some((dice, sides));
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(roll);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |content: &str| block_on(framework.dispatch(&ctx, &test_util::message(content)));
+
+    // The sentinel is consumed, and the next segment is given to the next argument.
+    assert!(run("!roll - 20").is_ok());
+    assert_eq!(ARGS.lock().unwrap().take(), Some((None, Some(20))));
+
+    assert!(run("!roll 2 20").is_ok());
+    assert_eq!(ARGS.lock().unwrap().take(), Some((Some(2), Some(20))));
+
+    assert!(run("!roll").is_ok());
+    assert_eq!(ARGS.lock().unwrap().take(), Some((None, None)));
+}
+
+#[test]
+fn num_range() {
+    static PICKED: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+    #[command]
+    async fn pick(_ctx: FrameworkContext, _msg: &Message, #[range] range: NumRange<u32>) {
+        *PICKED.lock().unwrap() = range.iter().collect();
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(pick);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |content: &str| block_on(framework.dispatch(&ctx, &test_util::message(content)));
+
+    assert!(run("!pick 1..4").is_ok());
+    assert_eq!(*PICKED.lock().unwrap(), vec![1, 2, 3]);
+
+    assert!(run("!pick 1..=4").is_ok());
+    assert_eq!(*PICKED.lock().unwrap(), vec![1, 2, 3, 4]);
+
+    assert!(run("!pick 2-3").is_ok());
+    assert_eq!(*PICKED.lock().unwrap(), vec![2, 3]);
+
+    assert!(matches!(
+        run("!pick 4-1"),
+        Err(Error::Dispatch(DispatchError::Argument {
+            kind: ArgumentErrorKind::Parse(_),
+            ..
+        }))
+    ));
+}
+
+#[test]
+fn list_argument() {
+    static IDS: Mutex<Option<Vec<u32>>> = Mutex::new(None);
+    static SIZE: Mutex<Option<(u32, u32)>> = Mutex::new(None);
+
+    #[command]
+    async fn ids(_ctx: FrameworkContext, _msg: &Message, #[list] ids: Vec<u32>) {
+        *IDS.lock().unwrap() = Some(ids);
+    }
+
+    #[command]
+    async fn tags(_ctx: FrameworkContext, _msg: &Message, #[list(sep = ";")] ids: Vec<u32>) {
+        *IDS.lock().unwrap() = Some(ids);
+    }
+
+    #[command]
+    async fn size(_ctx: FrameworkContext, _msg: &Message, #[list] size: (u32, u32)) {
+        *SIZE.lock().unwrap() = Some(size);
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(ids).command(tags).command(size);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |content: &str| block_on(framework.dispatch(&ctx, &test_util::message(content)));
+
+    assert!(matches!(
+        run("!ids"),
+        Err(Error::Dispatch(DispatchError::Argument {
+            position: 0,
+            kind: ArgumentErrorKind::Missing,
+            ..
+        }))
+    ));
+    assert_eq!(IDS.lock().unwrap().take(), None);
+
+    assert!(run("!ids ,").is_ok());
+    assert_eq!(IDS.lock().unwrap().take(), Some(vec![]));
+
+    assert!(run("!ids 1").is_ok());
+    assert_eq!(IDS.lock().unwrap().take(), Some(vec![1]));
+
+    assert!(run("!ids 1,2,").is_ok());
+    assert_eq!(IDS.lock().unwrap().take(), Some(vec![1, 2]));
+
+    assert!(run("!tags 1;2;3").is_ok());
+    assert_eq!(IDS.lock().unwrap().take(), Some(vec![1, 2, 3]));
+
+    assert!(run("!size 3,4").is_ok());
+    assert_eq!(SIZE.lock().unwrap().take(), Some((3, 4)));
+
+    assert!(matches!(
+        run("!size 3"),
+        Err(Error::Dispatch(DispatchError::Argument {
+            position: 0,
+            kind: ArgumentErrorKind::Parse(_),
+            ..
+        }))
+    ));
+    assert_eq!(SIZE.lock().unwrap().take(), None);
+}
+
+#[test]
+fn member_argument() {
+    let members = vec![
+        test_util::member(1, "ferris", 1, Some("Crab")),
+        test_util::member(2, "ferris", 2, None),
+        test_util::member(3, "corro", 1, Some("crab")),
+    ];
+
+    let find = |arg: &str| {
+        let arg = arg.parse::<MemberArg>().unwrap();
+        arg.find(&members).map(|member| member.map(|m| m.user.id.0))
+    };
+
+    assert_eq!(find("<@!2>"), Ok(Some(2)));
+    assert_eq!(find("3"), Ok(Some(3)));
+    assert_eq!(find("4"), Ok(None));
+    assert_eq!(find("ferris#0002"), Ok(Some(2)));
+    assert_eq!(find("ferris#0003"), Ok(None));
+    // The exact casing of a nickname takes precedence.
+    assert_eq!(find("Crab"), Ok(Some(1)));
+    assert_eq!(find("corro"), Ok(Some(3)));
+    assert_eq!(find("CORRO"), Ok(Some(3)));
+    assert_eq!(find("ferris"), Err(MemberParseError::Ambiguous(2)));
+    assert_eq!(find("CRAB"), Err(MemberParseError::Ambiguous(2)));
+
+    #[command]
+    async fn kick(_ctx: FrameworkContext, _msg: &Message, #[member] _target: Member) {}
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(kick);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let msg = MessageBuilder::new("!kick ferris").guild(None::<u64>).build();
+
+    let result = block_on(framework.dispatch(&ctx, &msg));
+    assert!(matches!(result, Err(Error::Dispatch(DispatchError::Argument {
+        kind: ArgumentErrorKind::Parse(err),
+        ..
+    })) if err == MemberParseError::NotInGuild.to_string()));
+}
+
+#[test]
+fn bool_argument() {
+    static ENABLED: Mutex<Option<bool>> = Mutex::new(None);
+
+    #[command]
+    async fn toggle(_ctx: FrameworkContext, _msg: &Message, #[bool] enabled: bool) {
+        *ENABLED.lock().unwrap() = Some(enabled);
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(toggle);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |content: &str| block_on(framework.dispatch(&ctx, &test_util::message(content)));
+
+    assert!(run("!toggle yes").is_ok());
+    assert_eq!(*ENABLED.lock().unwrap(), Some(true));
+
+    assert!(run("!toggle Off").is_ok());
+    assert_eq!(*ENABLED.lock().unwrap(), Some(false));
+
+    assert!(matches!(
+        run("!toggle maybe"),
+        Err(Error::Dispatch(DispatchError::Argument {
+            kind: ArgumentErrorKind::Parse(_),
+            ..
+        }))
+    ));
+}
+
+#[test]
+fn shell_tokenizer() {
+    static WORDS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    #[command]
+    #[tokenizer(shell)]
+    async fn echo(_ctx: FrameworkContext, _msg: &Message, words: Vec<String>) {
+        *WORDS.lock().unwrap() = words;
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(echo);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |content: &str| block_on(framework.dispatch(&ctx, &test_util::message(content)));
+
+    assert!(run(r#"!echo "say \"hi\"" back\ slash \"quote"#).is_ok());
+    assert_eq!(*WORDS.lock().unwrap(), vec![r#"say "hi""#, "back slash", "\"quote"]);
+}
+
+#[test]
+fn strict_quotes() {
+    static ARGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    #[command]
+    async fn tag(_ctx: FrameworkContext, _msg: &Message, args: Vec<String>) {
+        *ARGS.lock().unwrap() = args;
+    }
+
+    for strict in &[false, true] {
+        let mut conf = Configuration::new();
+        conf.prefix("!").strict_quotes(*strict).command(tag);
+
+        let framework: Framework = Framework::new(conf);
+
+        let ctx = test_util::context();
+        let run = |content: &str| block_on(framework.dispatch(&ctx, &test_util::message(content)));
+
+        assert!(run("!tag \"a b\" c").is_ok());
+        assert_eq!(*ARGS.lock().unwrap(), vec!["a b", "c"]);
+
+        let result = run("!tag c \"a b");
+
+        if *strict {
+            assert!(matches!(result, Err(Error::Dispatch(DispatchError::UnterminatedQuote))));
+        } else {
+            assert!(result.is_ok());
+            assert_eq!(*ARGS.lock().unwrap(), vec!["c", "a b"]);
+        }
+    }
+}
+
+#[test]
+fn required_rest() {
+    static TEXT: Mutex<Option<String>> = Mutex::new(None);
+
+    #[command]
+    async fn say(_ctx: FrameworkContext, _msg: &Message, #[rest] text: String) {
+        *TEXT.lock().unwrap() = Some(text);
+    }
+
+    #[command]
+    async fn shout(_ctx: FrameworkContext, _msg: &Message, #[rest(required)] text: String) {
+        *TEXT.lock().unwrap() = Some(text.to_uppercase());
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(say).command(shout);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |content: &str| block_on(framework.dispatch(&ctx, &test_util::message(content)));
+
+    // A rest argument may be empty.
+    assert!(run("!say").is_ok());
+    assert_eq!(TEXT.lock().unwrap().take(), Some(String::new()));
+
+    assert!(run("!say hello world").is_ok());
+    assert_eq!(TEXT.lock().unwrap().take().as_deref(), Some("hello world"));
+
+    // A required rest argument may not.
+    assert!(matches!(
+        run("!shout   "),
+        Err(Error::Dispatch(DispatchError::Argument {
+            position: 0,
+            kind: ArgumentErrorKind::Missing,
+            ..
+        }))
+    ));
+    assert_eq!(TEXT.lock().unwrap().take(), None);
+
+    assert!(run("!shout hello world").is_ok());
+    assert_eq!(TEXT.lock().unwrap().take().as_deref(), Some("HELLO WORLD"));
+}
+
+#[test]
+fn delimiter_per_invocation() {
+    static ITEMS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    #[command]
+    #[delimiter(",")]
+    async fn list(ctx: FrameworkContext, _msg: &Message, items: Vec<String>) {
+        assert_eq!(ctx.arguments().collect::<Vec<_>>(), items);
+        *ITEMS.lock().unwrap() = items;
+    }
+
+    fn semicolons(_msg: &Message, args: &str) -> Option<String> {
+        if args.contains(';') {
+            Some(";".to_string())
+        } else {
+            None
+        }
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").dynamic_delimiter(semicolons).command(list);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |content: &str| block_on(framework.dispatch(&ctx, &test_util::message(content)));
+
+    assert!(run("!list a,b,c").is_ok());
+    assert_eq!(*ITEMS.lock().unwrap(), vec!["a", "b", "c"]);
+
+    assert!(run("!list a,b;c").is_ok());
+    assert_eq!(*ITEMS.lock().unwrap(), vec!["a,b", "c"]);
+}
+
+#[test]
+fn locale_delimiters() {
+    static ITEMS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    #[command]
+    #[delimiter(",")]
+    #[locale_delimiter("fr", ";")]
+    async fn list(_ctx: FrameworkContext, _msg: &Message, items: Vec<String>) {
+        *ITEMS.lock().unwrap() = items;
+    }
+
+    // The guild `2` speaks French.
+    fn guild_locale(msg: &Message) -> Option<String> {
+        match msg.guild_id?.0 {
+            2 => Some("fr".to_string()),
+            _ => Some("en".to_string()),
+        }
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").locale_resolver(guild_locale).command(list);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+
+    let msg = test_util::message("!list 1,5;2,5");
+    assert!(block_on(framework.dispatch(&ctx, &msg)).is_ok());
+    assert_eq!(*ITEMS.lock().unwrap(), vec!["1", "5;2", "5"]);
+
+    let msg = MessageBuilder::new("!list 1,5;2,5").guild(Some(2)).build();
+    assert!(block_on(framework.dispatch(&ctx, &msg)).is_ok());
+    assert_eq!(*ITEMS.lock().unwrap(), vec!["1,5", "2,5"]);
+}
+
+#[test]
+fn sticker_and_embed_arguments() {
+    static STICKER: Mutex<Option<String>> = Mutex::new(None);
+    static EMBEDS: Mutex<Vec<Option<String>>> = Mutex::new(Vec::new());
+
+    #[command]
+    async fn react(
+        _ctx: FrameworkContext,
+        _msg: &Message,
+        #[sticker] sticker: StickerItem,
+        times: Option<u32>,
+    ) {
+        *STICKER.lock().unwrap() = Some(format!("{} x{}", sticker.name, times.unwrap_or(1)));
+    }
+
+    #[command]
+    async fn titles(_ctx: FrameworkContext, _msg: &Message, #[embed] embeds: Vec<Embed>) {
+        *EMBEDS.lock().unwrap() = embeds.into_iter().map(|embed| embed.title).collect();
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(react).command(titles);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+
+    let msg = MessageBuilder::new("!react 3").sticker("wave").build();
+    assert!(block_on(framework.dispatch(&ctx, &msg)).is_ok());
+    assert_eq!(STICKER.lock().unwrap().as_deref(), Some("wave x3"));
+
+    let msg = MessageBuilder::new("!react 3").build();
+    assert!(matches!(
+        block_on(framework.dispatch(&ctx, &msg)),
+        Err(Error::Dispatch(DispatchError::Argument {
+            kind: ArgumentErrorKind::Missing,
+            ..
+        }))
+    ));
+
+    let msg = MessageBuilder::new("!titles").embed("first").embed("second").build();
+    assert!(block_on(framework.dispatch(&ctx, &msg)).is_ok());
+    assert_eq!(*EMBEDS.lock().unwrap(), vec![
+        Some("first".to_string()),
+        Some("second".to_string())
+    ]);
+}
+
+#[test]
+fn validate_examples() {
+    #[command]
+    #[example("6 2")]
+    #[example("six")]
+    #[example("20 3 4")]
+    #[deny_extra_args]
+    async fn roll(_ctx: FrameworkContext, _msg: &Message, sides: u32, dice: Option<u32>) {
+        let _ = (sides, dice);
+    }
+
+    let command = roll();
+
+    let ctx = test_util::context();
+    let msg = test_util::message("");
+
+    assert_eq!(block_on(command.validate_examples(&ctx, &msg)), vec!["six", "20 3 4"]);
+}
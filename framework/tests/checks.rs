@@ -0,0 +1,378 @@
+//! Tests of the checks of commands.
+
+#![cfg(feature = "test-util")]
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serenity::futures::executor::block_on;
+use serenity::model::channel::Message;
+use serenity::model::id::{GuildId, RoleId};
+use serenity_framework::check::{Check, RoleSpec};
+use serenity_framework::command::{CommandBuilder, CommandId};
+use serenity_framework::configuration::BeforeAction;
+use serenity_framework::error::{DispatchError, Error};
+use serenity_framework::prelude::*;
+use serenity_framework::test_util::{self, MessageBuilder};
+use serenity_framework::{check, DefaultData, DefaultError};
+
+#[test]
+fn nsfw() {
+    #[command]
+    #[nsfw]
+    async fn lewd(_ctx: FrameworkContext, _msg: &Message) -> CommandResult {
+        unreachable!()
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").nsfw_in_dms(false).command(lewd);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let msg = MessageBuilder::new("!lewd").guild(None::<GuildId>).build();
+
+    let result = block_on(framework.dispatch(&ctx, &msg));
+    assert!(matches!(result, Err(Error::Dispatch(DispatchError::NsfwRequired))));
+}
+
+#[test]
+fn required_roles() {
+    #[command]
+    #[required_role(10)]
+    async fn ban(_ctx: FrameworkContext, _msg: &Message) {}
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(ban);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |msg: Message| block_on(framework.dispatch(&ctx, &msg));
+
+    assert!(run(MessageBuilder::new("!ban").member_roles(vec![RoleId(10)]).build()).is_ok());
+    assert!(matches!(
+        run(MessageBuilder::new("!ban").member_roles(vec![RoleId(20)]).build()),
+        Err(Error::Dispatch(DispatchError::CheckFailed(_, Reason::User(_))))
+    ));
+    assert!(matches!(
+        run(MessageBuilder::new("!ban").guild(None::<u64>).build()),
+        Err(Error::Dispatch(DispatchError::CheckFailed(_, Reason::User(_))))
+    ));
+
+    // Names are resolved against the roles of the guild.
+    let roles = vec![test_util::role(10, "Moderator"), test_util::role(20, "Member")];
+    let roles = roles.into_iter().map(|role| (role.id, role)).collect::<HashMap<_, _>>();
+
+    assert!(RoleSpec::from("Moderator").is_held(&[RoleId(10), RoleId(20)], &roles));
+    assert!(!RoleSpec::from("Moderator").is_held(&[RoleId(20)], &roles));
+    assert!(!RoleSpec::from("Admin").is_held(&[RoleId(10)], &roles));
+}
+
+#[test]
+fn combined_checks() {
+    #[check]
+    async fn first(_ctx: &CheckContext<'_>, msg: &Message) -> CheckResult {
+        if msg.author.id.0 == 1 {
+            Ok(())
+        } else {
+            Err(Reason::User("not the first user".to_string()))
+        }
+    }
+
+    #[check]
+    async fn second(_ctx: &CheckContext<'_>, msg: &Message) -> CheckResult {
+        if msg.author.id.0 == 2 {
+            Ok(())
+        } else {
+            Err(Reason::User("not the second user".to_string()))
+        }
+    }
+
+    fn any() -> Check<DefaultData, DefaultError> {
+        check::any(&[first, second])
+    }
+
+    fn all() -> Check<DefaultData, DefaultError> {
+        check::all(&[first, second])
+    }
+
+    fn not() -> Check<DefaultData, DefaultError> {
+        check::not(first)
+    }
+
+    #[command]
+    #[check(any)]
+    async fn a(_ctx: FrameworkContext, _msg: &Message) -> CommandResult {
+        Ok(())
+    }
+
+    #[command]
+    #[check(all)]
+    async fn b(_ctx: FrameworkContext, _msg: &Message) -> CommandResult {
+        Ok(())
+    }
+
+    #[command]
+    #[check(not)]
+    async fn c(_ctx: FrameworkContext, _msg: &Message) -> CommandResult {
+        Ok(())
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(a).command(b).command(c);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |content: &str, author: u64| {
+        let msg = MessageBuilder::new(content).author(author).build();
+        block_on(framework.dispatch(&ctx, &msg))
+    };
+
+    assert!(run("!a", 1).is_ok());
+    assert!(run("!a", 2).is_ok());
+
+    match run("!a", 3) {
+        Err(Error::Dispatch(DispatchError::CheckFailed(name, Reason::User(reason)))) => {
+            assert_eq!(name, "first or second");
+            assert_eq!(reason, "not the first user; not the second user");
+        },
+        _ => unreachable!(),
+    }
+
+    assert!(matches!(run("!b", 1), Err(Error::Dispatch(DispatchError::CheckFailed(..)))));
+    assert!(matches!(run("!b", 2), Err(Error::Dispatch(DispatchError::CheckFailed(..)))));
+
+    assert!(matches!(run("!c", 1), Err(Error::Dispatch(DispatchError::CheckFailed(..)))));
+    assert!(run("!c", 2).is_ok());
+}
+
+#[test]
+fn several_checks() {
+    #[check]
+    async fn not_first(_ctx: &CheckContext<'_>, msg: &Message) -> CheckResult {
+        if msg.author.id.0 != 1 {
+            Ok(())
+        } else {
+            Err(Reason::User("the first user".to_string()))
+        }
+    }
+
+    #[check]
+    async fn not_second(_ctx: &CheckContext<'_>, msg: &Message) -> CheckResult {
+        if msg.author.id.0 != 2 {
+            Ok(())
+        } else {
+            Err(Reason::User("the second user".to_string()))
+        }
+    }
+
+    #[command]
+    #[check(not_first)]
+    #[check(not_second)]
+    async fn a(_ctx: FrameworkContext, _msg: &Message) -> CommandResult {
+        Ok(())
+    }
+
+    fn b() -> Command<DefaultData, DefaultError> {
+        CommandBuilder::new("b").checks(&[not_first, not_second]).function(a().function).build()
+    }
+
+    assert_eq!(a().checks.len(), 2);
+    assert_eq!(b().checks.len(), 2);
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(a).command(b);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |content: &str, author: u64| {
+        let msg = MessageBuilder::new(content).author(author).build();
+        block_on(framework.dispatch(&ctx, &msg))
+    };
+
+    for name in &["!a", "!b"] {
+        assert!(run(name, 3).is_ok());
+
+        match run(name, 1) {
+            Err(Error::Dispatch(DispatchError::CheckFailed(check, _))) => {
+                assert_eq!(check, "not_first")
+            },
+            _ => unreachable!(),
+        }
+
+        match run(name, 2) {
+            Err(Error::Dispatch(DispatchError::CheckFailed(check, _))) => {
+                assert_eq!(check, "not_second")
+            },
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[test]
+fn owners() {
+    static OWNER: AtomicBool = AtomicBool::new(false);
+
+    #[command]
+    async fn whoami(ctx: FrameworkContext, msg: &Message) {
+        OWNER.store(ctx.author_is_owner(msg).await, Ordering::SeqCst);
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").owners(vec![7]).command(whoami);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+
+    let msg = MessageBuilder::new("!whoami").author(7).build();
+    assert!(block_on(framework.dispatch(&ctx, &msg)).is_ok());
+    assert!(OWNER.load(Ordering::SeqCst));
+
+    let msg = MessageBuilder::new("!whoami").author(8).build();
+    assert!(block_on(framework.dispatch(&ctx, &msg)).is_ok());
+    assert!(!OWNER.load(Ordering::SeqCst));
+}
+
+#[test]
+fn global_checks_on_redirect() {
+    #[check]
+    async fn not_admin(ctx: &CheckContext<'_>, _msg: &Message) -> CheckResult {
+        if ctx.command_id == CommandId::from(admin as fn() -> _) {
+            Err(Reason::User("admins only".to_string()))
+        } else {
+            Ok(())
+        }
+    }
+
+    #[command]
+    async fn admin(_ctx: FrameworkContext, _msg: &Message) {
+        unreachable!()
+    }
+
+    #[command]
+    async fn alias(_ctx: FrameworkContext, _msg: &Message) {
+        unreachable!()
+    }
+
+    #[hook]
+    async fn redirect(ctx: &FrameworkContext, _msg: &Message) -> BeforeAction {
+        if ctx.command_id == CommandId::from(alias as fn() -> _) {
+            BeforeAction::Redirect {
+                command: CommandId::from(admin as fn() -> _),
+                args: String::new(),
+            }
+        } else {
+            BeforeAction::Proceed
+        }
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").global_check(not_admin).before(redirect).command(admin).command(alias);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+
+    for &content in &["!admin", "!alias"] {
+        let msg = test_util::message(content);
+
+        match block_on(framework.dispatch(&ctx, &msg)) {
+            Err(Error::Dispatch(DispatchError::CheckFailed(check, _))) => {
+                assert_eq!(check, "not_admin")
+            },
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[test]
+fn global_checks_in_help() {
+    #[check]
+    async fn not_admin(ctx: &CheckContext<'_>, _msg: &Message) -> CheckResult {
+        if ctx.command_id == CommandId::from(admin as fn() -> _) {
+            Err(Reason::User("admins only".to_string()))
+        } else {
+            Ok(())
+        }
+    }
+
+    #[command]
+    async fn admin(_ctx: FrameworkContext, _msg: &Message) {}
+
+    #[command]
+    async fn ping(_ctx: FrameworkContext, _msg: &Message) {}
+
+    let mut conf = Configuration::new();
+    conf.global_check(not_admin).command(admin).command(ping);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let msg = test_util::message("");
+
+    let commands = block_on(framework.available_commands(&ctx, &msg));
+    assert_eq!(commands, vec![CommandId::from(ping as fn() -> _)]);
+}
+
+#[test]
+fn bot_permissions_in_dms() {
+    #[command]
+    #[bot_permissions("MANAGE_MESSAGES")]
+    async fn purge(_ctx: FrameworkContext, _msg: &Message) {}
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").require_send_permission(true).command(purge);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+
+    // No request is made for the permissions of the bot in direct messages.
+    let msg = MessageBuilder::new("!purge").guild(None::<GuildId>).build();
+    assert!(block_on(framework.dispatch(&ctx, &msg)).is_ok());
+}
+
+#[test]
+fn reply_check_failures() {
+    #[check]
+    async fn deny(_ctx: &CheckContext<'_>, _msg: &Message) -> CheckResult {
+        Err(Reason::User("you may not do that".to_string()))
+    }
+
+    #[command]
+    #[check(deny)]
+    async fn secret(_ctx: FrameworkContext, _msg: &Message) -> CommandResult {
+        unreachable!()
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").reply_check_failures(true).command(secret);
+
+    let framework: Framework = Framework::new(conf);
+
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+
+    let http = test_util::MockHttp::start();
+    let ctx = runtime.block_on(http.context());
+    let msg = test_util::message("!secret");
+
+    let result = runtime.block_on(framework.dispatch(&ctx, &msg));
+    assert!(matches!(result, Err(Error::Dispatch(DispatchError::CheckFailed(_, Reason::User(_))))));
+
+    let requests = http.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert!(requests[0].path.ends_with("/channels/1/messages"));
+    assert!(requests[0].body.contains("you may not do that"));
+
+    // No reply is sent once disabled.
+    runtime.block_on(framework.conf.write()).reply_check_failures(false);
+
+    let result = runtime.block_on(framework.dispatch(&ctx, &msg));
+    assert!(matches!(result, Err(Error::Dispatch(DispatchError::CheckFailed(_, Reason::User(_))))));
+    assert_eq!(http.requests().len(), 1);
+}
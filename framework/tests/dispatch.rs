@@ -0,0 +1,826 @@
+//! Tests of dispatching commands from messages.
+
+#![cfg(feature = "test-util")]
+
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::task::Poll;
+use std::thread;
+use std::time::Duration;
+
+use serenity::futures::executor::block_on;
+use serenity::futures::future::{self, join, poll_fn, BoxFuture};
+use serenity::model::channel::Message;
+use serenity::model::id::{GuildId, UserId};
+use serenity_framework::command::CommandId;
+use serenity_framework::context::NormalMessageContext;
+use serenity_framework::error::{DispatchError, Error, FromSerenityError};
+use serenity_framework::prelude::*;
+use serenity_framework::test_util::{self, MessageBuilder};
+use serenity_framework::DefaultError;
+
+#[test]
+fn mutating_user_data() {
+    #[command]
+    async fn count(ctx: FrameworkContext<u64>, _msg: &Message) {
+        *ctx.data.write().await += 1;
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(count);
+
+    let framework: Framework<u64> = Framework::with_data(conf, 0);
+
+    let ctx = test_util::context();
+    let msg = test_util::message("!count");
+
+    block_on(framework.dispatch(&ctx, &msg)).unwrap();
+    block_on(framework.dispatch(&ctx, &msg)).unwrap();
+    assert_eq!(*block_on(framework.data.read()), 2);
+}
+
+#[test]
+fn dispatching_many_messages() {
+    static INVOKED: AtomicUsize = AtomicUsize::new(0);
+    static OBSERVED: AtomicBool = AtomicBool::new(false);
+
+    #[command]
+    async fn ping(_ctx: FrameworkContext, _msg: &Message) {
+        INVOKED.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[hook]
+    async fn observe(_ctx: &NormalMessageContext<'_>, msg: &Message) {
+        assert_eq!(msg.content, "hello");
+        OBSERVED.store(true, Ordering::SeqCst);
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(ping);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let msgs = vec![
+        test_util::message("!ping"),
+        test_util::message("hello"),
+        test_util::message("!ping again"),
+    ];
+
+    let results = block_on(framework.dispatch_many(&ctx, msgs));
+
+    assert_eq!(results.len(), 3);
+    assert!(results[0].is_ok());
+    assert!(matches!(results[1], Err(Error::Dispatch(DispatchError::NormalMessage))));
+    assert!(results[2].is_ok());
+    assert_eq!(INVOKED.load(Ordering::SeqCst), 2);
+
+    // Non-command messages reach the normal message hook.
+    block_on(framework.conf.write()).normal_message(observe);
+
+    let msgs = vec![test_util::message("!ping"), test_util::message("hello")];
+    let results = block_on(framework.dispatch_many(&ctx, msgs));
+    assert!(results.iter().all(Result::is_ok));
+    assert!(OBSERVED.load(Ordering::SeqCst));
+}
+
+#[test]
+fn invoking_checked() {
+    #[command]
+    async fn ping(_ctx: FrameworkContext, _msg: &Message) {}
+
+    #[command]
+    #[nsfw]
+    async fn lewd(_ctx: FrameworkContext, _msg: &Message) {
+        unreachable!()
+    }
+
+    let mut conf = Configuration::new();
+    conf.blocked_users(vec![UserId(2)]).nsfw_in_dms(false).command(ping).command(lewd);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+
+    let msg = MessageBuilder::new("").author(2).build();
+    let result = block_on(framework.invoke(&ctx, &msg, "ping", ""));
+    assert!(matches!(result, Err(Error::Dispatch(DispatchError::Blocked))));
+
+    let msg = MessageBuilder::new("").guild(None::<GuildId>).build();
+    let result = block_on(framework.invoke(&ctx, &msg, "lewd", ""));
+    assert!(matches!(result, Err(Error::Dispatch(DispatchError::NsfwRequired))));
+
+    // Unchecked invocations are not affected.
+    let msg = MessageBuilder::new("").author(2).build();
+    assert!(block_on(framework.invoke_unchecked(&ctx, &msg, "ping", "")).is_ok());
+}
+
+#[test]
+fn case_insensitive_arguments() {
+    static INVOKED: AtomicBool = AtomicBool::new(false);
+
+    #[command]
+    async fn echo(ctx: FrameworkContext, _msg: &Message) {
+        assert_eq!(ctx.command_name, "echo");
+        assert_eq!(ctx.args, "Hello WORLD");
+        INVOKED.store(true, Ordering::SeqCst);
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").case_insensitive(true).command(echo);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let msg = test_util::message("!ECHO Hello WORLD");
+
+    block_on(framework.dispatch(&ctx, &msg)).unwrap();
+    assert!(INVOKED.load(Ordering::SeqCst));
+}
+
+#[test]
+fn invoking_by_name() {
+    static INVOKED: AtomicBool = AtomicBool::new(false);
+
+    #[command]
+    async fn echo(ctx: FrameworkContext, _msg: &Message) -> CommandResult {
+        assert_eq!(ctx.prefix, "");
+        assert_eq!(ctx.args, "hello world");
+        INVOKED.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    let mut conf = Configuration::new();
+    conf.command(echo);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let msg = test_util::message("unrelated content");
+
+    block_on(framework.invoke(&ctx, &msg, "echo", "hello world")).unwrap();
+    assert!(INVOKED.load(Ordering::SeqCst));
+
+    let result = block_on(framework.invoke(&ctx, &msg, "missing", ""));
+    assert!(matches!(result, Err(Error::Dispatch(DispatchError::InvalidCommandName(_)))));
+}
+
+#[test]
+fn content_offset() {
+    static CONTENT: Mutex<String> = Mutex::new(String::new());
+
+    #[command]
+    async fn calc(ctx: FrameworkContext, msg: &Message) {
+        *CONTENT.lock().unwrap() = msg.content[ctx.content_offset..].to_string();
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").on_mention(42).command(calc);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |content: &str| block_on(framework.dispatch(&ctx, &test_util::message(content)));
+
+    assert!(run("!  calc 1 + 2").is_ok());
+    assert_eq!(*CONTENT.lock().unwrap(), "calc 1 + 2");
+
+    assert!(run("<@!42> calc 3 * 4").is_ok());
+    assert_eq!(*CONTENT.lock().unwrap(), "calc 3 * 4");
+}
+
+#[test]
+fn content_offset_with_preprocessor() {
+    static SLICES: Mutex<Option<(String, String)>> = Mutex::new(None);
+
+    #[command]
+    async fn calc(ctx: FrameworkContext, msg: &Message) {
+        let content = msg.content[ctx.content_offset..].to_string();
+        *SLICES.lock().unwrap() = Some((content, ctx.args.clone()));
+    }
+
+    // Replaces a leading fullwidth exclamation mark, which is three bytes long.
+    fn fullwidth_prefix(msg: &Message) -> Cow<'_, str> {
+        match msg.content.strip_prefix('！') {
+            Some(rest) => Cow::Owned(format!("!{}", rest)),
+            None => Cow::Borrowed(&msg.content),
+        }
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").content_preprocessor(fullwidth_prefix).trim_leading(true).command(calc);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |content: &str| {
+        block_on(framework.dispatch(&ctx, &test_util::message(content))).unwrap();
+        SLICES.lock().unwrap().take().unwrap()
+    };
+
+    let (content, args) = run("！calc 1 + 2");
+    assert_eq!(content, "calc 1 + 2");
+    assert!(content.ends_with(&args));
+    assert_eq!(args, "1 + 2");
+
+    let (content, args) = run(" \t!calc 3 * 4");
+    assert_eq!(content, "calc 3 * 4");
+    assert_eq!(args, "3 * 4");
+}
+
+#[test]
+fn command_returning_nothing() {
+    static INVOKED: AtomicBool = AtomicBool::new(false);
+
+    #[command]
+    async fn ping(_ctx: FrameworkContext, _msg: &Message, count: Option<u32>) {
+        if count == Some(0) {
+            return;
+        }
+
+        INVOKED.store(true, Ordering::SeqCst);
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(ping);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+
+    block_on(framework.dispatch(&ctx, &test_util::message("!ping 0"))).unwrap();
+    assert!(!INVOKED.load(Ordering::SeqCst));
+
+    block_on(framework.dispatch(&ctx, &test_util::message("!ping"))).unwrap();
+    assert!(INVOKED.load(Ordering::SeqCst));
+}
+
+#[test]
+fn unknown_command() {
+    static INVOKED: AtomicBool = AtomicBool::new(false);
+
+    #[command]
+    async fn ping(_ctx: FrameworkContext, _msg: &Message) {}
+
+    #[command]
+    async fn not_found(ctx: FrameworkContext, _msg: &Message) {
+        assert_eq!(ctx.command_name, "pnig");
+        assert_eq!(ctx.args, "pnig with args");
+        INVOKED.store(true, Ordering::SeqCst);
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(ping).unknown_command(not_found);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+
+    block_on(framework.dispatch(&ctx, &test_util::message("!ping"))).unwrap();
+    assert!(!INVOKED.load(Ordering::SeqCst));
+
+    block_on(framework.dispatch(&ctx, &test_util::message("!pnig with args"))).unwrap();
+    assert!(INVOKED.load(Ordering::SeqCst));
+
+    // The unknown command is not invocable by its name.
+    assert!(block_on(framework.invoke(&ctx, &test_util::message(""), "not_found", "")).is_err());
+}
+
+#[test]
+fn timing_hook() {
+    static ELAPSED: AtomicU64 = AtomicU64::new(0);
+
+    #[command]
+    async fn slow(_ctx: FrameworkContext, _msg: &Message) {
+        thread::sleep(Duration::from_millis(20));
+    }
+
+    fn on_timing(id: CommandId, elapsed: Duration) {
+        assert_eq!(id, CommandId::from(slow as fn() -> _));
+        ELAPSED.store(elapsed.as_millis() as u64, Ordering::SeqCst);
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").on_timing(on_timing).command(slow);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+
+    block_on(framework.dispatch(&ctx, &test_util::message("!slow"))).unwrap();
+    assert!(ELAPSED.load(Ordering::SeqCst) >= 20);
+}
+
+#[test]
+fn normal_message_hook() {
+    static OBSERVED: AtomicBool = AtomicBool::new(false);
+
+    #[hook]
+    async fn observe(_ctx: &NormalMessageContext<'_>, msg: &Message) {
+        assert_eq!(msg.content, "hello");
+        OBSERVED.store(true, Ordering::SeqCst);
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!");
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let msg = test_util::message("hello");
+
+    let result = block_on(framework.dispatch(&ctx, &msg));
+    assert!(matches!(result, Err(Error::Dispatch(DispatchError::NormalMessage))));
+
+    block_on(framework.conf.write()).normal_message(observe);
+
+    block_on(framework.dispatch(&ctx, &msg)).unwrap();
+    assert!(OBSERVED.load(Ordering::SeqCst));
+}
+
+#[test]
+fn serialize_per_user() {
+    static RUNNING: AtomicUsize = AtomicUsize::new(0);
+    static COMPLETED: AtomicUsize = AtomicUsize::new(0);
+
+    #[command]
+    async fn count(_ctx: FrameworkContext, _msg: &Message) -> CommandResult {
+        assert_eq!(RUNNING.fetch_add(1, Ordering::SeqCst), 0);
+
+        // Yield to the other invocation of the command.
+        let mut yielded = false;
+        poll_fn(|cx| {
+            if yielded {
+                return Poll::Ready(());
+            }
+
+            yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        })
+        .await;
+
+        RUNNING.fetch_sub(1, Ordering::SeqCst);
+        COMPLETED.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").serialize_per_user(true).command(count);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let msg = test_util::message("!count");
+
+    let (a, b) = block_on(join(framework.dispatch(&ctx, &msg), framework.dispatch(&ctx, &msg)));
+    assert!(a.is_ok() && b.is_ok());
+    assert_eq!(COMPLETED.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn dedupe_window() {
+    #[command]
+    async fn ping(_ctx: FrameworkContext, _msg: &Message) {}
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").dedupe_window(Duration::from_secs(60)).command(ping);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let msg = test_util::message("!ping");
+
+    assert!(block_on(framework.dispatch(&ctx, &msg)).is_ok());
+
+    let result = block_on(framework.dispatch(&ctx, &msg));
+    assert!(matches!(result, Err(Error::Dispatch(DispatchError::DuplicateInvocation))));
+
+    // Other users and other content are unaffected.
+    let msg = MessageBuilder::new("!ping").author(2).build();
+    assert!(block_on(framework.dispatch(&ctx, &msg)).is_ok());
+
+    let msg = test_util::message("!ping ");
+    assert!(block_on(framework.dispatch(&ctx, &msg)).is_ok());
+
+    // Duplicates within a batch are rejected as well.
+    let msgs = vec![test_util::message("!ping  "), test_util::message("!ping  ")];
+    let results = block_on(framework.dispatch_many(&ctx, msgs));
+    assert!(results[0].is_ok());
+    assert!(matches!(results[1], Err(Error::Dispatch(DispatchError::DuplicateInvocation))));
+}
+
+#[test]
+fn dedupe_window_expiry() {
+    #[command]
+    async fn ping(_ctx: FrameworkContext, _msg: &Message) {}
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").dedupe_window(Duration::from_millis(20)).command(ping);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let msg = test_util::message("!ping");
+
+    assert!(block_on(framework.dispatch(&ctx, &msg)).is_ok());
+
+    let result = block_on(framework.dispatch(&ctx, &msg));
+    assert!(matches!(result, Err(Error::Dispatch(DispatchError::DuplicateInvocation))));
+
+    // Once the window has passed, the invocation is recorded anew.
+    std::thread::sleep(Duration::from_millis(40));
+    assert!(block_on(framework.dispatch(&ctx, &msg)).is_ok());
+
+    let result = block_on(framework.dispatch(&ctx, &msg));
+    assert!(matches!(result, Err(Error::Dispatch(DispatchError::DuplicateInvocation))));
+}
+
+#[test]
+fn name_collisions() {
+    static INVOKED: AtomicU64 = AtomicU64::new(0);
+
+    #[check]
+    async fn guild_only(_ctx: &CheckContext<'_>, msg: &Message) -> CheckResult {
+        match msg.guild_id {
+            Some(_) => Ok(()),
+            None => Err(Reason::User("only in guilds".to_string())),
+        }
+    }
+
+    #[check]
+    async fn dm_only(_ctx: &CheckContext<'_>, msg: &Message) -> CheckResult {
+        match msg.guild_id {
+            Some(_) => Err(Reason::User("only in direct messages".to_string())),
+            None => Ok(()),
+        }
+    }
+
+    #[command("info")]
+    #[check(guild_only)]
+    async fn guild_info(_ctx: FrameworkContext, _msg: &Message) -> CommandResult {
+        INVOKED.store(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    #[command("info")]
+    #[check(dm_only)]
+    async fn dm_info(_ctx: FrameworkContext, _msg: &Message) -> CommandResult {
+        INVOKED.store(2, Ordering::SeqCst);
+        Ok(())
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").allow_name_collisions(true).command(guild_info).command(dm_info);
+
+    assert_eq!(conf.validate(), Ok(()));
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+
+    let msg = MessageBuilder::new("!info").build();
+    assert!(block_on(framework.dispatch(&ctx, &msg)).is_ok());
+    assert_eq!(INVOKED.load(Ordering::SeqCst), 1);
+
+    let msg = MessageBuilder::new("!info").guild(None::<u64>).build();
+    assert!(block_on(framework.dispatch(&ctx, &msg)).is_ok());
+    assert_eq!(INVOKED.load(Ordering::SeqCst), 2);
+
+    // Without collisions, the name belongs to the command registered last.
+    let mut conf = Configuration::<_, DefaultError>::new();
+    conf.prefix("!").command(guild_info).command(dm_info);
+
+    let framework: Framework = Framework::new(conf);
+
+    let msg = MessageBuilder::new("!info").build();
+    let result = block_on(framework.dispatch(&ctx, &msg));
+    assert!(matches!(result, Err(Error::Dispatch(DispatchError::CheckFailed(..)))));
+}
+
+#[test]
+fn subcommand_separator() {
+    static INVOKED: Mutex<Option<(String, String)>> = Mutex::new(None);
+
+    #[command]
+    #[subcommands(set)]
+    async fn config(ctx: FrameworkContext, _msg: &Message) -> CommandResult {
+        *INVOKED.lock().unwrap() = Some(("config".to_string(), ctx.args.clone()));
+        Ok(())
+    }
+
+    #[command]
+    async fn set(ctx: FrameworkContext, _msg: &Message) -> CommandResult {
+        *INVOKED.lock().unwrap() = Some(("set".to_string(), ctx.args.clone()));
+        Ok(())
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").subcommand_separator("/").command(config);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |content: &str| {
+        block_on(framework.dispatch(&ctx, &test_util::message(content))).unwrap();
+        INVOKED.lock().unwrap().take().unwrap()
+    };
+
+    assert_eq!(run("!config/set value"), ("set".to_string(), "value".to_string()));
+    assert_eq!(run("!config/set"), ("set".to_string(), String::new()));
+    assert_eq!(run("!config value"), ("config".to_string(), "value".to_string()));
+    // Spaces no longer separate subcommands.
+    assert_eq!(run("!config set value"), ("config".to_string(), "set value".to_string()));
+    assert_eq!(run("!config /set value"), ("config".to_string(), "/set value".to_string()));
+
+    // The separator is not part of the arguments if no subcommand follows it.
+    assert_eq!(run("!config/unknown value"), ("config".to_string(), "unknown value".to_string()));
+    assert_eq!(run("!config/"), ("config".to_string(), String::new()));
+    assert_eq!(run("!config/set/x y"), ("set".to_string(), "x y".to_string()));
+}
+
+#[test]
+fn prefixed_category() {
+    static INVOKED: Mutex<Option<String>> = Mutex::new(None);
+
+    #[command]
+    async fn kick(ctx: FrameworkContext, _msg: &Message) {
+        *INVOKED.lock().unwrap() = Some(ctx.args);
+    }
+
+    #[command]
+    async fn ping(_ctx: FrameworkContext, _msg: &Message) {}
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").prefixed_category("Moderation", "mod", &[kick]).command(ping);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |content: &str| block_on(framework.dispatch(&ctx, &test_util::message(content)));
+
+    run("!mod kick someone").unwrap();
+    assert_eq!(INVOKED.lock().unwrap().take(), Some("someone".to_string()));
+
+    // Commands of the category are only reachable after its prefix, and root level
+    // commands only without it.
+    for (content, name) in &[("!kick", "kick"), ("!mod", "mod"), ("!mod ping", "ping")] {
+        match run(content) {
+            Err(Error::Dispatch(DispatchError::InvalidCommandName(n))) => assert_eq!(n, *name),
+            result => panic!("unexpected result for {}: {:?}", content, result),
+        }
+    }
+
+    assert!(INVOKED.lock().unwrap().is_none());
+}
+
+#[test]
+fn blocked_users() {
+    static BLOCKED: Mutex<Vec<UserId>> = Mutex::new(Vec::new());
+
+    fn record<'fut>(msg: &'fut Message, err: &'fut DispatchError) -> BoxFuture<'fut, ()> {
+        assert_eq!(*err, DispatchError::Blocked);
+        BLOCKED.lock().unwrap().push(msg.author.id);
+        Box::pin(future::ready(()))
+    }
+
+    #[command]
+    async fn ping(_ctx: FrameworkContext, _msg: &Message) {}
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").blocked_users(vec![UserId(2)]).on_blocked(record).command(ping);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |msg: Message| block_on(framework.dispatch(&ctx, &msg));
+
+    assert!(run(MessageBuilder::new("!ping").author(1).build()).is_ok());
+    assert!(matches!(
+        run(MessageBuilder::new("!ping").author(2).build()),
+        Err(Error::Dispatch(DispatchError::Blocked))
+    ));
+
+    // Messages that do not invoke a command are not recorded.
+    assert!(run(MessageBuilder::new("hello").author(2).build()).is_err());
+
+    assert_eq!(*BLOCKED.lock().unwrap(), vec![UserId(2)]);
+}
+
+#[test]
+fn is_command() {
+    #[command]
+    async fn ping(_ctx: FrameworkContext, _msg: &Message) -> CommandResult {
+        panic!("the command must not be invoked");
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(ping);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let is_command =
+        |content: &str| block_on(framework.is_command(&ctx, &test_util::message(content)));
+
+    assert!(is_command("!ping"));
+    assert!(is_command("!ping with arguments"));
+    assert!(!is_command("!"));
+    assert!(!is_command("!pong"));
+    assert!(!is_command("ping"));
+}
+
+#[test]
+fn auto_respond() {
+    #[command(auto_respond)]
+    async fn echo(
+        _ctx: FrameworkContext,
+        _msg: &Message,
+        text: Option<String>,
+    ) -> CommandResult<Option<String>> {
+        if text.as_deref() == Some("") {
+            return Err("nothing to echo".into());
+        }
+
+        // The text is sent to the channel of the message. Without any text,
+        // nothing is sent.
+        Ok(text)
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(echo);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |content: &str| block_on(framework.dispatch(&ctx, &test_util::message(content)));
+
+    assert!(run("!echo").is_ok());
+    // An error of the command is returned without sending a response.
+    assert!(
+        matches!(run("!echo \"\""), Err(Error::User(err)) if err.to_string() == "nothing to echo")
+    );
+}
+
+#[test]
+fn maintenance() {
+    #[command]
+    async fn play(_ctx: FrameworkContext, _msg: &Message) {}
+
+    #[command]
+    #[available_in_maintenance]
+    async fn status(_ctx: FrameworkContext, _msg: &Message) {}
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").owners(vec![7]).maintenance(true).command(play).command(status);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |content: &str, author: u64| {
+        let msg = MessageBuilder::new(content).author(author).build();
+        block_on(framework.dispatch(&ctx, &msg))
+    };
+
+    assert!(matches!(run("!play", 8), Err(Error::Dispatch(DispatchError::Maintenance))));
+    assert!(run("!status", 8).is_ok());
+
+    // Owners are not affected by maintenance mode.
+    assert!(run("!play", 7).is_ok());
+
+    block_on(framework.conf.write()).maintenance(false);
+
+    assert!(run("!play", 8).is_ok());
+}
+
+#[test]
+fn maintenance_unknown_command() {
+    #[command]
+    async fn not_found(_ctx: FrameworkContext, _msg: &Message) {}
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").owners(vec![7]).maintenance(true).unknown_command(not_found);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |author: u64| {
+        let msg = MessageBuilder::new("!pnig").author(author).build();
+        block_on(framework.dispatch(&ctx, &msg))
+    };
+
+    assert!(matches!(run(8), Err(Error::Dispatch(DispatchError::Maintenance))));
+    assert!(run(7).is_ok());
+}
+
+#[test]
+fn command_enabled() {
+    #[command]
+    async fn play(_ctx: FrameworkContext, _msg: &Message) {}
+
+    #[command]
+    async fn status(_ctx: FrameworkContext, _msg: &Message) {}
+
+    // A settings store would be consulted here.
+    fn command_enabled(msg: &Message, id: CommandId) -> BoxFuture<'_, bool> {
+        let disabled = msg.guild_id == Some(GuildId(2)) && id == CommandId::from(play as fn() -> _);
+
+        Box::pin(future::ready(!disabled))
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command_enabled(command_enabled).command(play).command(status);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |content: &str, guild: u64| {
+        let msg = MessageBuilder::new(content).guild(Some(guild)).build();
+        block_on(framework.dispatch(&ctx, &msg))
+    };
+
+    assert!(matches!(
+        run("!play", 2),
+        Err(Error::Dispatch(DispatchError::DisabledInGuild(id)))
+            if id == CommandId::from(play as fn() -> _)
+    ));
+    assert!(run("!status", 2).is_ok());
+    assert!(run("!play", 3).is_ok());
+}
+
+#[test]
+fn available_commands_order() {
+    #[command]
+    async fn ping(_ctx: FrameworkContext, _msg: &Message) {}
+
+    #[command]
+    async fn about(_ctx: FrameworkContext, _msg: &Message) {}
+
+    #[command]
+    #[sort_key(-1)]
+    async fn help(_ctx: FrameworkContext, _msg: &Message) {}
+
+    #[command]
+    #[sort_key(5)]
+    async fn setup(_ctx: FrameworkContext, _msg: &Message) {}
+
+    let ctx = test_util::context();
+    let msg = test_util::message("");
+
+    // The order does not depend on the order of registration.
+    for commands in &[[ping, setup, about, help], [help, about, setup, ping]] {
+        let mut conf = Configuration::new();
+
+        for command in commands {
+            conf.command(*command);
+        }
+
+        let framework: Framework = Framework::new(conf);
+
+        let conf = block_on(framework.conf.read());
+        let names = block_on(framework.available_commands(&ctx, &msg))
+            .into_iter()
+            .map(|id| conf.commands.get(id).unwrap().primary_name().to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, vec!["help", "setup", "about", "ping"]);
+    }
+}
+
+#[test]
+fn serenity_errors_in_commands() {
+    #[derive(Debug)]
+    enum MyError {
+        Discord(serenity::Error),
+    }
+
+    impl FromSerenityError for MyError {
+        fn from_serenity_error(err: serenity::Error) -> Self {
+            MyError::Discord(err)
+        }
+    }
+
+    serenity_framework::from_serenity_error!(MyError);
+
+    #[command]
+    async fn ping(ctx: FrameworkContext<(), MyError>, msg: &Message) -> CommandResult<(), MyError> {
+        msg.channel_id.say(&ctx.serenity_ctx.http, "pong").await?;
+
+        Ok(())
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(ping);
+
+    let framework: Framework<(), MyError> = Framework::new(conf);
+
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+
+    let http = test_util::MockHttp::start();
+    let ctx = runtime.block_on(http.context());
+
+    let result = runtime.block_on(framework.dispatch(&ctx, &test_util::message("!ping")));
+    assert!(matches!(result, Err(Error::User(MyError::Discord(_)))));
+    assert_eq!(http.requests().len(), 1);
+}
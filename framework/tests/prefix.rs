@@ -0,0 +1,117 @@
+//! Tests of parsing the prefix of messages.
+
+#![cfg(feature = "test-util")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serenity::futures::executor::block_on;
+use serenity::futures::future::{self, BoxFuture};
+use serenity::model::channel::{ChannelType, Message};
+use serenity_framework::context::PrefixContext;
+use serenity_framework::error::{DispatchError, Error};
+use serenity_framework::prefix::{PrefixMatcher, StaticMatcher};
+use serenity_framework::prelude::*;
+use serenity_framework::test_util::{self, MessageBuilder};
+
+#[test]
+fn prefix_requirement() {
+    #[command]
+    async fn ping(_ctx: FrameworkContext, _msg: &Message) {}
+
+    fn outside_dms(kind: ChannelType) -> bool {
+        kind != ChannelType::Private
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").prefix_requirement(outside_dms).command(ping);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |content: &str, guild: Option<u64>| {
+        let msg = MessageBuilder::new(content).guild(guild).build();
+        block_on(framework.dispatch(&ctx, &msg))
+    };
+
+    assert!(run("ping", None).is_ok());
+    assert!(run("!ping", None).is_ok());
+    assert!(run("!ping", Some(1)).is_ok());
+    // The guild channel is not cached, so the prefix is required.
+    assert!(matches!(run("ping", Some(1)), Err(Error::Dispatch(DispatchError::NormalMessage))));
+}
+
+#[test]
+fn trim_leading() {
+    static OFFSET: AtomicUsize = AtomicUsize::new(0);
+
+    #[command]
+    async fn ping(ctx: FrameworkContext, _msg: &Message) {
+        OFFSET.store(ctx.content_offset, Ordering::SeqCst);
+    }
+
+    let mut conf = Configuration::new();
+    conf.prefix("!").command(ping);
+
+    let framework: Framework = Framework::new(conf.clone());
+
+    let ctx = test_util::context();
+    let result = block_on(framework.dispatch(&ctx, &test_util::message(" !ping")));
+    assert!(matches!(result, Err(Error::Dispatch(DispatchError::NormalMessage))));
+
+    conf.trim_leading(true);
+    let framework: Framework = Framework::new(conf);
+
+    for &content in &[" !ping", "\t!ping", " \t \u{200B}!ping"] {
+        assert!(block_on(framework.dispatch(&ctx, &test_util::message(content))).is_ok());
+    }
+
+    // The offset accounts for the ignored characters.
+    block_on(framework.dispatch(&ctx, &test_util::message("\t !ping"))).unwrap();
+    assert_eq!(OFFSET.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn prefix_matcher() {
+    /// Matches the name of the bot followed by a comma, regardless of its casing.
+    struct Nickname;
+
+    impl<D, E> PrefixMatcher<D, E> for Nickname {
+        fn match_prefix<'a, 'fut>(
+            &'fut self,
+            _ctx: &'fut PrefixContext<'_, D, E>,
+            _msg: &'fut Message,
+            content: &'a str,
+        ) -> BoxFuture<'fut, Option<(&'a str, &'a str)>>
+        where
+            'a: 'fut,
+        {
+            let pair = match content.get(..6) {
+                Some(prefix) if prefix.eq_ignore_ascii_case("botty,") => {
+                    Some((prefix, content[6..].trim_start()))
+                },
+                _ => None,
+            };
+
+            Box::pin(future::ready(pair))
+        }
+    }
+
+    #[command]
+    async fn ping(_ctx: FrameworkContext, _msg: &Message) {}
+
+    let mut conf = Configuration::new();
+    conf.prefix("!")
+        .prefix_matcher(Nickname)
+        .prefix_matcher(StaticMatcher::new(vec!["$"]))
+        .command(ping);
+
+    let framework: Framework = Framework::new(conf);
+
+    let ctx = test_util::context();
+    let run = |content: &str| block_on(framework.dispatch(&ctx, &test_util::message(content)));
+
+    assert!(run("!ping").is_ok());
+    assert!(run("Botty, ping").is_ok());
+    assert!(run("$ping").is_ok());
+    assert!(matches!(run("bot, ping"), Err(Error::Dispatch(DispatchError::NormalMessage))));
+}
@@ -0,0 +1,9 @@
+//! Tests of the compile errors of the procedural macros.
+
+#![cfg(feature = "macros")]
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
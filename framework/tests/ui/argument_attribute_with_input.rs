@@ -0,0 +1,13 @@
+use serenity_framework::prelude::*;
+
+#[command]
+async fn toggle(
+    _ctx: FrameworkContext,
+    _msg: &serenity::model::channel::Message,
+    #[optional(skip = "-")]
+    #[bool(strict)]
+    enabled: Option<bool>,
+) {
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use serenity::model::channel::Message;
+use serenity_framework::prelude::*;
+
+#[command]
+#[check(missing_check)]
+async fn ping(_ctx: FrameworkContext, _msg: &Message) {}
+
+fn main() {}
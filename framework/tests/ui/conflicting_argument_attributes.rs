@@ -0,0 +1,13 @@
+use serenity_framework::prelude::*;
+
+#[command]
+async fn react(
+    _ctx: FrameworkContext,
+    _msg: &serenity::model::channel::Message,
+    #[emoji]
+    #[rest]
+    emoji: String,
+) {
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use serenity_framework::prelude::*;
+
+#[command]
+async fn sum(
+    _ctx: FrameworkContext,
+    _msg: &serenity::model::channel::Message,
+    #[variadic(skip_invalid)]
+    #[variadic(max = 3)]
+    numbers: Vec<i64>,
+) {
+}
+
+fn main() {}
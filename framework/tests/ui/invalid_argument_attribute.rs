@@ -0,0 +1,11 @@
+use serenity_framework::prelude::*;
+
+#[command]
+async fn ping(
+    _ctx: FrameworkContext,
+    _msg: &serenity::model::channel::Message,
+    #[rest(all)] text: String,
+) {
+}
+
+fn main() {}